@@ -88,15 +88,22 @@ fn formatter_benchmarks(c: &mut Criterion) {
         changes: Vec::new(),
         attributes,
         applied: false,
+        replace: false,
+        span: None,
     };
     
     let small_plan = TerraformPlan {
         mode: PlanMode::Plan,
         summary: Summary { add: 1, change: 0, destroy: 0, read: 0 },
+        parsed_summary: Summary::default(),
         resources: vec![resource],
         data_sources: Vec::new(),
         warnings: Vec::new(),
         metadata: Metadata::default(),
+        suppressed: Vec::new(),
+        policy_results: Vec::new(),
+        annotations: Vec::new(),
+        cost: None,
     };
     
     group.bench_function("terminal_small", |b| {
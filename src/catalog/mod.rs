@@ -0,0 +1,120 @@
+use crate::config::parse_toml;
+use crate::query::glob;
+use crate::{ActionType, Resource, ResourceAnnotation, ResourceClass, Result, TerraformPlan};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+lazy_static! {
+    static ref BUILTIN: ResourceCatalog = ResourceCatalog::from_toml(include_str!("catalog.toml"))
+        .expect("the built-in resource catalog is valid");
+}
+
+/// Resource type patterns for one provider, by class. Patterns may use `*` and `?`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogEntry {
+    pub stateful: Vec<String>,
+    pub security_sensitive: Vec<String>,
+    pub network_critical: Vec<String>,
+    pub cheap_to_recreate: Vec<String>,
+}
+
+impl CatalogEntry {
+    fn patterns(&self) -> [(ResourceClass, &[String]); 4] {
+        [
+            (ResourceClass::Stateful, &self.stateful),
+            (ResourceClass::SecuritySensitive, &self.security_sensitive),
+            (ResourceClass::NetworkCritical, &self.network_critical),
+            (ResourceClass::CheapToRecreate, &self.cheap_to_recreate),
+        ]
+    }
+
+    fn extend(&mut self, other: &CatalogEntry) {
+        self.stateful.extend(other.stateful.iter().cloned());
+        self.security_sensitive.extend(other.security_sensitive.iter().cloned());
+        self.network_critical.extend(other.network_critical.iter().cloned());
+        self.cheap_to_recreate.extend(other.cheap_to_recreate.iter().cloned());
+    }
+}
+
+/// Classifies resource types as stateful, security-sensitive, network-critical
+/// or cheap to recreate, keyed by provider as in the TOML it is read from:
+///
+/// ```toml
+/// [aws]
+/// stateful = ["aws_db_instance", "aws_s3_bucket"]
+/// security_sensitive = ["aws_iam_*"]
+/// ```
+///
+/// `ResourceCatalog::default()` is the catalog that ships with tfdiff, covering
+/// AWS, Azure, GCP and Kubernetes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceCatalog {
+    providers: BTreeMap<String, CatalogEntry>,
+}
+
+impl Default for ResourceCatalog {
+    fn default() -> Self {
+        BUILTIN.clone()
+    }
+}
+
+impl ResourceCatalog {
+    pub fn from_toml(source: &str) -> Result<Self> {
+        Ok(ResourceCatalog { providers: parse_toml(source)? })
+    }
+
+    /// Add resource types, e.g. from the `[catalog]` table of the configuration file.
+    pub fn extend(&mut self, extensions: &BTreeMap<String, CatalogEntry>) {
+        for (provider, entry) in extensions {
+            self.providers.entry(provider.clone()).or_default().extend(entry);
+        }
+    }
+
+    /// Every class a resource type belongs to, in the order of `ResourceClass`.
+    pub fn classes(&self, type_name: &str) -> Vec<ResourceClass> {
+        let mut classes: Vec<ResourceClass> = self.providers.values()
+            .flat_map(CatalogEntry::patterns)
+            .filter(|(_, patterns)| patterns.iter().any(|pattern| glob(pattern, type_name)))
+            .map(|(class, _)| class)
+            .collect();
+        classes.sort();
+        classes.dedup();
+        classes
+    }
+
+    /// Notes on what the resource's change may cause, most serious first.
+    pub fn annotations(&self, resource: &Resource) -> Vec<ResourceAnnotation> {
+        let removed = matches!(resource.action, ActionType::Destroy) || resource.replace;
+        let changed = matches!(resource.action, ActionType::Create | ActionType::Update | ActionType::Destroy);
+        let modified = changed && resource.action != ActionType::Create;
+        let classes = self.classes(&resource.type_name);
+        let verb = resource.action_description();
+
+        classes.iter()
+            .filter_map(|class| {
+                let consequence = match class {
+                    ResourceClass::Stateful if removed => "data loss possible",
+                    ResourceClass::SecuritySensitive if changed => "check the access it grants",
+                    ResourceClass::NetworkCritical if modified => "connectivity may be interrupted",
+                    // Only reassuring when nothing else about the resource is at stake
+                    ResourceClass::CheapToRecreate if removed && classes.len() == 1 => "little lasting impact",
+                    _ => return None,
+                };
+                Some(ResourceAnnotation {
+                    address: resource.id.clone(),
+                    class: *class,
+                    message: format!("{} resource will be {} — {}", class, verb, consequence),
+                })
+            })
+            .collect()
+    }
+
+    /// Record the annotations of every resource in the plan.
+    pub fn annotate(&self, plan: &mut TerraformPlan) {
+        plan.annotations = plan.resources.iter()
+            .flat_map(|resource| self.annotations(resource))
+            .collect();
+    }
+}
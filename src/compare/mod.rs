@@ -0,0 +1,159 @@
+use crate::parser::parse_terraform_value;
+use crate::{ActionType, Resource, Summary, TerraformPlan};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// What a plan proposes for one attribute of a resource.
+///
+/// Creates only have an `after`, destroys only a `before`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedValue {
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// An attribute whose proposed change differs between the two plans.
+///
+/// `None` on one side means that plan does not touch the attribute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeDifference {
+    pub path: String,
+    pub previous: Option<ProposedValue>,
+    pub current: Option<ProposedValue>,
+}
+
+/// A resource present in both plans whose action or proposed attributes differ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceDifference {
+    pub address: String,
+    pub type_name: String,
+    pub previous_action: ActionType,
+    pub current_action: ActionType,
+    pub attributes: Vec<AttributeDifference>,
+}
+
+impl ResourceDifference {
+    pub fn action_changed(&self) -> bool {
+        self.previous_action != self.current_action
+    }
+}
+
+/// Differences between two plans, e.g. before and after addressing review comments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanComparison {
+    pub previous_summary: Summary,
+    pub current_summary: Summary,
+    /// Resources only the current plan touches
+    pub added: Vec<Resource>,
+    /// Resources only the previous plan touched
+    pub removed: Vec<Resource>,
+    pub changed: Vec<ResourceDifference>,
+    /// Resources both plans change in exactly the same way
+    pub unchanged: usize,
+}
+
+impl PlanComparison {
+    /// Whether both plans propose the same changes.
+    pub fn is_identical(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two plans resource by resource, matching on address.
+pub fn compare_plans(previous: &TerraformPlan, current: &TerraformPlan) -> PlanComparison {
+    let mut comparison = PlanComparison {
+        previous_summary: previous.summary.clone(),
+        current_summary: current.summary.clone(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+
+    for resource in &current.resources {
+        let Some(earlier) = previous.resources.iter().find(|earlier| earlier.id == resource.id) else {
+            comparison.added.push(resource.clone());
+            continue;
+        };
+
+        let attributes = compare_attributes(&proposed_values(earlier), &proposed_values(resource));
+        if earlier.action == resource.action && attributes.is_empty() {
+            comparison.unchanged += 1;
+        } else {
+            comparison.changed.push(ResourceDifference {
+                address: resource.id.clone(),
+                type_name: resource.type_name.clone(),
+                previous_action: earlier.action.clone(),
+                current_action: resource.action.clone(),
+                attributes,
+            });
+        }
+    }
+
+    comparison.removed = previous.resources.iter()
+        .filter(|earlier| !current.resources.iter().any(|resource| resource.id == earlier.id))
+        .cloned()
+        .collect();
+
+    comparison
+}
+
+/// The attribute changes a plan proposes for a resource, keyed by dotted path.
+pub fn proposed_values(resource: &Resource) -> BTreeMap<String, ProposedValue> {
+    let mut values = BTreeMap::new();
+
+    for change in &resource.changes {
+        values.insert(change.path.join("."), ProposedValue {
+            before: change.before.clone(),
+            after: change.after.clone(),
+        });
+    }
+
+    // Creates and destroys list their attributes rather than changes
+    if resource.changes.is_empty() {
+        for (key, value) in &resource.attributes {
+            values.insert(key.clone(), attribute_value(&resource.action, value));
+        }
+    }
+
+    values
+}
+
+fn attribute_value(action: &ActionType, value: &Value) -> ProposedValue {
+    // The text parser keeps attributes as display strings: `+ "x"`, `- "x"` or `old → new`
+    if let Value::String(text) = value {
+        if let Some((before, after)) = text.split_once(" → ") {
+            return ProposedValue { before: Some(parse_terraform_value(before)), after: Some(parse_terraform_value(after)) };
+        }
+        if let Some(added) = text.strip_prefix("+ ").or_else(|| text.strip_prefix("~ ")) {
+            return ProposedValue { before: None, after: Some(parse_terraform_value(added)) };
+        }
+        if let Some(removed) = text.strip_prefix("- ") {
+            return ProposedValue { before: Some(parse_terraform_value(removed)), after: None };
+        }
+    }
+
+    match action {
+        ActionType::Destroy => ProposedValue { before: Some(value.clone()), after: None },
+        _ => ProposedValue { before: None, after: Some(value.clone()) },
+    }
+}
+
+fn compare_attributes(
+    previous: &BTreeMap<String, ProposedValue>,
+    current: &BTreeMap<String, ProposedValue>,
+) -> Vec<AttributeDifference> {
+    let mut paths: Vec<&String> = previous.keys().chain(current.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths.into_iter()
+        .filter(|path| previous.get(*path) != current.get(*path))
+        .map(|path| AttributeDifference {
+            path: path.clone(),
+            previous: previous.get(path).cloned(),
+            current: current.get(path).cloned(),
+        })
+        .collect()
+}
@@ -0,0 +1,157 @@
+use crate::query::expression::regex_error_reason;
+use crate::{CatalogEntry, IgnoreRules, Policy, PriceTable, RedactionRules, Result, TfdiffError};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file.
+pub const CONFIG_FILE_NAME: &str = ".tfdiff.toml";
+
+/// Project settings shared by a team, read from `.tfdiff.toml`:
+///
+/// ```toml
+/// format = "markdown"
+/// group_by = "module"
+/// theme = "plain"
+/// width = 120
+/// prefix_patterns = ['^\[[a-z0-9-]+\]\s+']
+///
+/// [filter]
+/// exclude = ["aws_iam_*"]
+///
+/// [ignore]
+/// unknown_only_updates = true
+///
+/// [[ignore.rule]]
+/// attribute = "tags_all"
+///
+/// [redact]
+/// attributes = ["*password*"]
+///
+/// [[policy]]
+/// name = "protect-databases"
+/// actions = ["destroy", "replace"]
+/// resource_types = ["aws_db_instance"]
+///
+/// [catalog.aws]
+/// stateful = ["aws_memorydb_cluster"]
+///
+/// [[cost.price]]
+/// resource_types = ["aws_instance"]
+/// attribute = "instance_type"
+/// prices = { "m7i.large" = 73.58 }
+/// ```
+///
+/// Command line options and environment variables override these settings one by one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Output format name, as for `--format`
+    pub format: Option<String>,
+    /// Template file for the report, as for `--template`; relative to the configuration file
+    pub template: Option<PathBuf>,
+    /// Grouping name, as for `--group-by`
+    pub group_by: Option<String>,
+    /// Color theme name, as for `--theme`
+    pub theme: Option<String>,
+    /// Columns for the terminal report, as for `--width`
+    pub width: Option<usize>,
+    pub strict: Option<bool>,
+    pub hide_cosmetic: Option<bool>,
+    /// Stack or log prefixes to strip from each line, as regular expressions
+    pub prefix_patterns: Vec<String>,
+    pub filter: FilterConfig,
+    pub ignore: Option<IgnoreRules>,
+    pub redact: RedactionRules,
+    #[serde(rename = "policy")]
+    pub policies: Vec<Policy>,
+    /// Resource types to add to the built-in catalog, by provider
+    pub catalog: BTreeMap<String, CatalogEntry>,
+    /// Prices to use before the built-in ones, as in a `--price-file`
+    pub cost: Option<PriceTable>,
+}
+
+/// Default resource filters, named after the command line options.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterConfig {
+    pub actions: Vec<String>,
+    pub resource_types: Vec<String>,
+    pub addresses: Vec<String>,
+    pub modules: Vec<String>,
+    pub providers: Vec<String>,
+    pub changed_attributes: Vec<String>,
+    pub exclude: Vec<String>,
+    #[serde(rename = "where")]
+    pub where_expression: Option<String>,
+    #[serde(rename = "match")]
+    pub match_mode: Option<String>,
+}
+
+impl Config {
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let config: Config = parse_toml(source)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml_file(path, Self::from_toml)
+    }
+
+    /// Find `.tfdiff.toml` in `directory` or the closest of its parents.
+    pub fn discover(directory: &Path) -> Option<PathBuf> {
+        directory.ancestors()
+            .map(|ancestor| ancestor.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Check the settings that are not checked while deserializing.
+    pub fn validate(&self) -> Result<()> {
+        self.prefix_regexes()?;
+        if let Some(ignore) = &self.ignore {
+            ignore.validate()?;
+        }
+        self.redact.validate()?;
+        if let Some(cost) = &self.cost {
+            cost.validate()?;
+        }
+        self.policies.iter().try_for_each(Policy::validate)
+    }
+
+    pub fn prefix_regexes(&self) -> Result<Vec<Regex>> {
+        self.prefix_patterns.iter()
+            .map(|pattern| compile_pattern("prefix pattern", pattern))
+            .collect()
+    }
+}
+
+/// Compile a regular expression from a configuration file.
+pub(crate) fn compile_pattern(kind: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| TfdiffError::ConfigError(format!(
+        "invalid {} \"{}\": {}", kind, pattern, regex_error_reason(&e)
+    )))
+}
+
+/// Deserialize TOML, reporting errors with the line they occur on.
+pub(crate) fn parse_toml<T: DeserializeOwned>(source: &str) -> Result<T> {
+    toml::from_str(source).map_err(|e| {
+        let line = e.span().map(|span| source[..span.start].matches('\n').count() + 1);
+        match line {
+            Some(line) => TfdiffError::ConfigError(format!("line {}: {}", line, e.message())),
+            None => TfdiffError::ConfigError(e.message().to_string()),
+        }
+    })
+}
+
+/// Read a TOML file with `parse`, naming the file in any error.
+pub(crate) fn load_toml_file<T>(path: &Path, parse: impl FnOnce(&str) -> Result<T>) -> Result<T> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| TfdiffError::ConfigError(format!("{}: {}", path.display(), e)))?;
+    parse(&source).map_err(|e| match e {
+        TfdiffError::ConfigError(message) => TfdiffError::ConfigError(format!("{}: {}", path.display(), message)),
+        other => other,
+    })
+}
@@ -0,0 +1,300 @@
+use crate::compare::{proposed_values, ProposedValue};
+use crate::config::{load_toml_file, parse_toml};
+use crate::query::expression::lookup;
+use crate::query::glob;
+use crate::{ActionType, PlanCost, Resource, ResourceCost, Result, TerraformPlan, TfdiffError};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Currency of a price table that does not name one.
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+lazy_static! {
+    static ref BUILTIN: PriceTable = PriceTable::from_toml(include_str!("prices.toml"))
+        .expect("the built-in price table is valid");
+}
+
+/// Monthly prices of resource types, read from a TOML file:
+///
+/// ```toml
+/// currency = "USD"
+///
+/// [[price]]
+/// resource_types = ["aws_db_instance"]
+/// attribute = "instance_class"
+/// per_unit = { allocated_storage = 0.115 }
+///
+/// [price.prices]
+/// "db.t3.micro" = 12.41
+/// "db.t3.small" = 24.82
+///
+/// [[price]]
+/// resource_types = ["aws_nat_gateway"]
+/// monthly = 32.85
+/// ```
+///
+/// `PriceTable::builtin()` is the table that ships with tfdiff.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriceTable {
+    /// Currency of every price in the table, `USD` if not set
+    pub currency: Option<String>,
+    #[serde(rename = "price")]
+    pub rules: Vec<PriceRule>,
+}
+
+/// The monthly price of the resource types it selects:
+/// `(monthly + prices[attribute] + per_unit prices × attribute values) × count`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriceRule {
+    /// Resource type patterns; `*` and `?` wildcards work
+    pub resource_types: Vec<String>,
+    /// The attribute whose value picks the price from `prices`, e.g. `instance_type`
+    pub attribute: Option<String>,
+    pub prices: BTreeMap<String, f64>,
+    /// A flat price per resource
+    pub monthly: f64,
+    /// Prices per unit of numeric attributes, e.g. per GB of `allocated_storage`
+    pub per_unit: BTreeMap<String, f64>,
+    /// A numeric attribute the price is multiplied by, e.g. `node_count`
+    pub count: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Before,
+    After,
+}
+
+/// The attribute values a price is worked out from.
+struct PricedResource<'a> {
+    resource: &'a Resource,
+    values: BTreeMap<String, ProposedValue>,
+}
+
+impl PricedResource<'_> {
+    /// Creates and destroys list every attribute they set; updates only the ones they change.
+    fn lists_all_attributes(&self) -> bool {
+        matches!(self.resource.action, ActionType::Create | ActionType::Destroy)
+    }
+
+    fn changed(&self, path: &str) -> bool {
+        lookup(&self.values, path).is_some_and(|value| value.before != value.after)
+    }
+
+    fn value(&self, path: &str, side: Side) -> Option<Value> {
+        let value = lookup(&self.values, path)?;
+        match side {
+            Side::Before => value.before,
+            Side::After => value.after,
+        }
+    }
+
+    /// The text of the attribute whose value picks a price; lists count by their first element.
+    fn key(&self, path: &str, side: Side) -> std::result::Result<String, String> {
+        match self.value(path, side) {
+            Some(Value::String(text)) if text == "(known after apply)" => Err(format!("{} is not known until apply", path)),
+            Some(Value::String(text)) => Ok(text),
+            Some(Value::Array(items)) => match items.first() {
+                Some(Value::String(text)) => Ok(text.clone()),
+                _ => Err(format!("{} is empty", path)),
+            },
+            Some(Value::Number(number)) => Ok(number.to_string()),
+            _ => Err(format!("{} is not in the plan", path)),
+        }
+    }
+
+    /// A numeric attribute; `unset` is its value when a create or destroy leaves it out.
+    fn number(&self, path: &str, side: Side, unset: f64) -> std::result::Result<f64, String> {
+        match self.value(path, side) {
+            Some(Value::Number(number)) => number.as_f64().ok_or_else(|| format!("{} is not a number", path)),
+            Some(Value::String(text)) if text == "(known after apply)" => Err(format!("{} is not known until apply", path)),
+            Some(Value::String(text)) => text.trim().parse().map_err(|_| format!("{} is not a number", path)),
+            None | Some(Value::Null) if self.lists_all_attributes() => Ok(unset),
+            None | Some(Value::Null) => Err(format!("{} is not in the plan", path)),
+            Some(_) => Err(format!("{} is not a number", path)),
+        }
+    }
+}
+
+impl PriceRule {
+    fn validate(&self) -> Result<()> {
+        let Some(first) = self.resource_types.first() else {
+            return Err(TfdiffError::ConfigError("every price needs resource_types".to_string()));
+        };
+        let invalid = |message: &str| Err(TfdiffError::ConfigError(format!("price for '{}': {}", first, message)));
+        if self.attribute.is_some() && self.prices.is_empty() {
+            return invalid("attribute needs prices to pick from");
+        }
+        if self.attribute.is_none() && !self.prices.is_empty() {
+            return invalid("prices need an attribute to pick them by");
+        }
+        let mut amounts = std::iter::once(&self.monthly).chain(self.prices.values()).chain(self.per_unit.values());
+        if amounts.any(|amount| !amount.is_finite() || *amount < 0.0) {
+            return invalid("prices cannot be negative");
+        }
+        Ok(())
+    }
+
+    fn selects(&self, type_name: &str) -> bool {
+        self.resource_types.iter().any(|pattern| glob(pattern, type_name))
+    }
+
+    /// The price of the attribute value on one side.
+    fn listed_price(&self, resource: &PricedResource, side: Side) -> std::result::Result<f64, String> {
+        let Some(attribute) = &self.attribute else { return Ok(0.0) };
+        let key = resource.key(attribute, side)?;
+        self.prices.get(&key).copied()
+            .ok_or_else(|| format!("no price for {} \"{}\"", attribute, key))
+    }
+
+    /// The monthly price on one side of the change.
+    fn monthly_price(&self, resource: &PricedResource, side: Side) -> std::result::Result<f64, String> {
+        let mut price = self.monthly + self.listed_price(resource, side)?;
+        for (attribute, rate) in &self.per_unit {
+            price += rate * resource.number(attribute, side, 0.0)?;
+        }
+        let count = match &self.count {
+            Some(attribute) => resource.number(attribute, side, 1.0)?,
+            None => 1.0,
+        };
+        Ok(price * count)
+    }
+
+    /// The difference an update makes, from the attributes it changes; the
+    /// ones it leaves alone are not in the plan but cancel out.
+    fn update_delta(&self, resource: &PricedResource) -> std::result::Result<f64, String> {
+        if let Some(count) = self.count.as_ref().filter(|count| resource.changed(count)) {
+            return Err(format!("{} changes, but not every price it multiplies is in the plan", count));
+        }
+        let mut delta = 0.0;
+        if self.attribute.as_ref().is_some_and(|attribute| resource.changed(attribute)) {
+            delta += self.listed_price(resource, Side::After)? - self.listed_price(resource, Side::Before)?;
+        }
+        for (attribute, rate) in self.per_unit.iter().filter(|(attribute, _)| resource.changed(attribute)) {
+            let number = |side| match resource.value(attribute, side) {
+                None | Some(Value::Null) => Ok(0.0),
+                Some(_) => resource.number(attribute, side, 0.0),
+            };
+            delta += rate * (number(Side::After)? - number(Side::Before)?);
+        }
+        match &self.count {
+            Some(count) if delta != 0.0 => Err(format!("{} is not in the plan", count)),
+            _ => Ok(delta),
+        }
+    }
+
+    fn touches_price(&self, resource: &PricedResource) -> bool {
+        self.attribute.iter().chain(self.per_unit.keys()).chain(self.count.iter())
+            .any(|attribute| resource.changed(attribute))
+    }
+}
+
+impl PriceTable {
+    /// The prices that ship with tfdiff.
+    pub fn builtin() -> Self {
+        BUILTIN.clone()
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let table: PriceTable = parse_toml(source)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml_file(path, Self::from_toml)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.rules.iter().try_for_each(PriceRule::validate)
+    }
+
+    pub fn currency(&self) -> &str {
+        self.currency.as_deref().unwrap_or(DEFAULT_CURRENCY)
+    }
+
+    /// Put the prices of `other` ahead of these ones, so they take precedence.
+    /// A table in another currency replaces these prices instead.
+    pub fn extend(&mut self, other: &PriceTable) {
+        if other.currency() != self.currency() {
+            *self = other.clone();
+            return;
+        }
+        self.rules.splice(0..0, other.rules.iter().cloned());
+    }
+
+    /// The first rule that prices the resource type.
+    pub fn rule(&self, type_name: &str) -> Option<&PriceRule> {
+        self.rules.iter().find(|rule| rule.selects(type_name))
+    }
+
+    /// What the change does to the resource's monthly price. `None` if the
+    /// type has no price, it is not changed, or an update leaves the price alone.
+    pub fn estimate_resource(&self, resource: &Resource) -> Option<ResourceCost> {
+        let rule = self.rule(&resource.type_name)?;
+        let priced = PricedResource { resource, values: proposed_values(resource) };
+
+        let (before, after, delta) = match resource.action {
+            ActionType::Create => {
+                let after = rule.monthly_price(&priced, Side::After);
+                (Ok(0.0), after.clone(), after)
+            }
+            ActionType::Destroy => {
+                let before = rule.monthly_price(&priced, Side::Before);
+                (before.clone(), Ok(0.0), before.map(|price| -price))
+            }
+            ActionType::Update if rule.touches_price(&priced) => {
+                let before = rule.monthly_price(&priced, Side::Before);
+                let after = rule.monthly_price(&priced, Side::After);
+                let delta = match (&before, &after) {
+                    (Ok(before), Ok(after)) => Ok(after - before),
+                    _ => rule.update_delta(&priced),
+                };
+                (before, after, delta)
+            }
+            ActionType::Update | ActionType::Read | ActionType::NoOp => return None,
+        };
+
+        let note = delta.clone().err();
+        Some(ResourceCost {
+            address: resource.id.clone(),
+            before: before.ok().map(round_cents),
+            after: after.ok().map(round_cents),
+            delta: delta.ok().map(round_cents),
+            note,
+        })
+    }
+
+    /// The monthly cost of the plan's changes; `None` if nothing it changes has a price.
+    pub fn estimate(&self, plan: &TerraformPlan) -> Option<PlanCost> {
+        let resources: Vec<ResourceCost> = plan.resources.iter()
+            .filter_map(|resource| self.estimate_resource(resource))
+            .collect();
+        if resources.is_empty() {
+            return None;
+        }
+
+        let total = |price: fn(&ResourceCost) -> Option<f64>| round_cents(resources.iter().filter_map(price).sum());
+        Some(PlanCost {
+            currency: self.currency().to_string(),
+            before: total(|cost| cost.before),
+            after: total(|cost| cost.after),
+            delta: total(|cost| cost.delta),
+            resources,
+        })
+    }
+
+    /// Record the cost estimate of the plan.
+    pub fn price(&self, plan: &mut TerraformPlan) {
+        plan.cost = self.estimate(plan);
+    }
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
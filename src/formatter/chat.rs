@@ -0,0 +1,253 @@
+use crate::formatter::pr_comment::{counts, mode_name, risk_emoji};
+use crate::formatter::registry::{FormatOptions, Formatter};
+use crate::formatter::{format_cost_line, format_policy_counts};
+use crate::risk::{assess_plan, ResourceRisk};
+use crate::{ActionType, PolicyOutcome, Resource, Result, RiskLevel, TerraformPlan};
+use serde_json::{json, Value};
+
+/// Resources listed in a chat message; the rest are counted.
+const MAX_RESOURCES: usize = 25;
+
+/// Risky resources called out in a chat message.
+const MAX_RISKS: usize = 3;
+
+/// Slack rejects section text longer than 3,000 characters.
+const SLACK_TEXT_LIMIT: usize = 3_000;
+
+/// Teams cards are limited to 28 KB, so lists are cut well before that.
+const TEAMS_TEXT_LIMIT: usize = 8_000;
+
+/// What a chat message says about a plan, before it is laid out for Slack or Teams.
+struct Digest {
+    title: String,
+    counts: [(&'static str, usize); 3],
+    risk: Option<String>,
+    risks: Vec<String>,
+    policy: Option<String>,
+    policies: Vec<String>,
+    cost: Option<String>,
+    resources: Vec<String>,
+}
+
+impl Digest {
+    /// Addresses are wrapped in `code` marks, which Teams does not render.
+    fn new(plan: &TerraformPlan, code: fn(&str) -> String) -> Self {
+        let risk = assess_plan(plan);
+        let mut risky: Vec<(&Resource, &ResourceRisk)> = plan.resources.iter().zip(&risk.resources)
+            .filter(|(_, resource_risk)| resource_risk.level >= RiskLevel::Medium)
+            .collect();
+        risky.sort_by_key(|(_, resource_risk)| std::cmp::Reverse(resource_risk.score));
+
+        let policies = plan.policy_results.iter()
+            .filter(|result| result.outcome != PolicyOutcome::Pass)
+            .map(|result| {
+                let outcome = if result.outcome == PolicyOutcome::Fail { "🛑 failed" } else { "⚠️ warning" };
+                let mut line = format!("{} {}", outcome, code(&result.policy));
+                if let Some(description) = &result.description {
+                    line.push_str(&format!(" — {}", description));
+                }
+                if !result.addresses.is_empty() {
+                    let addresses: Vec<String> = result.addresses.iter().map(|address| code(address)).collect();
+                    line.push_str(&format!(": {}", addresses.join(", ")));
+                }
+                line
+            })
+            .collect();
+
+        Digest {
+            title: format!("Terraform {}: {}", mode_name(plan), counts(&plan.summary)),
+            counts: [("➕ Add", plan.summary.add), ("🔄 Change", plan.summary.change), ("❌ Destroy", plan.summary.destroy)],
+            risk: (!plan.resources.is_empty()).then(|| format!("{} risk ({}/100)", risk.level, risk.score)),
+            risks: risky.iter().take(MAX_RISKS)
+                .map(|(resource, resource_risk)| format!("{} {} — {} ({})",
+                    risk_emoji(resource_risk.level), code(&resource.id), resource_risk.score, resource_risk.factors.join(", ")))
+                .collect(),
+            policy: format_policy_counts(&plan.policy_results),
+            policies,
+            cost: plan.cost.as_ref().map(format_cost_line),
+            resources: plan.resources.iter()
+                .map(|resource| format!("{} {} will be {}", action_emoji(resource), code(&resource.id), resource.action_description()))
+                .collect(),
+        }
+    }
+}
+
+/// Join as many lines as fit in `limit` characters and `MAX_RESOURCES`
+/// lines, counting the ones left out on a last line.
+fn truncated_list(lines: &[String], limit: usize) -> String {
+    let mut text = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let hidden = lines.len() - index;
+        let more = format!("…and {} more", hidden);
+        let fits = text.chars().count() + line.chars().count() + 1 + if hidden > 1 { more.len() + 1 } else { 0 } <= limit;
+        if index == MAX_RESOURCES || !fits {
+            text.push_str(&more);
+            return text;
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.trim_end().to_string()
+}
+
+pub(crate) fn action_emoji(resource: &Resource) -> &'static str {
+    match resource.action {
+        ActionType::Update if resource.replace => "♻️",
+        ActionType::Create => "➕",
+        ActionType::Update => "🔄",
+        ActionType::Destroy => "❌",
+        ActionType::Read => "📖",
+        ActionType::NoOp => "⭕",
+    }
+}
+
+/// Escape the characters Slack's mrkdwn treats as control sequences.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn slack_code(text: &str) -> String {
+    format!("`{}`", text.replace('`', "'"))
+}
+
+/// Render the plan as a Slack Block Kit message for an incoming webhook.
+pub fn format_slack_output(plan: &TerraformPlan) -> Result<String> {
+    let digest = Digest::new(plan, slack_code);
+    let section = |text: String| json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } });
+
+    let mut fields: Vec<Value> = digest.counts.iter()
+        .map(|(label, count)| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", label, count) }))
+        .collect();
+    if let Some(risk) = &digest.risk {
+        fields.push(json!({ "type": "mrkdwn", "text": format!("*⚠️ Risk*\n{}", risk) }));
+    }
+    let mut blocks = vec![
+        json!({ "type": "header", "text": { "type": "plain_text", "text": truncate(&digest.title, 150), "emoji": true } }),
+        json!({ "type": "section", "fields": fields }),
+    ];
+
+    if !digest.risks.is_empty() {
+        blocks.push(section(truncate(&format!("*Riskiest changes*\n{}", slack_escape(&digest.risks.join("\n"))), SLACK_TEXT_LIMIT)));
+    }
+    if let Some(policy) = &digest.policy {
+        let mut text = format!("*Policy checks:* {}", policy);
+        if !digest.policies.is_empty() {
+            text.push('\n');
+            text.push_str(&digest.policies.join("\n"));
+        }
+        blocks.push(section(truncate(&slack_escape(&text), SLACK_TEXT_LIMIT)));
+    }
+    if let Some(cost) = &digest.cost {
+        blocks.push(section(slack_escape(&format!("*💰 Monthly cost:* {}", cost))));
+    }
+    if !digest.resources.is_empty() {
+        blocks.push(json!({ "type": "divider" }));
+        let resources: Vec<String> = digest.resources.iter().map(|line| slack_escape(line)).collect();
+        blocks.push(section(truncated_list(&resources, SLACK_TEXT_LIMIT)));
+    }
+    blocks.push(json!({ "type": "context", "elements": [{ "type": "mrkdwn", "text": "Generated by tfdiff" }] }));
+
+    let message = json!({
+        "text": digest.title,
+        "blocks": blocks,
+    });
+    Ok(serde_json::to_string_pretty(&message)?)
+}
+
+/// Render the plan as a Microsoft Teams message with an Adaptive Card, for
+/// an incoming webhook or workflow.
+pub fn format_teams_output(plan: &TerraformPlan) -> Result<String> {
+    let digest = Digest::new(plan, |text| text.to_string());
+    let text = |text: String| json!({ "type": "TextBlock", "text": text, "wrap": true });
+    let heading = |title: &str| json!({ "type": "TextBlock", "text": title, "weight": "Bolder", "spacing": "Medium" });
+
+    let mut facts: Vec<Value> = digest.counts.iter()
+        .map(|(label, count)| json!({ "title": label, "value": count.to_string() }))
+        .collect();
+    if let Some(risk) = &digest.risk {
+        facts.push(json!({ "title": "⚠️ Risk", "value": risk }));
+    }
+    if let Some(policy) = &digest.policy {
+        facts.push(json!({ "title": "📋 Policies", "value": policy }));
+    }
+    if let Some(cost) = &digest.cost {
+        facts.push(json!({ "title": "💰 Monthly cost", "value": cost }));
+    }
+    let mut body = vec![
+        json!({ "type": "TextBlock", "text": digest.title, "size": "Large", "weight": "Bolder", "wrap": true }),
+        json!({ "type": "FactSet", "facts": facts }),
+    ];
+
+    if !digest.risks.is_empty() {
+        body.push(heading("Riskiest changes"));
+        body.push(text(digest.risks.join("\n\n")));
+    }
+    if !digest.policies.is_empty() {
+        body.push(heading("Policy checks"));
+        body.push(text(truncate(&digest.policies.join("\n\n"), TEAMS_TEXT_LIMIT)));
+    }
+    if !digest.resources.is_empty() {
+        body.push(heading("Changes"));
+        body.push(text(truncated_list(&digest.resources, TEAMS_TEXT_LIMIT).replace('\n', "\n\n")));
+    }
+    body.push(json!({ "type": "TextBlock", "text": "Generated by tfdiff", "isSubtle": true, "size": "Small" }));
+
+    let message = json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "msteams": { "width": "Full" },
+                "body": body,
+            },
+        }],
+    });
+    Ok(serde_json::to_string_pretty(&message)?)
+}
+
+/// Cut text to at most `limit` characters, ending with `…` if it was longer.
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+/// A Slack message, as from `format_slack_output`.
+pub struct SlackFormatter;
+
+impl Formatter for SlackFormatter {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    fn description(&self) -> &str {
+        "Slack Block Kit message for an incoming webhook"
+    }
+
+    fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+        format_slack_output(plan)
+    }
+}
+
+/// A Teams message, as from `format_teams_output`.
+pub struct TeamsFormatter;
+
+impl Formatter for TeamsFormatter {
+    fn name(&self) -> &str {
+        "teams"
+    }
+
+    fn description(&self) -> &str {
+        "Microsoft Teams Adaptive Card message for an incoming webhook"
+    }
+
+    fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+        format_teams_output(plan)
+    }
+}
@@ -0,0 +1,362 @@
+use crate::compare::{AttributeDifference, PlanComparison, ProposedValue};
+use crate::formatter::html::html_escape;
+use crate::formatter::layout::{DEFAULT_WIDTH, MIN_WIDTH};
+use crate::formatter::terminal::{format_summary_line, header_line};
+use crate::{ActionType, Result};
+use colored::*;
+
+pub fn format_comparison_terminal_output(comparison: &PlanComparison) -> String {
+    format_comparison_terminal_output_with_width(comparison, DEFAULT_WIDTH)
+}
+
+/// The report laid out in `width` columns, at least `MIN_WIDTH`.
+pub fn format_comparison_terminal_output_with_width(comparison: &PlanComparison, width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
+    let mut output = String::new();
+
+    output.push_str(&title_box("TERRAFORM PLAN COMPARISON", width));
+    output.push('\n');
+
+    output.push_str("📊 ");
+    output.push_str(&"SUMMARY".bright_white().bold().to_string());
+    output.push('\n');
+    output.push_str(&"─".repeat(width).dimmed().to_string());
+    output.push_str("\n\n");
+    output.push_str(&format!("  {}  {}\n", "Previous:".dimmed(), summary_or_none(&comparison.previous_summary)));
+    output.push_str(&format!("  {}  {}\n\n", "Current: ".dimmed(), summary_or_none(&comparison.current_summary)));
+
+    if comparison.is_identical() {
+        output.push_str(&format!("  {} Both plans propose the same changes ({} resources)\n\n",
+            "✔".bright_green(),
+            comparison.unchanged
+        ));
+        return output;
+    }
+
+    output.push_str(&format!("  {} added  {} removed  {} changed  {} unchanged\n\n",
+        comparison.added.len().to_string().bright_green().bold(),
+        comparison.removed.len().to_string().bright_red().bold(),
+        comparison.changed.len().to_string().bright_yellow().bold(),
+        comparison.unchanged.to_string().dimmed()
+    ));
+
+    if !comparison.added.is_empty() {
+        output.push_str(&section_header("➕", "ADDED TO PLAN", width));
+        for resource in &comparison.added {
+            output.push_str(&format!("  {} {} {}\n", "+".bright_green().bold(), action_label(&resource.action), resource.id.bright_white()));
+        }
+        output.push('\n');
+    }
+
+    if !comparison.removed.is_empty() {
+        output.push_str(&section_header("➖", "REMOVED FROM PLAN", width));
+        for resource in &comparison.removed {
+            output.push_str(&format!("  {} {} {}\n", "-".bright_red().bold(), action_label(&resource.action), resource.id.bright_white()));
+        }
+        output.push('\n');
+    }
+
+    if !comparison.changed.is_empty() {
+        output.push_str(&section_header("🔀", "CHANGED BETWEEN PLANS", width));
+        for difference in &comparison.changed {
+            output.push_str(&format!("  {} {}\n", "~".bright_yellow().bold(), difference.address.bright_white().bold()));
+            if difference.action_changed() {
+                output.push_str(&format!("    {} {} → {}\n",
+                    "action:".dimmed(),
+                    action_label(&difference.previous_action),
+                    action_label(&difference.current_action)
+                ));
+            }
+            for attribute in &difference.attributes {
+                output.push_str(&format!("    {}\n", attribute.path.bright_cyan()));
+                output.push_str(&format!("      {} {}\n", "previous:".dimmed(), describe(attribute.previous.as_ref()).bright_red()));
+                output.push_str(&format!("      {} {}\n", "current: ".dimmed(), describe(attribute.current.as_ref()).bright_green()));
+            }
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+pub fn format_comparison_json_output(comparison: &PlanComparison) -> Result<String> {
+    Ok(serde_json::to_string_pretty(comparison)?)
+}
+
+pub fn format_comparison_markdown_output(comparison: &PlanComparison) -> String {
+    let mut md = String::new();
+
+    md.push_str("# Terraform Plan Comparison\n\n");
+    md.push_str("## Summary\n\n");
+    md.push_str("| Plan | Add | Change | Destroy |\n");
+    md.push_str("|------|-----|--------|---------|\n");
+    for (label, summary) in [("Previous", &comparison.previous_summary), ("Current", &comparison.current_summary)] {
+        md.push_str(&format!("| {} | {} | {} | {} |\n", label, summary.add, summary.change, summary.destroy));
+    }
+    md.push('\n');
+
+    if comparison.is_identical() {
+        md.push_str(&format!("✅ Both plans propose the same changes ({} resources).\n", comparison.unchanged));
+        return md;
+    }
+
+    md.push_str(&format!("**{}** added, **{}** removed, **{}** changed, **{}** unchanged\n\n",
+        comparison.added.len(), comparison.removed.len(), comparison.changed.len(), comparison.unchanged));
+
+    if !comparison.added.is_empty() {
+        md.push_str("## ➕ Added to Plan\n\n");
+        for resource in &comparison.added {
+            md.push_str(&format!("- {} `{}`\n", action_text(&resource.action), resource.id));
+        }
+        md.push('\n');
+    }
+
+    if !comparison.removed.is_empty() {
+        md.push_str("## ➖ Removed from Plan\n\n");
+        for resource in &comparison.removed {
+            md.push_str(&format!("- {} `{}`\n", action_text(&resource.action), resource.id));
+        }
+        md.push('\n');
+    }
+
+    if !comparison.changed.is_empty() {
+        md.push_str("## 🔀 Changed Between Plans\n\n");
+        for difference in &comparison.changed {
+            md.push_str(&format!("### `{}`\n\n", difference.address));
+            if difference.action_changed() {
+                md.push_str(&format!("Action: **{}** → **{}**\n\n",
+                    action_text(&difference.previous_action), action_text(&difference.current_action)));
+            }
+            if !difference.attributes.is_empty() {
+                md.push_str("| Attribute | Previous | Current |\n");
+                md.push_str("|-----------|----------|---------|\n");
+                for attribute in &difference.attributes {
+                    md.push_str(&format!("| `{}` | {} | {} |\n",
+                        attribute.path,
+                        markdown_cell(attribute.previous.as_ref()),
+                        markdown_cell(attribute.current.as_ref())
+                    ));
+                }
+                md.push('\n');
+            }
+        }
+    }
+
+    md
+}
+
+pub fn format_comparison_html_output(comparison: &PlanComparison) -> String {
+    let mut html = String::new();
+
+    html.push_str(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Terraform Plan Comparison</title>
+    <style>
+        body {
+            font-family: 'SF Mono', Monaco, 'Cascadia Code', 'Roboto Mono', Consolas, 'Courier New', monospace;
+            background-color: #0d1117;
+            color: #c9d1d9;
+            line-height: 1.6;
+            margin: 0;
+            padding: 20px;
+        }
+
+        .container {
+            max-width: 1400px;
+            margin: 0 auto;
+        }
+
+        h1 {
+            color: #58a6ff;
+            font-size: 28px;
+        }
+
+        h2 {
+            font-size: 18px;
+            margin-top: 30px;
+        }
+
+        .panel {
+            background-color: #161b22;
+            border: 1px solid #30363d;
+            border-radius: 12px;
+            padding: 20px;
+            margin-bottom: 20px;
+        }
+
+        table {
+            width: 100%;
+            border-collapse: collapse;
+            font-size: 14px;
+        }
+
+        th, td {
+            text-align: left;
+            padding: 6px 12px;
+            border-bottom: 1px solid #30363d;
+            vertical-align: top;
+        }
+
+        th {
+            color: #8b949e;
+            font-weight: normal;
+        }
+
+        .added { color: #3fb950; }
+        .removed { color: #f85149; }
+        .changed { color: #d29922; }
+        .muted { color: #8b949e; }
+
+        .address {
+            color: #ffffff;
+            font-weight: bold;
+        }
+
+        .value {
+            white-space: pre-wrap;
+            word-break: break-all;
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>🔀 Terraform Plan Comparison</h1>
+        <div class="panel">
+            <table>
+                <tr><th>Plan</th><th>Add</th><th>Change</th><th>Destroy</th></tr>"#);
+
+    for (label, summary) in [("Previous", &comparison.previous_summary), ("Current", &comparison.current_summary)] {
+        html.push_str(&format!(r#"
+                <tr><td>{}</td><td class="added">{}</td><td class="changed">{}</td><td class="removed">{}</td></tr>"#,
+            label, summary.add, summary.change, summary.destroy));
+    }
+
+    html.push_str(&format!(r#"
+            </table>
+            <p class="muted">{} added, {} removed, {} changed, {} unchanged</p>
+        </div>"#,
+        comparison.added.len(), comparison.removed.len(), comparison.changed.len(), comparison.unchanged));
+
+    for (title, class, resources) in [("➕ Added to Plan", "added", &comparison.added), ("➖ Removed from Plan", "removed", &comparison.removed)] {
+        if resources.is_empty() {
+            continue;
+        }
+        html.push_str(&format!(r#"
+        <h2 class="{}">{}</h2>
+        <div class="panel">
+            <table>"#, class, title));
+        for resource in resources {
+            html.push_str(&format!(r#"
+                <tr><td>{}</td><td class="address">{}</td></tr>"#,
+                action_text(&resource.action), html_escape(&resource.id)));
+        }
+        html.push_str(r#"
+            </table>
+        </div>"#);
+    }
+
+    if !comparison.changed.is_empty() {
+        html.push_str(r#"
+        <h2 class="changed">🔀 Changed Between Plans</h2>"#);
+        for difference in &comparison.changed {
+            html.push_str(&format!(r#"
+        <div class="panel">
+            <div class="address">{}</div>"#, html_escape(&difference.address)));
+            if difference.action_changed() {
+                html.push_str(&format!(r#"
+            <p>Action: <span class="removed">{}</span> → <span class="added">{}</span></p>"#,
+                    action_text(&difference.previous_action), action_text(&difference.current_action)));
+            }
+            if !difference.attributes.is_empty() {
+                html.push_str(r#"
+            <table>
+                <tr><th>Attribute</th><th>Previous</th><th>Current</th></tr>"#);
+                for attribute in &difference.attributes {
+                    html.push_str(&html_attribute_row(attribute));
+                }
+                html.push_str(r#"
+            </table>"#);
+            }
+            html.push_str(r#"
+        </div>"#);
+        }
+    }
+
+    html.push_str(r#"
+    </div>
+</body>
+</html>"#);
+
+    html
+}
+
+fn html_attribute_row(attribute: &AttributeDifference) -> String {
+    format!(r#"
+                <tr><td>{}</td><td class="value removed">{}</td><td class="value added">{}</td></tr>"#,
+        html_escape(&attribute.path),
+        html_escape(&describe(attribute.previous.as_ref())),
+        html_escape(&describe(attribute.current.as_ref()))
+    )
+}
+
+/// The boxed title at the top of a report, `width` columns wide.
+pub(crate) fn title_box(title: &str, width: usize) -> String {
+    format!("{}\n{}{}\n",
+        format!("╔{}╗", "═".repeat(width - 2)).bright_blue(),
+        header_line(title, width, |text| text.bright_cyan()),
+        format!("╚{}╝", "═".repeat(width - 2)).bright_blue()
+    )
+}
+
+pub(crate) fn section_header(icon: &str, title: &str, width: usize) -> String {
+    format!("{} {}\n{}\n\n", icon, title.bright_white().bold(), "─".repeat(width).dimmed())
+}
+
+pub(crate) fn summary_or_none(summary: &crate::Summary) -> String {
+    let line = format_summary_line(summary);
+    if line.is_empty() { "no changes".dimmed().to_string() } else { line }
+}
+
+pub(crate) fn action_text(action: &ActionType) -> &'static str {
+    match action {
+        ActionType::Create => "CREATE",
+        ActionType::Update => "UPDATE",
+        ActionType::Destroy => "DESTROY",
+        ActionType::Read => "READ",
+        ActionType::NoOp => "NO-OP",
+    }
+}
+
+pub(crate) fn action_label(action: &ActionType) -> ColoredString {
+    let text = action_text(action);
+    match action {
+        ActionType::Create => text.bright_green(),
+        ActionType::Update => text.bright_yellow(),
+        ActionType::Destroy => text.bright_red(),
+        ActionType::Read => text.bright_cyan(),
+        ActionType::NoOp => text.dimmed(),
+    }
+}
+
+/// One side of an attribute difference as `before → after`.
+fn describe(proposed: Option<&ProposedValue>) -> String {
+    let value = |value: &serde_json::Value| match value {
+        serde_json::Value::String(text) if text.starts_with('(') => text.clone(),
+        other => other.to_string(),
+    };
+
+    match proposed {
+        None => "(not changed)".to_string(),
+        Some(ProposedValue { before: None, after: Some(after) }) => format!("+ {}", value(after)),
+        Some(ProposedValue { before: Some(before), after: None }) => format!("- {}", value(before)),
+        Some(ProposedValue { before: Some(before), after: Some(after) }) => format!("{} → {}", value(before), value(after)),
+        Some(ProposedValue { before: None, after: None }) => "null".to_string(),
+    }
+}
+
+fn markdown_cell(proposed: Option<&ProposedValue>) -> String {
+    format!("`{}`", describe(proposed).replace('|', "\\|").replace('`', "'"))
+}
@@ -0,0 +1,124 @@
+use crate::{TerraformPlan, Resource, ActionType};
+use crate::parser::clean_ansi_codes;
+use colored::*;
+
+const RAW_COLUMN_WIDTH: usize = 60;
+
+/// Show each parsed resource next to the raw input lines it was parsed from.
+pub fn format_explain_output(plan: &TerraformPlan, input: &str) -> String {
+    let raw_lines: Vec<&str> = input.lines().collect();
+    let mut output = String::new();
+
+    for resource in &plan.resources {
+        output.push_str(&format_explained_resource(resource, &raw_lines));
+        output.push('\n');
+    }
+
+    for warning in &plan.warnings {
+        let location = match &warning.span {
+            Some(span) => format_span(span.start_line, span.end_line),
+            None => "unknown location".to_string(),
+        };
+        output.push_str(&format!("{} {} ({})\n",
+            "⚠".bright_yellow(),
+            warning.message.bright_yellow(),
+            location.dimmed()
+        ));
+    }
+
+    output
+}
+
+fn format_explained_resource(resource: &Resource, raw_lines: &[&str]) -> String {
+    let mut output = String::new();
+
+    let location = match &resource.span {
+        Some(span) => {
+            let mut location = format_span(span.start_line, span.end_line);
+            if let Some(prefix) = &span.prefix {
+                location.push_str(&format!(", prefix {}", prefix));
+            }
+            location
+        }
+        None => "no source span".to_string(),
+    };
+    output.push_str(&format!("{} {}\n", resource.id.bright_white().bold(), format!("({})", location).dimmed()));
+    output.push_str(&"─".repeat(RAW_COLUMN_WIDTH * 2).dimmed().to_string());
+    output.push('\n');
+
+    let left: Vec<String> = match &resource.span {
+        Some(span) => (span.start_line..=span.end_line)
+            .filter_map(|n| raw_lines.get(n - 1).map(|raw| (n, *raw)))
+            .map(|(n, raw)| format!("{:>5} │ {}", n, clean_ansi_codes(raw)))
+            .collect(),
+        None => Vec::new(),
+    };
+    let right = parsed_representation(resource);
+
+    for i in 0..left.len().max(right.len()) {
+        let raw = left.get(i).map(String::as_str).unwrap_or("");
+        let parsed = right.get(i).map(String::as_str).unwrap_or("");
+        output.push_str(&format!("{} {} {}\n",
+            fit_column(raw, RAW_COLUMN_WIDTH).dimmed(),
+            "┃".bright_blue(),
+            parsed
+        ));
+    }
+
+    output
+}
+
+fn parsed_representation(resource: &Resource) -> Vec<String> {
+    let action = match resource.action {
+        ActionType::Create => "create",
+        ActionType::Update => "update",
+        ActionType::Destroy => "destroy",
+        ActionType::Read => "read",
+        ActionType::NoOp => "no-op",
+    };
+
+    let mut lines = vec![
+        format!("action: {}", action),
+        format!("type: {}", resource.type_name),
+        format!("name: {}", resource.name),
+    ];
+
+    for change in &resource.changes {
+        let line = change.span.as_ref()
+            .map(|span| format!(" (line {})", span.start_line))
+            .unwrap_or_default();
+        let value = match (&change.before, &change.after) {
+            (Some(before), Some(after)) => format!("{} -> {}", before, after),
+            (None, Some(after)) => format!("+ {}", after),
+            (Some(before), None) => format!("- {}", before),
+            (None, None) => "(none)".to_string(),
+        };
+        lines.push(format!("change {}: {}{}", change.path.join("."), value, line));
+    }
+
+    let mut keys: Vec<&String> = resource.attributes.keys().collect();
+    keys.sort();
+    for key in keys {
+        lines.push(format!("{} = {}", key, resource.attributes[key]));
+    }
+
+    lines
+}
+
+fn format_span(start_line: usize, end_line: usize) -> String {
+    if start_line == end_line {
+        format!("line {}", start_line)
+    } else {
+        format!("lines {}-{}", start_line, end_line)
+    }
+}
+
+fn fit_column(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count > width {
+        let truncated: String = text.chars().take(width - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        format!("{}{}", text, " ".repeat(width - count))
+    }
+}
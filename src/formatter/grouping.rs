@@ -0,0 +1,45 @@
+use crate::{ActionType, Resource};
+
+/// How resources are grouped in the terminal, HTML and Markdown reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    #[default]
+    None,
+    Provider,
+    Module,
+    Type,
+    Action,
+}
+
+/// Group resources by the given key, keeping groups and resources in plan order.
+///
+/// With `GroupBy::None` everything ends up in a single group with an empty label.
+pub fn group_resources(resources: &[Resource], group_by: GroupBy) -> Vec<(String, Vec<&Resource>)> {
+    let mut groups: Vec<(String, Vec<&Resource>)> = Vec::new();
+
+    for resource in resources {
+        let label = group_label(resource, group_by);
+        match groups.iter_mut().find(|(existing, _)| *existing == label) {
+            Some((_, members)) => members.push(resource),
+            None => groups.push((label, vec![resource])),
+        }
+    }
+
+    groups
+}
+
+fn group_label(resource: &Resource, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::None => String::new(),
+        GroupBy::Provider => resource.provider.clone(),
+        GroupBy::Module => resource.module().unwrap_or_else(|| "root module".to_string()),
+        GroupBy::Type => resource.type_name.clone(),
+        GroupBy::Action => match resource.action {
+            ActionType::Create => "create",
+            ActionType::Update => "update",
+            ActionType::Destroy => "destroy",
+            ActionType::Read => "read",
+            ActionType::NoOp => "no-op",
+        }.to_string(),
+    }
+}
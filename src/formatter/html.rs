@@ -1,9 +1,24 @@
-use crate::{TerraformPlan, ActionType};
+use crate::{TerraformPlan, ActionType, Result};
+use crate::formatter::registry::{FormatOptions, Formatter, Verbosity};
+use crate::formatter::grouping::{GroupBy, group_resources};
+use crate::semantic::{classify_change, ChangeClass};
+use crate::risk::assess_plan;
 use serde_json::{Value, json};
 use chrono::Local;
 
 pub fn format_html_output(plan: &TerraformPlan) -> String {
+    format_html_output_grouped(plan, GroupBy::None)
+}
+
+pub fn format_html_output_grouped(plan: &TerraformPlan, group_by: GroupBy) -> String {
+    html_report(plan, group_by, Verbosity::Normal)
+}
+
+/// The page at a verbosity: quiet pages stop after the summary and policy
+/// checks, verbose ones also list each change the ignore rules suppressed.
+fn html_report(plan: &TerraformPlan, group_by: GroupBy, verbosity: Verbosity) -> String {
     let mut html = String::new();
+    let quiet = verbosity == Verbosity::Quiet;
     
     // HTML header - exactly matching the sample
     html.push_str(r#"<!DOCTYPE html>
@@ -167,6 +182,18 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
         .destroy { color: #f85149; }
         .destroy .stat-number { color: #f85149; }
         
+        .suppressed-note {
+            margin-top: 20px;
+            font-size: 14px;
+            color: #8b949e;
+        }
+        
+        .suppressed-list {
+            margin: 8px 0 0 20px;
+            font-size: 13px;
+            color: #8b949e;
+        }
+        
         .read { color: #58a6ff; }
         .read .stat-number { color: #58a6ff; }
         
@@ -309,6 +336,10 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
             font-style: italic;
         }
         
+        .line.cosmetic {
+            opacity: 0.45;
+        }
+        
         .attribute {
             color: #79c0ff;
             font-weight: 600;
@@ -384,6 +415,136 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
             font-size: 13px;
         }
         
+        /* Risk */
+        .risk-overview {
+            margin-top: 20px;
+            display: flex;
+            align-items: center;
+            gap: 15px;
+            font-size: 14px;
+            color: #8b949e;
+        }
+        
+        .risk-badge {
+            padding: 2px 8px;
+            border-radius: 10px;
+            font-size: 12px;
+            font-weight: bold;
+            text-transform: uppercase;
+            border: 1px solid currentColor;
+        }
+        
+        .risk-high { color: #f85149; }
+        .risk-medium { color: #d29922; }
+        .risk-low { color: #3fb950; }
+        .risk-none { color: #8b949e; }
+        
+        .resource-header .risk-badge {
+            margin-left: auto;
+        }
+        
+        .resource-id ~ .risk-badge {
+            margin-left: 0;
+        }
+        
+        .resource-sort {
+            background-color: #0d1117;
+            color: #c9d1d9;
+            border: 1px solid #30363d;
+            border-radius: 6px;
+            padding: 2px 6px;
+        }
+        
+        /* Cost */
+        .cost-overview {
+            margin-top: 20px;
+            font-size: 14px;
+            color: #8b949e;
+        }
+        
+        .cost-total strong {
+            color: #c9d1d9;
+        }
+        
+        .cost-table {
+            margin-top: 8px;
+            border-collapse: collapse;
+            font-size: 13px;
+        }
+        
+        .cost-table td {
+            padding: 2px 16px 2px 0;
+        }
+        
+        .cost-table td:first-child {
+            font-family: 'SF Mono', Monaco, monospace;
+        }
+        
+        .cost-up td:last-child { color: #f85149; }
+        .cost-down td:last-child { color: #3fb950; }
+        .cost-unknown td:last-child { color: #d29922; }
+        
+        /* Catalog annotations */
+        .resource-annotation {
+            padding: 8px 20px;
+            font-size: 13px;
+            border-bottom: 1px solid #30363d;
+        }
+        
+        .annotation-stateful { color: #f85149; background-color: rgba(248, 81, 73, 0.08); }
+        .annotation-security-sensitive { color: #d2a8ff; background-color: rgba(210, 168, 255, 0.08); }
+        .annotation-network-critical { color: #d29922; background-color: rgba(210, 153, 34, 0.08); }
+        .annotation-cheap-to-recreate { color: #8b949e; }
+        
+        /* Policy checks */
+        .policy-section {
+            background-color: #161b22;
+            border: 1px solid #30363d;
+            border-radius: 12px;
+            padding: 20px;
+            margin-bottom: 30px;
+        }
+        
+        .policy-header {
+            font-size: 18px;
+            margin-bottom: 15px;
+        }
+        
+        .policy-counts {
+            color: #8b949e;
+            font-size: 14px;
+            font-weight: normal;
+        }
+        
+        .policy-result {
+            border-left: 3px solid #3fb950;
+            padding: 10px 15px;
+            margin-bottom: 10px;
+            border-radius: 4px;
+        }
+        
+        .policy-result.policy-warn { border-left-color: #d29922; background-color: rgba(210, 153, 34, 0.1); }
+        .policy-result.policy-fail { border-left-color: #f85149; background-color: rgba(248, 81, 73, 0.1); }
+        
+        .policy-outcome {
+            font-weight: bold;
+            margin-right: 8px;
+        }
+        
+        .policy-pass .policy-outcome { color: #3fb950; }
+        .policy-warn .policy-outcome { color: #d29922; }
+        .policy-fail .policy-outcome { color: #f85149; }
+        
+        .policy-description {
+            color: #8b949e;
+            font-size: 13px;
+        }
+        
+        .policy-violations {
+            margin: 8px 0 0 20px;
+            font-size: 13px;
+        }
+        
         /* Code block styling */
         .code-block {
             background-color: #161b22;
@@ -394,6 +555,22 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
             overflow-x: auto;
         }
         
+        /* Resource groups */
+        .group-header {
+            color: #d2a8ff;
+            font-size: 18px;
+            font-weight: 600;
+            margin: 30px 0 15px;
+            padding-bottom: 8px;
+            border-bottom: 1px solid #30363d;
+        }
+        
+        .group-count {
+            color: #8b949e;
+            font-size: 14px;
+            font-weight: normal;
+        }
+        
         @media (max-width: 768px) {
             .diff-container {
                 grid-template-columns: 1fr;
@@ -471,63 +648,191 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
     }
     
     html.push_str(r#"
-            </div>
-        </div>"#);
+            </div>"#);
     
-    // Resources
-    for resource in &plan.resources {
-        let (action_class, action_text) = match resource.action {
-            ActionType::Create => ("action-create", "CREATE"),
-            ActionType::Update => ("action-update", "UPDATE"),
-            ActionType::Destroy => ("action-destroy", "DESTROY"),
-            ActionType::Read => ("action-read", "READ"),
-            ActionType::NoOp => ("action-read", "NO-OP"),
-        };
-        
-        let new_resource_class = if resource.action == ActionType::Create { 
-            " new-resource" 
-        } else { 
-            "" 
-        };
-        
+    if let Some(note) = crate::formatter::format_suppressed_note(plan) {
         html.push_str(&format!(r#"
-        <div class="resource-section{}">
-            <div class="resource-header">
-                <span class="action-badge {}">{}</span>
-                <span class="resource-name">{}</span>"#,
-            new_resource_class, action_class, action_text, resource.id));
-        
-        // Add resource ID if it's an update
-        if resource.action == ActionType::Update && !resource.attributes.is_empty() {
-            if let Some(id_value) = resource.attributes.get("id") {
-                if let Value::String(id_str) = id_value {
-                    html.push_str(&format!(r#"
-                <span class="resource-id">id: {}</span>"#, id_str));
-                }
+            <div class="suppressed-note">🔇 {}</div>"#, html_escape(&note)));
+        if verbosity == Verbosity::Verbose {
+            html.push_str(r#"
+            <ul class="suppressed-list">"#);
+            for suppressed in &plan.suppressed {
+                html.push_str(&format!(r#"
+                <li><code>{}</code> {} ({})</li>"#,
+                    html_escape(&suppressed.address), html_escape(&suppressed.path), html_escape(&suppressed.reason.to_string())));
             }
+            html.push_str(r#"
+            </ul>"#);
+        }
+    }
+    
+    if let Some(cost) = &plan.cost {
+        html.push_str(&format!(r#"
+            <div class="cost-overview">
+                <div class="cost-total">💰 Monthly cost <strong>{}</strong></div>
+                <table class="cost-table">"#, html_escape(&crate::formatter::format_cost_line(cost))));
+        for resource_cost in &cost.resources {
+            let direction = match resource_cost.delta {
+                Some(delta) if delta > 0.0 => "cost-up",
+                Some(delta) if delta < 0.0 => "cost-down",
+                Some(_) => "cost-same",
+                None => "cost-unknown",
+            };
+            html.push_str(&format!(r#"
+                    <tr class="{}"><td>{}</td><td>{}</td></tr>"#,
+                direction,
+                html_escape(&resource_cost.address),
+                html_escape(&crate::formatter::format_resource_cost(resource_cost, &cost.currency))));
         }
-        
         html.push_str(r#"
+                </table>
             </div>"#);
+    }
+    
+    let risk = assess_plan(plan);
+    if !plan.resources.is_empty() {
+        html.push_str(&format!(r#"
+            <div class="risk-overview">
+                <span>Overall risk <span class="risk-badge risk-{}">{} · {}</span></span>"#, risk.level, risk.level, risk.score));
+        if !quiet {
+            html.push_str(r#"
+                <label>Sort resources by
+                    <select class="resource-sort">
+                        <option value="plan">plan order</option>
+                        <option value="risk">risk</option>
+                    </select>
+                </label>"#);
+        }
+        html.push_str(r#"
+            </div>"#);
+    }
+    
+    html.push_str(r#"
+        </div>"#);
+    
+    // Policy checks
+    if let Some(counts) = crate::formatter::format_policy_counts(&plan.policy_results) {
+        html.push_str(&format!(r#"
+        <div class="policy-section">
+            <div class="policy-header">🛡️ Policy checks <span class="policy-counts">{}</span></div>"#, html_escape(&counts)));
         
-        // Format resource content based on action type
-        match resource.action {
-            ActionType::Update => {
-                // For updates, show side-by-side diff
-                html.push_str(&format_update_diff(resource));
-            },
-            _ => {
-                // For create/destroy/read, show single column
-                html.push_str(&format_single_column(resource));
+        for result in &plan.policy_results {
+            html.push_str(&format!(r#"
+            <div class="policy-result policy-{}">
+                <span class="policy-outcome">{}</span>
+                <span class="policy-name">{}</span>"#,
+                result.outcome, result.outcome.to_string().to_uppercase(), html_escape(&result.policy)));
+            if let Some(description) = &result.description {
+                html.push_str(&format!(r#"
+                <div class="policy-description">{}</div>"#, html_escape(description)));
             }
+            if let Some(label) = &result.waived_by {
+                html.push_str(&format!(r#"
+                <div class="policy-description">allowed by label {}</div>"#, html_escape(label)));
+            }
+            if !result.violations.is_empty() {
+                html.push_str(r#"
+                <ul class="policy-violations">"#);
+                for violation in &result.violations {
+                    html.push_str(&format!(r#"
+                    <li>{}</li>"#, html_escape(violation)));
+                }
+                html.push_str(r#"
+                </ul>"#);
+            }
+            html.push_str(r#"
+            </div>"#);
         }
         
         html.push_str(r#"
         </div>"#);
     }
     
+    // Quiet pages end with the summary and policy checks
+    let (resources, data_sources, warnings) = if quiet {
+        (&[][..], &[][..], &[][..])
+    } else {
+        (&plan.resources[..], &plan.data_sources[..], &plan.warnings[..])
+    };
+    
+    // Resources
+    let mut order = 0;
+    for (label, resources) in group_resources(resources, group_by) {
+        if group_by != GroupBy::None {
+            html.push_str(&format!(r#"
+        <div class="group-header">{} <span class="group-count">({})</span></div>"#,
+                html_escape(&label), resources.len()));
+        }
+        
+        for resource in resources {
+            let (action_class, action_text) = match resource.action {
+                ActionType::Create => ("action-create", "CREATE"),
+                ActionType::Update => ("action-update", "UPDATE"),
+                ActionType::Destroy => ("action-destroy", "DESTROY"),
+                ActionType::Read => ("action-read", "READ"),
+                ActionType::NoOp => ("action-read", "NO-OP"),
+            };
+        
+            let new_resource_class = if resource.action == ActionType::Create { 
+                " new-resource" 
+            } else { 
+                "" 
+            };
+        
+            // Score and position let the sort control reorder resources within their group
+            let (score, level, factors) = match risk.resource(&resource.id) {
+                Some(resource_risk) => (resource_risk.score, resource_risk.level, resource_risk.factors.join(", ")),
+                None => (0, crate::RiskLevel::None, String::new()),
+            };
+            html.push_str(&format!(r#"
+            <div class="resource-section{}" data-risk-score="{}" data-order="{}">
+                <div class="resource-header">
+                    <span class="action-badge {}">{}</span>
+                    <span class="resource-name">{}</span>"#,
+                new_resource_class, score, order, action_class, action_text, resource.id));
+            order += 1;
+        
+            // Add resource ID if it's an update
+            if resource.action == ActionType::Update && !resource.attributes.is_empty() {
+                if let Some(Value::String(id_str)) = resource.attributes.get("id") {
+                    html.push_str(&format!(r#"
+                    <span class="resource-id">id: {}</span>"#, id_str));
+                }
+            }
+            
+            html.push_str(&format!(r#"
+                    <span class="risk-badge risk-{}" title="{}">{} · {}</span>"#,
+                level, html_escape(&factors), level, score));
+        
+            html.push_str(r#"
+                </div>"#);
+            
+            for annotation in plan.annotations.iter().filter(|annotation| annotation.address == resource.id) {
+                html.push_str(&format!(r#"
+                <div class="resource-annotation annotation-{}">{} {}</div>"#,
+                    annotation.class,
+                    crate::formatter::annotation_icon(annotation.class), html_escape(&annotation.message)));
+            }
+        
+            // Format resource content based on action type
+            match resource.action {
+                ActionType::Update => {
+                    // For updates, show side-by-side diff
+                    html.push_str(&format_update_diff(resource));
+                },
+                _ => {
+                    // For create/destroy/read, show single column
+                    html.push_str(&format_single_column(resource));
+                }
+            }
+        
+            html.push_str(r#"
+            </div>"#);
+        }
+    }
+    
     // Data Sources
-    for data_source in &plan.data_sources {
+    for data_source in data_sources {
         html.push_str(&format!(r#"
         <div class="resource-section">
             <div class="resource-header">
@@ -552,7 +857,7 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
     }
     
     // Warnings Section
-    if !plan.warnings.is_empty() {
+    if !warnings.is_empty() {
         html.push_str(r#"
         <div class="warnings-section">
             <div class="warnings-header">
@@ -560,7 +865,7 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
                 <span>Warnings</span>
             </div>"#);
         
-        for warning in &plan.warnings {
+        for warning in warnings {
             html.push_str(&format!(r#"
             <div class="warning-item">
                 <div class="warning-title">Warning</div>
@@ -574,6 +879,31 @@ pub fn format_html_output(plan: &TerraformPlan) -> String {
     
     html.push_str(r#"
     </div>
+    <script>
+        // Reorder each run of adjacent resources (one per group) by risk or back to plan order
+        document.querySelectorAll('.resource-sort').forEach(function (select) {
+            select.addEventListener('change', function () {
+                var sections = Array.from(document.querySelectorAll('.resource-section[data-risk-score]'));
+                var runs = [];
+                sections.forEach(function (section, index) {
+                    if (index === 0 || sections[index - 1].nextElementSibling !== section) {
+                        runs.push([]);
+                    }
+                    runs[runs.length - 1].push(section);
+                });
+                runs.forEach(function (run) {
+                    var anchor = run[run.length - 1].nextElementSibling;
+                    run.sort(function (a, b) {
+                        var byRisk = select.value === 'risk' ? b.dataset.riskScore - a.dataset.riskScore : 0;
+                        return byRisk || a.dataset.order - b.dataset.order;
+                    });
+                    run.forEach(function (section) {
+                        section.parentNode.insertBefore(section, anchor);
+                    });
+                });
+            });
+        });
+    </script>
 </body>
 </html>"#);
     
@@ -600,11 +930,15 @@ fn format_update_diff(resource: &crate::Resource) -> String {
             
             match (&change.before, &change.after) {
                 (Some(before), Some(after)) if before != after => {
-                    // Changed value
-                    before_lines.push(format!(r#"<span class="line remove">    <span class="attribute">{}</span> = {}</span>"#,
-                        path, format_value(before, false)));
-                    after_lines.push(format!(r#"<span class="line add">    <span class="attribute">{}</span> = {}</span>"#,
-                        path, format_value(after, false)));
+                    // Changed value; cosmetic changes are dimmed
+                    let (class, title) = match classify_change(change) {
+                        ChangeClass::Cosmetic(reason) => (" cosmetic", format!(r#" title="cosmetic: {}""#, reason)),
+                        ChangeClass::Semantic => ("", String::new()),
+                    };
+                    before_lines.push(format!(r#"<span class="line remove{}"{}>    <span class="attribute">{}</span> = {}</span>"#,
+                        class, title, path, format_value(before, false)));
+                    after_lines.push(format!(r#"<span class="line add{}"{}>    <span class="attribute">{}</span> = {}</span>"#,
+                        class, title, path, format_value(after, false)));
                 },
                 (None, Some(after)) => {
                     // Added value
@@ -684,15 +1018,15 @@ fn format_attributes_as_diff(resource: &crate::Resource) -> String {
                 }
             }
             // Check for addition prefix
-            else if val_str.starts_with("+ ") {
+            else if let Some(added) = val_str.strip_prefix("+ ") {
                 after_lines.push(format!(r#"<span class="line add">    <span class="attribute">{}</span> = {}</span>"#,
-                    key, format_value(&json!(&val_str[2..]), true)));
+                    key, format_value(&json!(added), true)));
                 continue;
             }
             // Check for removal prefix
-            else if val_str.starts_with("- ") {
+            else if let Some(removed) = val_str.strip_prefix("- ") {
                 before_lines.push(format!(r#"<span class="line remove">    <span class="attribute">{}</span> = {}</span>"#,
-                    key, format_value(&json!(&val_str[2..]), true)));
+                    key, format_value(&json!(removed), true)));
                 continue;
             }
         }
@@ -810,10 +1144,10 @@ fn format_value(value: &Value, with_quotes: bool) -> String {
         },
         Value::Number(n) => format!(r#"<span class="value">{}</span>"#, n),
         Value::Bool(b) => format!(r#"<span class="value">{}</span>"#, b),
-        Value::Null => format!(r#"<span class="value">null</span>"#),
+        Value::Null => r#"<span class="value">null</span>"#.to_string(),
         Value::Array(arr) => {
             if arr.is_empty() {
-                format!(r#"<span class="value">[]</span>"#)
+                r#"<span class="value">[]</span>"#.to_string()
             } else {
                 let items: Vec<String> = arr.iter()
                     .map(|v| format_value(v, true))
@@ -825,7 +1159,7 @@ fn format_value(value: &Value, with_quotes: bool) -> String {
         },
         Value::Object(obj) => {
             if obj.is_empty() {
-                format!(r#"<span class="value">{{}}</span>"#)
+                r#"<span class="value">{}</span>"#.to_string()
             } else {
                 let mut items = Vec::new();
                 for (k, v) in obj {
@@ -840,10 +1174,27 @@ fn format_value(value: &Value, with_quotes: bool) -> String {
     }
 }
 
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
      .replace('<', "&lt;")
      .replace('>', "&gt;")
      .replace('"', "&quot;")
      .replace('\'', "&#x27;")
-}
\ No newline at end of file
+}
+
+/// A standalone HTML page.
+pub struct HtmlFormatter;
+
+impl Formatter for HtmlFormatter {
+    fn name(&self) -> &str {
+        "html"
+    }
+    
+    fn description(&self) -> &str {
+        "Standalone HTML page"
+    }
+    
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        Ok(html_report(plan, options.group_by, options.verbosity))
+    }
+}
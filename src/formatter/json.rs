@@ -1,6 +1,24 @@
+use crate::formatter::registry::{FormatOptions, Formatter};
 use crate::{TerraformPlan, Result};
 
 pub fn format_json_output(plan: &TerraformPlan) -> Result<String> {
     let json = serde_json::to_string_pretty(plan)?;
     Ok(json)
-}
\ No newline at end of file
+}
+
+/// The plan model as JSON, for scripts.
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn name(&self) -> &str {
+        "json"
+    }
+    
+    fn description(&self) -> &str {
+        "The parsed plan as JSON"
+    }
+    
+    fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+        format_json_output(plan)
+    }
+}
@@ -0,0 +1,179 @@
+use crate::formatter::html::html_escape;
+use crate::formatter::registry::{FormatOptions, Formatter};
+use crate::{ActionType, PlanMode, PolicyOutcome, PolicyResult, Resource, ResourceAddress, ResourceClass, Result, SuppressedChange, TerraformPlan};
+
+/// Why a resource change fails its test case.
+struct Failure {
+    kind: &'static str,
+    message: String,
+}
+
+/// Render the plan as a JUnit XML report for CI test report widgets.
+///
+/// Each resource change is a test case, named by its address. It fails when
+/// it breaks a failing policy or destroys a stateful resource from the
+/// catalog, and is skipped when it changes nothing. Resources whose every
+/// change the ignore rules suppressed are skipped test cases too. Policies,
+/// if any, get a test suite of their own, so a change limit shows up as well.
+pub fn format_junit_output(plan: &TerraformPlan) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let suite_name = match plan.mode {
+        PlanMode::Plan => "terraform plan",
+        PlanMode::Apply => "terraform apply",
+    };
+    let suppressed = suppressed_resources(plan);
+    let resource_cases: Vec<String> = plan.resources.iter()
+        .map(|resource| resource_case(plan, resource))
+        .chain(suppressed.iter().map(|(address, changes)| suppressed_case(address, changes)))
+        .collect();
+    let resource_failures = plan.resources.iter().filter(|resource| !failures(plan, resource).is_empty()).count();
+    let skipped = plan.resources.iter().filter(|resource| resource.action == ActionType::NoOp).count() + suppressed.len();
+    let policy_failures = plan.policy_results.iter().filter(|result| result.outcome == PolicyOutcome::Fail).count();
+
+    xml.push_str(&format!("<testsuites name=\"tfdiff\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" errors=\"0\" time=\"0\">\n",
+        resource_cases.len() + plan.policy_results.len(),
+        resource_failures + policy_failures,
+        skipped
+    ));
+
+    xml.push_str(&format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" errors=\"0\" time=\"0\"{}>\n",
+        suite_name,
+        resource_cases.len(),
+        resource_failures,
+        skipped,
+        plan.metadata.timestamp.as_ref()
+            .map(|timestamp| format!(" timestamp=\"{}\"", html_escape(timestamp)))
+            .unwrap_or_default()
+    ));
+    for case in resource_cases {
+        xml.push_str(&case);
+    }
+    xml.push_str("  </testsuite>\n");
+
+    if !plan.policy_results.is_empty() {
+        xml.push_str(&format!("  <testsuite name=\"policy checks\" tests=\"{}\" failures=\"{}\" skipped=\"0\" errors=\"0\" time=\"0\">\n",
+            plan.policy_results.len(),
+            policy_failures
+        ));
+        for result in &plan.policy_results {
+            xml.push_str(&policy_case(result));
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn resource_case(plan: &TerraformPlan, resource: &Resource) -> String {
+    let mut xml = format!("    <testcase name=\"{}\" classname=\"{}\" time=\"0\">\n",
+        html_escape(&resource.id), html_escape(&resource.type_name));
+
+    let failures = failures(plan, resource);
+    if let Some(first) = failures.first() {
+        let details: Vec<String> = failures.iter().map(|failure| failure.message.clone()).collect();
+        xml.push_str(&format!("      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+            first.kind, html_escape(&first.message), html_escape(&details.join("\n"))));
+    }
+    if resource.action == ActionType::NoOp {
+        xml.push_str("      <skipped message=\"no changes\"/>\n");
+    }
+
+    let mut output = vec![format!("{} will be {}", resource.id, resource.action_description())];
+    let mut paths: Vec<String> = resource.changes.iter()
+        .map(|change| change.path.join("."))
+        .chain(resource.attributes.keys().cloned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    output.extend(paths.into_iter().map(|path| format!("  {}", path)));
+    output.extend(plan.annotations.iter()
+        .filter(|annotation| annotation.address == resource.id && annotation.class != ResourceClass::Stateful)
+        .map(|annotation| format!("note: {}", annotation.message)));
+    output.extend(plan.policy_results.iter()
+        .filter(|result| result.outcome == PolicyOutcome::Warn && result.addresses.contains(&resource.id))
+        .map(|result| format!("warning: policy {}", result.policy)));
+    xml.push_str(&format!("      <system-out>{}</system-out>\n", html_escape(&output.join("\n"))));
+
+    xml.push_str("    </testcase>\n");
+    xml
+}
+
+/// Updates no longer in the plan because ignore rules suppressed all their changes.
+fn suppressed_resources(plan: &TerraformPlan) -> Vec<(&str, Vec<&SuppressedChange>)> {
+    let mut resources: Vec<(&str, Vec<&SuppressedChange>)> = Vec::new();
+    for suppressed in &plan.suppressed {
+        if plan.resources.iter().any(|resource| resource.id == suppressed.address) {
+            continue;
+        }
+        match resources.iter_mut().find(|(address, _)| *address == suppressed.address) {
+            Some((_, changes)) => changes.push(suppressed),
+            None => resources.push((&suppressed.address, vec![suppressed])),
+        }
+    }
+    resources
+}
+
+fn suppressed_case(address: &str, changes: &[&SuppressedChange]) -> String {
+    let type_name = ResourceAddress::parse(address).map(|parsed| parsed.type_name).unwrap_or_default();
+    let mut output = vec![format!("{} will be updated in-place", address)];
+    output.extend(changes.iter().map(|change| format!("  {} ({})", change.path, change.reason)));
+    format!("    <testcase name=\"{}\" classname=\"{}\" time=\"0\">\n      <skipped message=\"changes suppressed by ignore rules\"/>\n      <system-out>{}</system-out>\n    </testcase>\n",
+        html_escape(address), html_escape(&type_name), html_escape(&output.join("\n")))
+}
+
+/// Failing policies the resource breaks, then a stateful resource being destroyed or replaced.
+fn failures(plan: &TerraformPlan, resource: &Resource) -> Vec<Failure> {
+    let policies = plan.policy_results.iter()
+        .filter(|result| result.outcome == PolicyOutcome::Fail && result.addresses.contains(&resource.id))
+        .map(|result| Failure {
+            kind: "policy",
+            message: format!("policy {}: {} will be {}", result.policy, resource.id, resource.action_description()),
+        });
+    let protected = plan.annotations.iter()
+        .filter(|annotation| annotation.address == resource.id && annotation.class == ResourceClass::Stateful)
+        .map(|annotation| Failure {
+            kind: "protected-resource",
+            message: annotation.message.clone(),
+        });
+
+    policies.chain(protected).collect()
+}
+
+fn policy_case(result: &PolicyResult) -> String {
+    let mut xml = format!("    <testcase name=\"{}\" classname=\"policy\" time=\"0\">\n", html_escape(&result.policy));
+
+    if result.outcome == PolicyOutcome::Fail {
+        let message = result.description.clone()
+            .unwrap_or_else(|| format!("policy {} failed", result.policy));
+        xml.push_str(&format!("      <failure type=\"policy\" message=\"{}\">{}</failure>\n",
+            html_escape(&message), html_escape(&result.violations.join("\n"))));
+    } else if !result.violations.is_empty() {
+        let mut output = result.violations.clone();
+        if let Some(label) = &result.waived_by {
+            output.insert(0, format!("allowed by label {}", label));
+        }
+        xml.push_str(&format!("      <system-out>{}</system-out>\n", html_escape(&output.join("\n"))));
+    }
+
+    xml.push_str("    </testcase>\n");
+    xml
+}
+
+/// JUnit XML with a test case per resource change.
+pub struct JunitFormatter;
+
+impl Formatter for JunitFormatter {
+    fn name(&self) -> &str {
+        "junit"
+    }
+
+    fn description(&self) -> &str {
+        "JUnit XML for CI test reports"
+    }
+
+    fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+        Ok(format_junit_output(plan))
+    }
+}
@@ -0,0 +1,93 @@
+use crate::parser::clean_ansi_codes;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns the terminal report is laid out in when the terminal's width is
+/// unknown, e.g. when the output is piped.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// The narrowest layout; a smaller `--width` is widened to this.
+pub const MIN_WIDTH: usize = 40;
+
+/// The width of the terminal on stdout, else `$COLUMNS`, else `DEFAULT_WIDTH`.
+pub fn terminal_width() -> usize {
+    term_size::dimensions_stdout()
+        .map(|(width, _)| width)
+        .or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok())
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+        .max(MIN_WIDTH)
+}
+
+/// Columns the text takes up in a terminal. Color codes take none, and
+/// wide characters such as CJK and most emoji take two.
+pub fn display_width(text: &str) -> usize {
+    if text.contains('\x1b') {
+        clean_ansi_codes(text).width()
+    } else {
+        text.width()
+    }
+}
+
+/// Cut plain text to at most `width` columns, ending with `…` if it was
+/// longer. Characters and grapheme clusters are never split.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if used + grapheme_width + 1 > width {
+            break;
+        }
+        cut.push_str(grapheme);
+        used += grapheme_width;
+    }
+    cut.push('…');
+    cut
+}
+
+/// Pad text with spaces to `width` columns, not counting color codes.
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(display_width(text))))
+}
+
+/// Break plain text into lines of at most `width` columns at spaces. A word
+/// longer than a line is split between grapheme clusters.
+pub fn wrap_to_width(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+    for word in text.split_whitespace() {
+        let word_width = word.width();
+        if used > 0 && used + 1 + word_width <= width {
+            line.push(' ');
+            line.push_str(word);
+            used += 1 + word_width;
+            continue;
+        }
+        if used > 0 {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+        for grapheme in word.graphemes(true) {
+            let grapheme_width = grapheme.width();
+            if used > 0 && used + grapheme_width > width {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push_str(grapheme);
+            used += grapheme_width;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
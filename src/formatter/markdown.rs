@@ -1,6 +1,19 @@
-use crate::{TerraformPlan, ActionType};
+use crate::{TerraformPlan, ActionType, PolicyOutcome, Result, RiskLevel};
+use crate::formatter::registry::{FormatOptions, Formatter, Verbosity};
+use crate::risk::assess_plan;
+use crate::formatter::grouping::{GroupBy, group_resources};
 
 pub fn format_markdown_output(plan: &TerraformPlan) -> String {
+    format_markdown_output_grouped(plan, GroupBy::None)
+}
+
+pub fn format_markdown_output_grouped(plan: &TerraformPlan, group_by: GroupBy) -> String {
+    markdown_report(plan, group_by, Verbosity::Normal)
+}
+
+/// The report at a verbosity: quiet reports stop after the summary, verbose
+/// ones also list each change the ignore rules suppressed.
+fn markdown_report(plan: &TerraformPlan, group_by: GroupBy, verbosity: Verbosity) -> String {
     let mut md = String::new();
     
     // Title
@@ -18,28 +31,140 @@ pub fn format_markdown_output(plan: &TerraformPlan) -> String {
         md.push_str(&format!("- 📖 **{}** resources to read\n", plan.summary.read));
     }
     md.push('\n');
+    if let Some(note) = crate::formatter::format_suppressed_note(plan) {
+        md.push_str(&format!("> 🔇 {}\n\n", note));
+        if verbosity == Verbosity::Verbose {
+            for suppressed in &plan.suppressed {
+                md.push_str(&format!("- `{}` `{}` ({})\n", suppressed.address, suppressed.path, suppressed.reason));
+            }
+            md.push('\n');
+        }
+    }
+    
+    if verbosity == Verbosity::Quiet {
+        if let Some(cost) = &plan.cost {
+            md.push_str(&format!("💰 Monthly cost: {}\n\n", crate::formatter::format_cost_line(cost)));
+        }
+        if let Some(counts) = crate::formatter::format_policy_counts(&plan.policy_results) {
+            md.push_str(&format!("🛡️ Policy checks: {}\n\n", counts));
+        }
+        md.push_str("---\n");
+        md.push_str("*Generated by tfdiff*\n");
+        return md;
+    }
+    
+    // Monthly cost of the priced changes
+    if let Some(cost) = &plan.cost {
+        md.push_str("### Monthly Cost\n\n");
+        md.push_str(&format!("💰 {}\n\n", crate::formatter::format_cost_line(cost)));
+        md.push_str("| Resource | Before | After | Change |\n");
+        md.push_str("|----------|--------|-------|--------|\n");
+        let price = |amount: Option<f64>| amount.map(|amount| crate::formatter::format_money(amount, &cost.currency)).unwrap_or_else(|| "?".to_string());
+        for resource_cost in &cost.resources {
+            let change = match (resource_cost.delta, &resource_cost.note) {
+                (Some(delta), _) => crate::formatter::format_money_change(delta, &cost.currency),
+                (None, Some(note)) => format!("unknown ({})", note.replace('|', "\\|")),
+                (None, None) => "unknown".to_string(),
+            };
+            md.push_str(&format!("| `{}` | {} | {} | {} |\n",
+                resource_cost.address, price(resource_cost.before), price(resource_cost.after), change));
+        }
+        md.push('\n');
+    }
+    
+    // Risk, riskiest resources first
+    if !plan.resources.is_empty() {
+        let risk = assess_plan(plan);
+        md.push_str("## Risk\n\n");
+        md.push_str(&format!("Overall risk: **{}** ({}/100)\n\n", risk.level, risk.score));
+        md.push_str("| Resource | Action | Risk | Factors |\n");
+        md.push_str("|----------|--------|------|---------|\n");
+        let mut rows: Vec<(&crate::Resource, &crate::ResourceRisk)> = plan.resources.iter().zip(&risk.resources).collect();
+        rows.sort_by_key(|(_, resource_risk)| std::cmp::Reverse(resource_risk.score));
+        for (resource, resource_risk) in rows {
+            let action = match resource.action {
+                ActionType::Update if resource.replace => "replace",
+                ActionType::Create => "create",
+                ActionType::Update => "update",
+                ActionType::Destroy => "destroy",
+                ActionType::Read => "read",
+                ActionType::NoOp => "no-op",
+            };
+            let emoji = match resource_risk.level {
+                RiskLevel::High => "🔴",
+                RiskLevel::Medium => "🟠",
+                RiskLevel::Low => "🟢",
+                RiskLevel::None => "⚪",
+            };
+            md.push_str(&format!("| `{}` | {} | {} {} ({}) | {} |\n",
+                resource.id, action, emoji, resource_risk.level, resource_risk.score, resource_risk.factors.join(", ")));
+        }
+        md.push('\n');
+    }
+    
+    // Policy checks
+    if let Some(counts) = crate::formatter::format_policy_counts(&plan.policy_results) {
+        md.push_str("## Policy Checks\n\n");
+        md.push_str(&format!("{}\n\n", counts));
+        md.push_str("| Result | Policy | Details |\n");
+        md.push_str("|--------|--------|---------|\n");
+        for result in &plan.policy_results {
+            let outcome = match result.outcome {
+                PolicyOutcome::Pass => "✅ pass",
+                PolicyOutcome::Warn => "⚠️ warn",
+                PolicyOutcome::Fail => "❌ fail",
+            };
+            let mut details: Vec<String> = result.description.iter().cloned().collect();
+            if let Some(label) = &result.waived_by {
+                details.push(format!("allowed by label `{}`", label));
+            }
+            details.extend(result.violations.iter().cloned());
+            md.push_str(&format!("| {} | `{}` | {} |\n",
+                outcome, result.policy, details.join("<br>").replace('|', "\\|")));
+        }
+        md.push('\n');
+    }
     
     // Resources
     if !plan.resources.is_empty() {
         md.push_str("## Resource Changes\n\n");
         
-        for resource in &plan.resources {
-            let (emoji, action_text) = match resource.action {
-                ActionType::Create => ("✅", "CREATE"),
-                ActionType::Update => ("🔄", "UPDATE"),
-                ActionType::Destroy => ("❌", "DESTROY"),
-                ActionType::Read => ("📖", "READ"),
-                ActionType::NoOp => ("⭕", "NO-OP"),
-            };
-            
-            md.push_str(&format!("### {} {} `{}`\n\n", emoji, action_text, resource.id));
+        // Resources sit one heading level below their group, if any
+        let heading = if group_by == GroupBy::None { "###" } else { "####" };
+        
+        for (label, resources) in group_resources(&plan.resources, group_by) {
+            if group_by != GroupBy::None {
+                md.push_str(&format!("### 📦 {} ({})\n\n", label, resources.len()));
+            }
             
-            if !resource.attributes.is_empty() {
-                md.push_str("```json\n");
-                for (key, value) in &resource.attributes {
-                    md.push_str(&format!("{}: {}\n", key, value));
+            for resource in resources {
+                let (emoji, action_text) = match resource.action {
+                    ActionType::Create => ("✅", "CREATE"),
+                    ActionType::Update => ("🔄", "UPDATE"),
+                    ActionType::Destroy => ("❌", "DESTROY"),
+                    ActionType::Read => ("📖", "READ"),
+                    ActionType::NoOp => ("⭕", "NO-OP"),
+                };
+                
+                md.push_str(&format!("{} {} {} `{}`\n\n", heading, emoji, action_text, resource.id));
+                
+                let annotations: Vec<_> = plan.annotations.iter()
+                    .filter(|annotation| annotation.address == resource.id)
+                    .collect();
+                for annotation in &annotations {
+                    md.push_str(&format!("> {} {}\n", crate::formatter::annotation_icon(annotation.class), annotation.message));
+                }
+                if !annotations.is_empty() {
+                    md.push('\n');
+                }
+                
+                if !resource.attributes.is_empty() {
+                    md.push_str("```json\n");
+                    for (key, value) in &resource.attributes {
+                        md.push_str(&format!("{}: {}\n", key, value));
+                    }
+                    md.push_str("```\n\n");
                 }
-                md.push_str("```\n\n");
             }
         }
     }
@@ -75,4 +200,21 @@ pub fn format_markdown_output(plan: &TerraformPlan) -> String {
     md.push_str("*Generated by tfdiff*\n");
     
     md
-}
\ No newline at end of file
+}
+
+/// A Markdown report, e.g. for a wiki page or a CI job summary.
+pub struct MarkdownFormatter;
+
+impl Formatter for MarkdownFormatter {
+    fn name(&self) -> &str {
+        "markdown"
+    }
+    
+    fn description(&self) -> &str {
+        "Markdown report"
+    }
+    
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        Ok(markdown_report(plan, options.group_by, options.verbosity))
+    }
+}
@@ -1,9 +1,29 @@
 pub mod terminal;
 pub mod json;
+pub mod junit;
+pub mod sarif;
+pub mod pr_comment;
+pub mod chat;
+pub mod registry;
 pub mod html;
 pub mod markdown;
+pub mod explain;
+pub mod grouping;
+pub mod layout;
+pub mod comparison;
+pub mod reconciliation;
 
 pub use terminal::*;
 pub use json::*;
+pub use junit::*;
+pub use sarif::*;
+pub use pr_comment::*;
+pub use chat::*;
+pub use registry::*;
 pub use html::*;
-pub use markdown::*;
\ No newline at end of file
+pub use markdown::*;
+pub use explain::*;
+pub use grouping::*;
+pub use layout::*;
+pub use comparison::*;
+pub use reconciliation::*;
\ No newline at end of file
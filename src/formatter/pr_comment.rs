@@ -0,0 +1,307 @@
+use crate::compare::proposed_values;
+use crate::formatter::grouping::{GroupBy, group_resources};
+use crate::formatter::html::html_escape;
+use crate::formatter::registry::{FormatOptions, Formatter};
+use crate::formatter::{annotation_icon, format_cost_line, format_policy_counts, format_suppressed_note};
+use crate::risk::{assess_plan, PlanRisk};
+use crate::{ActionType, PlanMode, PolicyOutcome, Resource, Result, RiskLevel, Summary, TerraformPlan};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// The longest comment GitHub accepts on a pull request.
+pub const PR_COMMENT_LIMIT: usize = 65_536;
+
+/// Room kept in each comment for its `<!-- tfdiff comment n/N -->` marker.
+const MARKER_ROOM: usize = 40;
+
+/// Attribute values longer than this are cut short.
+const VALUE_LIMIT: usize = 500;
+
+/// Warning messages longer than this are cut short.
+const WARNING_LIMIT: usize = 1_000;
+
+/// Modules listed in the summary table; the rest share its last row.
+const MAX_MODULE_ROWS: usize = 50;
+
+/// Failed policies named in the banner; the rest are counted.
+const MAX_BANNER_POLICIES: usize = 10;
+
+/// Render the plan as pull request comments for GitHub or GitLab, split so
+/// that none is longer than `PR_COMMENT_LIMIT`.
+pub fn format_pr_comments(plan: &TerraformPlan, group_by: GroupBy) -> Vec<String> {
+    format_pr_comments_with_limit(plan, group_by, PR_COMMENT_LIMIT)
+}
+
+/// Render the plan as pull request comments of at most `limit` bytes each.
+///
+/// The first comment has a banner with the risk and policy results, a summary
+/// table and the cost estimate. The changes follow in collapsible sections by
+/// module (or `group_by`) and resource, with the attribute changes in `diff`
+/// blocks. Each comment starts with a `<!-- tfdiff comment n/N -->` marker.
+pub fn format_pr_comments_with_limit(plan: &TerraformPlan, group_by: GroupBy, limit: usize) -> Vec<String> {
+    let budget = limit.saturating_sub(MARKER_ROOM);
+    let risk = assess_plan(plan);
+    let group_by = if group_by == GroupBy::None { GroupBy::Module } else { group_by };
+
+    let mut comments = vec![header(plan, &risk, budget)];
+    for (label, resources) in group_resources(&plan.resources, group_by) {
+        let summary = Summary::from_resources(&resources.iter().map(|resource| (*resource).clone()).collect::<Vec<_>>());
+        let open = format!("<details>\n<summary>📦 <b>{}</b> · {}</summary>\n\n", html_escape(&label), counts(&summary));
+        let close = "</details>\n\n";
+        let blocks = resources.into_iter()
+            .map(|resource| resource_block(plan, &risk, resource, budget.saturating_sub(open.len() + close.len() + 100)));
+        push_section(&mut comments, plan, &open, close, blocks, budget);
+    }
+
+    if !plan.warnings.is_empty() {
+        let open = format!("<details>\n<summary>⚠️ {} warning{}</summary>\n\n",
+            plan.warnings.len(), if plan.warnings.len() == 1 { "" } else { "s" });
+        let items = plan.warnings.iter().map(|warning| format!("- {}\n", cut(&warning.message, WARNING_LIMIT)));
+        push_section(&mut comments, plan, &open, "\n</details>\n\n", items, budget);
+    }
+
+    let footer = "---\n*Generated by tfdiff*\n";
+    if comments.last().is_some_and(|current| current.len() + footer.len() > budget) {
+        comments.push(continued(plan));
+    }
+    comments.last_mut().expect("there is always a comment").push_str(footer);
+
+    let total = comments.len();
+    comments.into_iter()
+        .enumerate()
+        .map(|(index, comment)| format!("<!-- tfdiff comment {}/{} -->\n{}", index + 1, total, comment))
+        .collect()
+}
+
+/// Add blocks to the last comment inside a collapsible section. When the next
+/// block would not fit in `budget`, the section is closed and reopened in a
+/// new comment.
+fn push_section(comments: &mut Vec<String>, plan: &TerraformPlan, open: &str, close: &str, blocks: impl IntoIterator<Item = String>, budget: usize) {
+    let mut opened = false;
+    for block in blocks {
+        let current = comments.last_mut().expect("there is always a comment");
+        let needed = block.len() + close.len() + if opened { 0 } else { open.len() };
+        if current.len() + needed > budget {
+            if opened {
+                current.push_str(close);
+            }
+            comments.push(continued(plan));
+            opened = false;
+        }
+        let current = comments.last_mut().expect("there is always a comment");
+        if !opened {
+            current.push_str(open);
+            opened = true;
+        }
+        current.push_str(&block);
+    }
+    if opened {
+        comments.last_mut().expect("there is always a comment").push_str(close);
+    }
+}
+
+fn continued(plan: &TerraformPlan) -> String {
+    format!("## Terraform {} (continued)\n\n", mode_name(plan))
+}
+
+pub(crate) fn mode_name(plan: &TerraformPlan) -> &'static str {
+    match plan.mode {
+        PlanMode::Plan => "plan",
+        PlanMode::Apply => "apply",
+    }
+}
+
+/// Counts of a summary in words, e.g. `2 to add, 1 to change`.
+pub(crate) fn counts(summary: &Summary) -> String {
+    let parts: Vec<String> = [(summary.add, "to add"), (summary.change, "to change"), (summary.destroy, "to destroy"), (summary.read, "to read")]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+    if parts.is_empty() { "no changes".to_string() } else { parts.join(", ") }
+}
+
+/// Title, risk and policy banner, summary table by module and cost. The table
+/// takes at most a quarter of `budget`.
+fn header(plan: &TerraformPlan, risk: &PlanRisk, budget: usize) -> String {
+    let mut md = format!("## Terraform {}: {}\n\n", mode_name(plan), counts(&plan.summary));
+
+    let mut banners = Vec::new();
+    let failed: Vec<&str> = plan.policy_results.iter()
+        .filter(|result| result.outcome == PolicyOutcome::Fail)
+        .map(|result| result.policy.as_str())
+        .collect();
+    if let Some(policy_counts) = format_policy_counts(&plan.policy_results) {
+        let banner = if !failed.is_empty() {
+            let mut names = format!("`{}`", failed.iter().take(MAX_BANNER_POLICIES).copied().collect::<Vec<_>>().join("`, `"));
+            if failed.len() > MAX_BANNER_POLICIES {
+                names.push_str(&format!(" and {} more", failed.len() - MAX_BANNER_POLICIES));
+            }
+            format!("🛑 **Policy checks failed:** {} ({})", names, policy_counts)
+        } else if plan.policy_results.iter().any(|result| result.outcome == PolicyOutcome::Warn) {
+            format!("⚠️ **Policy checks passed with warnings** ({})", policy_counts)
+        } else {
+            format!("✅ **Policy checks passed** ({})", policy_counts)
+        };
+        banners.push(banner);
+    }
+    if !plan.resources.is_empty() {
+        let mut banner = format!("{} **{} risk** ({}/100)", risk_emoji(risk.level), capitalize(&risk.level.to_string()), risk.score);
+        let riskiest = plan.resources.iter().zip(&risk.resources)
+            .filter(|(_, resource_risk)| resource_risk.score > 0)
+            .max_by_key(|(_, resource_risk)| resource_risk.score);
+        if let Some((resource, resource_risk)) = riskiest {
+            banner.push_str(&format!(" · riskiest: `{}` ({})", resource.id, resource_risk.factors.join(", ")));
+        }
+        banners.push(banner);
+    }
+    if !banners.is_empty() {
+        md.push_str(&format!("> {}\n\n", banners.join("<br>\n> ")));
+    }
+
+    if !plan.resources.is_empty() {
+        md.push_str("| Module | ➕ Add | 🔄 Change | ❌ Destroy |\n");
+        md.push_str("|--------|-------:|----------:|-----------:|\n");
+        let modules = group_resources(&plan.resources, GroupBy::Module);
+        let module_count = modules.len();
+        let mut table = String::new();
+        let mut rest = Vec::new();
+        for (index, (label, resources)) in modules.into_iter().enumerate() {
+            let summary = Summary::from_resources(&resources.iter().map(|resource| (*resource).clone()).collect::<Vec<_>>());
+            let row = format!("| `{}` | {} | {} | {} |\n", label, summary.add, summary.change, summary.destroy);
+            if rest.is_empty() && index < MAX_MODULE_ROWS && table.len() + row.len() <= budget / 4 {
+                table.push_str(&row);
+            } else {
+                rest.extend(resources.into_iter().cloned());
+            }
+        }
+        if !rest.is_empty() {
+            let hidden = module_count - table.lines().count();
+            let summary = Summary::from_resources(&rest);
+            table.push_str(&format!("| …and {} more module{} | {} | {} | {} |\n",
+                hidden, if hidden == 1 { "" } else { "s" }, summary.add, summary.change, summary.destroy));
+        }
+        md.push_str(&table);
+        md.push_str(&format!("| **Total** | **{}** | **{}** | **{}** |\n\n",
+            plan.summary.add, plan.summary.change, plan.summary.destroy));
+    }
+
+    if let Some(cost) = &plan.cost {
+        md.push_str(&format!("💰 Monthly cost: {}\n\n", format_cost_line(cost)));
+    }
+
+    if let Some(note) = format_suppressed_note(plan) {
+        md.push_str(&format!("🔇 {}\n\n", note));
+    }
+
+    md
+}
+
+/// A collapsible section for one resource, no longer than `limit` bytes.
+fn resource_block(plan: &TerraformPlan, risk: &PlanRisk, resource: &Resource, limit: usize) -> String {
+    let emoji = match resource.action {
+        ActionType::Update if resource.replace => "♻️",
+        ActionType::Create => "✅",
+        ActionType::Update => "🔄",
+        ActionType::Destroy => "❌",
+        ActionType::Read => "📖",
+        ActionType::NoOp => "⭕",
+    };
+    let risk_badge = match risk.resource(&resource.id).map(|resource_risk| resource_risk.level) {
+        Some(level @ (RiskLevel::High | RiskLevel::Medium)) => format!(" {}", risk_emoji(level)),
+        _ => String::new(),
+    };
+    let mut head = format!("<details>\n<summary>{} <code>{}</code> will be {}{}</summary>\n\n",
+        emoji, html_escape(&resource.id), resource.action_description(), risk_badge);
+    for annotation in plan.annotations.iter().filter(|annotation| annotation.address == resource.id) {
+        head.push_str(&format!("> {} {}\n", annotation_icon(annotation.class), annotation.message));
+    }
+    if !head.ends_with("\n\n") {
+        head.push('\n');
+    }
+    let tail = "</details>\n\n";
+
+    let mut lines = diff_lines(resource);
+    if lines.is_empty() {
+        return format!("{}_No attribute changes._\n\n{}", head, tail);
+    }
+
+    // Leave out the last changes if the block would not fit in a comment
+    let fence = |lines: &[String], hidden: usize| {
+        let mut diff = format!("```diff\n{}\n", lines.join("\n"));
+        if hidden > 0 {
+            diff.push_str(&format!("# … {} more change{} not shown\n", hidden, if hidden == 1 { "" } else { "s" }));
+        }
+        diff.push_str("```\n\n");
+        diff
+    };
+    let total = lines.len();
+    while lines.len() > 1 && head.len() + fence(&lines, total - lines.len()).len() + tail.len() > limit {
+        lines.pop();
+    }
+    format!("{}{}{}", head, fence(&lines, total - lines.len()), tail)
+}
+
+/// One line per changed attribute: `+` added, `-` removed, `~` changed.
+pub(crate) fn diff_lines(resource: &Resource) -> Vec<String> {
+    let forcing: HashSet<String> = resource.changes.iter()
+        .filter(|change| change.forces_replacement)
+        .map(|change| change.path.join("."))
+        .collect();
+
+    proposed_values(resource).into_iter()
+        .filter(|(_, value)| value.before != value.after)
+        .map(|(path, value)| {
+            let line = match (&value.before, &value.after) {
+                (None, Some(after)) => format!("+ {} = {}", path, display(after)),
+                (Some(before), None) => format!("- {} = {}", path, display(before)),
+                (Some(before), Some(after)) => format!("~ {} = {} -> {}", path, display(before), display(after)),
+                (None, None) => format!("~ {}", path),
+            };
+            if forcing.contains(&path) { format!("{} # forces replacement", line) } else { line }
+        })
+        .collect()
+}
+
+fn display(value: &Value) -> String {
+    cut(&value.to_string().replace("```", "`\u{200b}``"), VALUE_LIMIT)
+}
+
+/// Text cut to `limit` characters, ending with `…` if it was longer.
+fn cut(text: &str, limit: usize) -> String {
+    match text.char_indices().nth(limit) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+pub(crate) fn risk_emoji(level: RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::High => "🔴",
+        RiskLevel::Medium => "🟠",
+        RiskLevel::Low => "🟢",
+        RiskLevel::None => "⚪",
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// Pull request comments, one after the other, each starting with its marker line.
+pub struct PrCommentFormatter;
+
+impl Formatter for PrCommentFormatter {
+    fn name(&self) -> &str {
+        "pr-comment"
+    }
+
+    fn description(&self) -> &str {
+        "Markdown comments for a GitHub or GitLab pull request"
+    }
+
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        Ok(format_pr_comments(plan, options.group_by).join("\n"))
+    }
+}
@@ -0,0 +1,340 @@
+use crate::formatter::comparison::{action_label, action_text, section_header, summary_or_none, title_box};
+use crate::formatter::html::html_escape;
+use crate::formatter::layout::{DEFAULT_WIDTH, MIN_WIDTH};
+use crate::reconcile::Reconciliation;
+use crate::Result;
+use colored::*;
+
+pub fn format_reconciliation_terminal_output(reconciliation: &Reconciliation) -> String {
+    format_reconciliation_terminal_output_with_width(reconciliation, DEFAULT_WIDTH)
+}
+
+/// The report laid out in `width` columns, at least `MIN_WIDTH`.
+pub fn format_reconciliation_terminal_output_with_width(reconciliation: &Reconciliation, width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
+    let mut output = String::new();
+
+    output.push_str(&title_box("TERRAFORM APPLY RECONCILIATION", width));
+    output.push('\n');
+
+    output.push_str("📊 ");
+    output.push_str(&"SUMMARY".bright_white().bold().to_string());
+    output.push('\n');
+    output.push_str(&"─".repeat(width).dimmed().to_string());
+    output.push_str("\n\n");
+    output.push_str(&format!("  {}  {}\n", "Planned:".dimmed(), summary_or_none(&reconciliation.planned_summary)));
+    output.push_str(&format!("  {}  {}\n\n", "Applied:".dimmed(), summary_or_none(&reconciliation.applied_summary)));
+
+    if reconciliation.is_consistent() {
+        output.push_str(&format!("  {} Apply matched the plan ({} resources)\n\n",
+            "✔".bright_green(),
+            reconciliation.matched.len()
+        ));
+        return output;
+    }
+
+    output.push_str(&format!("  {} Apply diverged from the plan: {} matched  {} failed  {} not applied  {} action changed  {} unplanned\n\n",
+        "✘".bright_red().bold(),
+        reconciliation.matched.len().to_string().bright_green().bold(),
+        reconciliation.failed.len().to_string().bright_red().bold(),
+        reconciliation.not_applied.len().to_string().bright_yellow().bold(),
+        reconciliation.action_mismatches.len().to_string().bright_yellow().bold(),
+        reconciliation.unplanned.len().to_string().bright_red().bold()
+    ));
+
+    if !reconciliation.failed.is_empty() {
+        output.push_str(&section_header("💥", "FAILED", width));
+        for failed in &reconciliation.failed {
+            output.push_str(&format!("  {} {} {}\n", "✘".bright_red().bold(), action_label(&failed.action), failed.address.bright_white()));
+            for error in &failed.errors {
+                output.push_str(&format!("    {}\n", error.bright_red()));
+            }
+        }
+        output.push('\n');
+    }
+
+    if !reconciliation.not_applied.is_empty() {
+        output.push_str(&section_header("⏸️ ", "PLANNED BUT NOT APPLIED", width));
+        for resource in &reconciliation.not_applied {
+            output.push_str(&format!("  {} {} {}\n", "?".bright_yellow().bold(), action_label(&resource.action), resource.id.bright_white()));
+        }
+        output.push('\n');
+    }
+
+    if !reconciliation.action_mismatches.is_empty() {
+        output.push_str(&section_header("🔀", "APPLIED WITH A DIFFERENT ACTION", width));
+        for mismatch in &reconciliation.action_mismatches {
+            output.push_str(&format!("  {} {}\n", "~".bright_yellow().bold(), mismatch.address.bright_white()));
+            output.push_str(&format!("    {} {} → {}\n", "planned → applied:".dimmed(), action_label(&mismatch.planned), action_label(&mismatch.applied)));
+        }
+        output.push('\n');
+    }
+
+    if !reconciliation.unplanned.is_empty() {
+        output.push_str(&section_header("⚠️ ", "NOT IN THE REVIEWED PLAN", width));
+        for resource in &reconciliation.unplanned {
+            output.push_str(&format!("  {} {} {}\n", "!".bright_red().bold(), action_label(&resource.action), resource.id.bright_white()));
+        }
+        output.push('\n');
+    }
+
+    if !reconciliation.errors.is_empty() {
+        output.push_str(&section_header("❌", "ERRORS", width));
+        for error in &reconciliation.errors {
+            output.push_str(&format!("  {}\n", error.bright_red()));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+pub fn format_reconciliation_json_output(reconciliation: &Reconciliation) -> Result<String> {
+    Ok(serde_json::to_string_pretty(reconciliation)?)
+}
+
+pub fn format_reconciliation_markdown_output(reconciliation: &Reconciliation) -> String {
+    let mut md = String::new();
+
+    md.push_str("# Terraform Apply Reconciliation\n\n");
+    md.push_str("## Summary\n\n");
+    md.push_str("| | Add | Change | Destroy |\n");
+    md.push_str("|---|-----|--------|---------|\n");
+    for (label, summary) in [("Planned", &reconciliation.planned_summary), ("Applied", &reconciliation.applied_summary)] {
+        md.push_str(&format!("| {} | {} | {} | {} |\n", label, summary.add, summary.change, summary.destroy));
+    }
+    md.push('\n');
+
+    if reconciliation.is_consistent() {
+        md.push_str(&format!("✅ Apply matched the plan ({} resources).\n", reconciliation.matched.len()));
+        return md;
+    }
+
+    md.push_str(&format!("❌ Apply diverged from the plan: **{}** matched, **{}** failed, **{}** not applied, **{}** action changed, **{}** unplanned\n\n",
+        reconciliation.matched.len(),
+        reconciliation.failed.len(),
+        reconciliation.not_applied.len(),
+        reconciliation.action_mismatches.len(),
+        reconciliation.unplanned.len()
+    ));
+
+    if !reconciliation.failed.is_empty() {
+        md.push_str("## 💥 Failed\n\n");
+        for failed in &reconciliation.failed {
+            md.push_str(&format!("- {} `{}`\n", action_text(&failed.action), failed.address));
+            for error in &failed.errors {
+                md.push_str(&format!("  - {}\n", error));
+            }
+        }
+        md.push('\n');
+    }
+
+    if !reconciliation.not_applied.is_empty() {
+        md.push_str("## ⏸️ Planned but Not Applied\n\n");
+        for resource in &reconciliation.not_applied {
+            md.push_str(&format!("- {} `{}`\n", action_text(&resource.action), resource.id));
+        }
+        md.push('\n');
+    }
+
+    if !reconciliation.action_mismatches.is_empty() {
+        md.push_str("## 🔀 Applied with a Different Action\n\n");
+        md.push_str("| Resource | Planned | Applied |\n");
+        md.push_str("|----------|---------|---------|\n");
+        for mismatch in &reconciliation.action_mismatches {
+            md.push_str(&format!("| `{}` | {} | {} |\n", mismatch.address, action_text(&mismatch.planned), action_text(&mismatch.applied)));
+        }
+        md.push('\n');
+    }
+
+    if !reconciliation.unplanned.is_empty() {
+        md.push_str("## ⚠️ Not in the Reviewed Plan\n\n");
+        for resource in &reconciliation.unplanned {
+            md.push_str(&format!("- {} `{}`\n", action_text(&resource.action), resource.id));
+        }
+        md.push('\n');
+    }
+
+    if !reconciliation.errors.is_empty() {
+        md.push_str("## ❌ Errors\n\n");
+        for error in &reconciliation.errors {
+            md.push_str(&format!("- {}\n", error));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+pub fn format_reconciliation_html_output(reconciliation: &Reconciliation) -> String {
+    let mut html = String::new();
+
+    html.push_str(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Terraform Apply Reconciliation</title>
+    <style>
+        body {
+            font-family: 'SF Mono', Monaco, 'Cascadia Code', 'Roboto Mono', Consolas, 'Courier New', monospace;
+            background-color: #0d1117;
+            color: #c9d1d9;
+            line-height: 1.6;
+            margin: 0;
+            padding: 20px;
+        }
+
+        .container {
+            max-width: 1400px;
+            margin: 0 auto;
+        }
+
+        h1 {
+            color: #58a6ff;
+            font-size: 28px;
+        }
+
+        h2 {
+            font-size: 18px;
+            margin-top: 30px;
+        }
+
+        .panel {
+            background-color: #161b22;
+            border: 1px solid #30363d;
+            border-radius: 12px;
+            padding: 20px;
+            margin-bottom: 20px;
+        }
+
+        table {
+            width: 100%;
+            border-collapse: collapse;
+            font-size: 14px;
+        }
+
+        th, td {
+            text-align: left;
+            padding: 6px 12px;
+            border-bottom: 1px solid #30363d;
+            vertical-align: top;
+        }
+
+        th {
+            color: #8b949e;
+            font-weight: normal;
+        }
+
+        .ok { color: #3fb950; }
+        .failed { color: #f85149; }
+        .warning { color: #d29922; }
+        .muted { color: #8b949e; }
+
+        .address {
+            color: #ffffff;
+            font-weight: bold;
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>🧾 Terraform Apply Reconciliation</h1>
+        <div class="panel">
+            <table>
+                <tr><th></th><th>Add</th><th>Change</th><th>Destroy</th></tr>"#);
+
+    for (label, summary) in [("Planned", &reconciliation.planned_summary), ("Applied", &reconciliation.applied_summary)] {
+        html.push_str(&format!(r#"
+                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+            label, summary.add, summary.change, summary.destroy));
+    }
+
+    let verdict = if reconciliation.is_consistent() {
+        format!(r#"<p class="ok">✅ Apply matched the plan ({} resources)</p>"#, reconciliation.matched.len())
+    } else {
+        format!(r#"<p class="failed">❌ Apply diverged from the plan: {} matched, {} failed, {} not applied, {} action changed, {} unplanned</p>"#,
+            reconciliation.matched.len(),
+            reconciliation.failed.len(),
+            reconciliation.not_applied.len(),
+            reconciliation.action_mismatches.len(),
+            reconciliation.unplanned.len())
+    };
+    html.push_str(&format!(r#"
+            </table>
+            {}
+        </div>"#, verdict));
+
+    if !reconciliation.failed.is_empty() {
+        html.push_str(r#"
+        <h2 class="failed">💥 Failed</h2>
+        <div class="panel">
+            <table>"#);
+        for failed in &reconciliation.failed {
+            html.push_str(&format!(r#"
+                <tr><td>{}</td><td class="address">{}</td><td class="failed">{}</td></tr>"#,
+                action_text(&failed.action),
+                html_escape(&failed.address),
+                failed.errors.iter().map(|error| html_escape(error)).collect::<Vec<_>>().join("<br>")));
+        }
+        html.push_str(r#"
+            </table>
+        </div>"#);
+    }
+
+    for (title, class, resources) in [
+        ("⏸️ Planned but Not Applied", "warning", &reconciliation.not_applied),
+        ("⚠️ Not in the Reviewed Plan", "failed", &reconciliation.unplanned),
+    ] {
+        if resources.is_empty() {
+            continue;
+        }
+        html.push_str(&format!(r#"
+        <h2 class="{}">{}</h2>
+        <div class="panel">
+            <table>"#, class, title));
+        for resource in resources {
+            html.push_str(&format!(r#"
+                <tr><td>{}</td><td class="address">{}</td></tr>"#,
+                action_text(&resource.action), html_escape(&resource.id)));
+        }
+        html.push_str(r#"
+            </table>
+        </div>"#);
+    }
+
+    if !reconciliation.action_mismatches.is_empty() {
+        html.push_str(r#"
+        <h2 class="warning">🔀 Applied with a Different Action</h2>
+        <div class="panel">
+            <table>
+                <tr><th>Resource</th><th>Planned</th><th>Applied</th></tr>"#);
+        for mismatch in &reconciliation.action_mismatches {
+            html.push_str(&format!(r#"
+                <tr><td class="address">{}</td><td>{}</td><td>{}</td></tr>"#,
+                html_escape(&mismatch.address), action_text(&mismatch.planned), action_text(&mismatch.applied)));
+        }
+        html.push_str(r#"
+            </table>
+        </div>"#);
+    }
+
+    if !reconciliation.errors.is_empty() {
+        html.push_str(r#"
+        <h2 class="failed">❌ Errors</h2>
+        <div class="panel">"#);
+        for error in &reconciliation.errors {
+            html.push_str(&format!(r#"
+            <p class="failed">{}</p>"#, html_escape(error)));
+        }
+        html.push_str(r#"
+        </div>"#);
+    }
+
+    html.push_str(r#"
+    </div>
+</body>
+</html>"#);
+
+    html
+}
@@ -0,0 +1,117 @@
+use crate::formatter::grouping::GroupBy;
+use crate::{Result, TerraformPlan, TfdiffError};
+use std::path::PathBuf;
+
+/// How much a report says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only the summary
+    Quiet,
+    #[default]
+    Normal,
+    /// Also each change the ignore rules suppressed
+    Verbose,
+}
+
+/// Settings shared by every output format. A format ignores the ones that do not apply to it.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Columns to lay text out in; the terminal's width if not set
+    pub width: Option<usize>,
+    /// Whether the report may use terminal colors
+    pub color: bool,
+    pub verbosity: Verbosity,
+    pub group_by: GroupBy,
+    /// The file the plan was read from, for reports that point into it
+    pub source: Option<PathBuf>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            width: None,
+            color: true,
+            verbosity: Verbosity::default(),
+            group_by: GroupBy::default(),
+            source: None,
+        }
+    }
+}
+
+/// An output format, selected by name with `--format`.
+pub trait Formatter: Send + Sync {
+    /// The name used with `--format`, e.g. `markdown`
+    fn name(&self) -> &str;
+
+    /// One line for `--list-formats`
+    fn description(&self) -> &str;
+
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String>;
+}
+
+/// The output formats available by name, in the order they are listed.
+pub struct FormatterRegistry {
+    formatters: Vec<Box<dyn Formatter>>,
+}
+
+impl Default for FormatterRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl FormatterRegistry {
+    /// A registry without any formats.
+    pub fn empty() -> Self {
+        FormatterRegistry { formatters: Vec::new() }
+    }
+
+    /// The formats that ship with tfdiff.
+    pub fn builtin() -> Self {
+        let mut registry = Self::empty();
+        registry.register(crate::formatter::TerminalFormatter);
+        registry.register(crate::formatter::JsonFormatter);
+        registry.register(crate::formatter::HtmlFormatter);
+        registry.register(crate::formatter::MarkdownFormatter);
+        registry.register(crate::formatter::JunitFormatter);
+        registry.register(crate::formatter::SarifFormatter);
+        registry.register(crate::formatter::PrCommentFormatter);
+        registry.register(crate::formatter::SlackFormatter);
+        registry.register(crate::formatter::TeamsFormatter);
+        registry
+    }
+
+    /// Add a format, replacing the one with the same name if there is one.
+    pub fn register(&mut self, formatter: impl Formatter + 'static) {
+        let formatter: Box<dyn Formatter> = Box::new(formatter);
+        match self.formatters.iter_mut().find(|known| known.name() == formatter.name()) {
+            Some(known) => *known = formatter,
+            None => self.formatters.push(formatter),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Formatter> {
+        self.formatters.iter().find(|formatter| formatter.name() == name).map(|formatter| formatter.as_ref())
+    }
+
+    pub fn formatters(&self) -> impl Iterator<Item = &dyn Formatter> {
+        self.formatters.iter().map(|formatter| formatter.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.formatters().map(|formatter| formatter.name()).collect()
+    }
+
+    /// The format registered as `name`, or an error listing the known ones.
+    pub fn lookup(&self, name: &str) -> Result<&dyn Formatter> {
+        self.get(name).ok_or_else(|| TfdiffError::ConfigError(format!(
+            "unknown format '{}' (expected {})", name, self.names().join(", ")
+        )))
+    }
+
+    /// Render the plan in the format registered as `name`. Secrets are masked
+    /// with `RedactionRules::apply` before, as the plan is loaded.
+    pub fn format(&self, name: &str, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        self.lookup(name)?.format(plan, options)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::formatter::registry::{FormatOptions, Formatter};
+use crate::risk::assess_plan;
+use crate::{DiagnosticKind, PolicyOutcome, Resource, Result, RiskLevel, SourceSpan, TerraformPlan, WarningLevel};
+use serde_json::{json, Value};
+use std::path::{Component, Path, PathBuf};
+
+/// A rule the report's results refer to, listed once in the tool's metadata.
+struct Rule {
+    id: String,
+    description: String,
+    level: &'static str,
+}
+
+/// Render policy violations, risky changes and parse diagnostics as a SARIF
+/// 2.1.0 log for code-scanning dashboards.
+///
+/// `artifact` is the path of the input file. Results carry the input lines
+/// they come from as physical locations when it is known, and the resource
+/// address as a logical location.
+pub fn format_sarif_output(plan: &TerraformPlan, artifact: Option<&Path>) -> Result<String> {
+    let artifact = artifact.map(artifact_location);
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut results: Vec<Value> = Vec::new();
+    let mut add_rule = |rule: Rule| {
+        if !rules.iter().any(|known| known.id == rule.id) {
+            rules.push(rule);
+        }
+    };
+    let location = |span: Option<&SourceSpan>, address: Option<&str>| -> Vec<Value> {
+        let mut location = serde_json::Map::new();
+        if let (Some(artifact), Some(span)) = (&artifact, span) {
+            location.insert("physicalLocation".to_string(), json!({
+                "artifactLocation": artifact,
+                "region": { "startLine": span.start_line, "endLine": span.end_line },
+            }));
+        }
+        if let Some(address) = address {
+            location.insert("logicalLocations".to_string(), json!([{ "fullyQualifiedName": address, "kind": "resource" }]));
+        }
+        if location.is_empty() { Vec::new() } else { vec![Value::Object(location)] }
+    };
+    let resource = |address: &str| -> Option<&Resource> { plan.resources.iter().find(|resource| resource.id == address) };
+
+    for policy in plan.policy_results.iter().filter(|result| result.outcome != PolicyOutcome::Pass || result.waived_by.is_some()) {
+        let id = format!("policy/{}", policy.policy);
+        let level = match policy.outcome {
+            PolicyOutcome::Warn => "warning",
+            _ => "error",
+        };
+        add_rule(Rule {
+            id: id.clone(),
+            description: policy.description.clone().unwrap_or_else(|| format!("Policy {}", policy.policy)),
+            level,
+        });
+
+        // Violations name one resource each, except for a change limit
+        let violations: Vec<(&String, Option<&String>)> = if policy.addresses.len() == policy.violations.len() {
+            policy.violations.iter().zip(policy.addresses.iter().map(Some)).collect()
+        } else {
+            policy.violations.iter().map(|violation| (violation, None)).collect()
+        };
+        for (violation, address) in violations {
+            let span = address.and_then(|address| resource(address)).and_then(|resource| resource.span.as_ref());
+            let mut result = json!({
+                "ruleId": id,
+                "level": level,
+                "message": { "text": format!("{}: {}", policy.policy, violation) },
+                "locations": location(span, address.map(String::as_str)),
+            });
+            if let Some(label) = &policy.waived_by {
+                result["suppressions"] = json!([{ "kind": "external", "justification": format!("allowed by label {}", label) }]);
+            }
+            results.push(result);
+        }
+    }
+
+    let risk = assess_plan(plan);
+    for (resource, resource_risk) in plan.resources.iter().zip(&risk.resources) {
+        let (id, level) = match resource_risk.level {
+            RiskLevel::High => ("risk/high", "warning"),
+            RiskLevel::Medium => ("risk/medium", "note"),
+            RiskLevel::Low | RiskLevel::None => continue,
+        };
+        add_rule(Rule {
+            id: id.to_string(),
+            description: format!("{} risk change", if resource_risk.level == RiskLevel::High { "High" } else { "Medium" }),
+            level,
+        });
+        results.push(json!({
+            "ruleId": id,
+            "level": level,
+            "message": { "text": format!("{} will be {} (risk {}/100: {})",
+                resource.id, resource.action_description(), resource_risk.score, resource_risk.factors.join(", ")) },
+            "locations": location(resource.span.as_ref(), Some(&resource.id)),
+            "properties": { "riskScore": resource_risk.score },
+        }));
+    }
+
+    for warning in &plan.warnings {
+        let (id, description) = match warning.kind {
+            Some(DiagnosticKind::UnrecognizedLine) => ("parser/unrecognized-line", "Line the parser does not recognize"),
+            Some(DiagnosticKind::UnbalancedBraces) => ("parser/unbalanced-braces", "Resource block with unbalanced braces"),
+            Some(DiagnosticKind::SummaryMismatch) => ("parser/summary-mismatch", "Parsed resources disagree with Terraform's summary"),
+            Some(DiagnosticKind::TruncatedInput) => ("parser/truncated-input", "Input ends in the middle of the plan"),
+            None => ("terraform/diagnostic", "Warning or error reported by Terraform"),
+        };
+        let level = match warning.level {
+            WarningLevel::Info => "note",
+            WarningLevel::Warning => "warning",
+            WarningLevel::Error => "error",
+        };
+        add_rule(Rule { id: id.to_string(), description: description.to_string(), level });
+        results.push(json!({
+            "ruleId": id,
+            "level": level,
+            "message": { "text": warning.message },
+            "locations": location(warning.span.as_ref(), warning.address.as_deref()),
+        }));
+    }
+
+    let rules: Vec<Value> = rules.iter()
+        .map(|rule| json!({
+            "id": rule.id,
+            "shortDescription": { "text": rule.description },
+            "defaultConfiguration": { "level": rule.level },
+        }))
+        .collect();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "tfdiff",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+/// Where the input file is, as SARIF expects it: a URI relative to
+/// `%SRCROOT%` (the working directory) when the file is under it, otherwise
+/// an absolute `file://` URI.
+fn artifact_location(path: &Path) -> Value {
+    let cwd = std::env::current_dir().map(|cwd| normalize(&cwd)).unwrap_or_default();
+    let absolute = normalize(&cwd.join(path));
+    match absolute.strip_prefix(&cwd) {
+        Ok(relative) if !cwd.as_os_str().is_empty() => json!({ "uri": uri_path(relative), "uriBaseId": "%SRCROOT%" }),
+        _ => json!({ "uri": format!("file:///{}", uri_path(&absolute)) }),
+    }
+}
+
+/// Resolve `.` and `..` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// `/`-separated, percent-encoded path segments; a Windows drive prefix is kept as is.
+fn uri_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().into_owned()),
+            Component::Normal(segment) => Some(percent_encode(&segment.to_string_lossy())),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn percent_encode(segment: &str) -> String {
+    segment.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// SARIF with the input file as the artifact results point into.
+pub struct SarifFormatter;
+
+impl Formatter for SarifFormatter {
+    fn name(&self) -> &str {
+        "sarif"
+    }
+
+    fn description(&self) -> &str {
+        "SARIF 2.1.0 log for code-scanning dashboards"
+    }
+
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        format_sarif_output(plan, options.source.as_deref())
+    }
+}
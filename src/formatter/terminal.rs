@@ -1,35 +1,66 @@
-use crate::{TerraformPlan, ActionType, Change};
+use crate::{TerraformPlan, ActionType, Change, PlanCost, PolicyOutcome, PolicyResult, ResourceAnnotation, ResourceClass, Result, RiskLevel};
+use crate::risk::assess_plan;
+use crate::semantic::{classify_change, ChangeClass};
+use crate::formatter::grouping::{GroupBy, group_resources};
+use crate::formatter::layout::{display_width, pad_to_width, terminal_width, truncate_to_width, wrap_to_width, DEFAULT_WIDTH, MIN_WIDTH};
+use crate::formatter::registry::{FormatOptions, Formatter, Verbosity};
+use crate::parser::clean_ansi_codes;
 use colored::*;
 use std::collections::HashMap;
 use serde_json::Value;
 
 pub fn format_terminal_output(plan: &TerraformPlan) -> String {
+    format_terminal_output_grouped(plan, GroupBy::None)
+}
+
+pub fn format_terminal_output_grouped(plan: &TerraformPlan, group_by: GroupBy) -> String {
+    format_terminal_output_with_width(plan, group_by, DEFAULT_WIDTH)
+}
+
+/// The report laid out in `width` columns, at least `MIN_WIDTH`.
+pub fn format_terminal_output_with_width(plan: &TerraformPlan, group_by: GroupBy, width: usize) -> String {
+    terminal_report(plan, group_by, width, Verbosity::Normal)
+}
+
+/// The full report; verbose reports also list each change the ignore rules suppressed.
+fn terminal_report(plan: &TerraformPlan, group_by: GroupBy, width: usize, verbosity: Verbosity) -> String {
+    let width = width.max(MIN_WIDTH);
     let mut output = String::new();
     
     // Header with improved styling
     output.push_str(&"╔".bright_blue().to_string());
-    output.push_str(&"═".repeat(78).bright_blue().to_string());
+    output.push_str(&"═".repeat(width - 2).bright_blue().to_string());
     output.push_str(&"╗".bright_blue().to_string());
     output.push('\n');
     
-    let mode_text = match plan.mode {
-        crate::PlanMode::Plan => "TERRAFORM PLAN ANALYSIS".bright_cyan(),
-        crate::PlanMode::Apply => "TERRAFORM APPLY ANALYSIS".bright_yellow(),
+    let (mode_text, mode_color): (&str, fn(&str) -> ColoredString) = match plan.mode {
+        crate::PlanMode::Plan => ("TERRAFORM PLAN ANALYSIS", |text| text.bright_cyan()),
+        crate::PlanMode::Apply => ("TERRAFORM APPLY ANALYSIS", |text| text.bright_yellow()),
+    };
+    output.push_str(&header_line(mode_text, width, mode_color));
+    
+    let risk = assess_plan(plan);
+    let badge = format!("RISK: {} ({}/100)", risk.level.to_string().to_uppercase(), risk.score);
+    let badge_color: fn(&str) -> ColoredString = match risk.level {
+        RiskLevel::High => |text| text.bright_red().bold(),
+        RiskLevel::Medium => |text| text.bright_yellow().bold(),
+        RiskLevel::Low => |text| text.bright_green(),
+        RiskLevel::None => |text| text.dimmed(),
     };
-    output.push_str(&format!("║{:^78}║\n", mode_text));
+    output.push_str(&header_line(&badge, width, badge_color));
     
     output.push_str(&"╚".bright_blue().to_string());
-    output.push_str(&"═".repeat(78).bright_blue().to_string());
+    output.push_str(&"═".repeat(width - 2).bright_blue().to_string());
     output.push_str(&"╝".bright_blue().to_string());
     output.push('\n');
     output.push('\n');
     
     // Enhanced Summary Section
     if plan.summary.add > 0 || plan.summary.change > 0 || plan.summary.destroy > 0 || plan.summary.read > 0 {
-        output.push_str(&"📊 ".to_string());
+        output.push_str("📊 ");
         output.push_str(&"PLAN SUMMARY".bright_white().bold().to_string());
         output.push('\n');
-        output.push_str(&"─".repeat(80).dimmed().to_string());
+        output.push_str(&"─".repeat(width).dimmed().to_string());
         output.push('\n');
         output.push('\n');
         
@@ -58,56 +89,113 @@ pub fn format_terminal_output(plan: &TerraformPlan) -> String {
                 plan.summary.read.to_string().bright_cyan().bold()
             ));
         }
+        if let Some(cost) = &plan.cost {
+            output.push_str(&format!("\n  💰 Monthly cost: {}\n", format_cost_line(cost).bright_white().bold()));
+            let column = cost.resources.iter()
+                .map(|resource_cost| display_width(&resource_cost.address))
+                .max().unwrap_or(0)
+                .min(width / 2);
+            for resource_cost in &cost.resources {
+                output.push_str(&format!("      {}  {}\n",
+                    pad_to_width(&truncate_to_width(&resource_cost.address, column), column).dimmed(),
+                    format_resource_cost(resource_cost, &cost.currency)
+                ));
+            }
+        }
+        output.push('\n');
+        output.push_str(&"═".repeat(width).bright_blue().to_string());
+        output.push('\n');
+        output.push('\n');
+    }
+    
+    if let Some(note) = format_suppressed_note(plan) {
+        for (index, line) in wrap_to_width(&note, width - 3).iter().enumerate() {
+            output.push_str(&format!("{} {}\n", if index == 0 { "🔇" } else { "  " }, line.dimmed()));
+        }
+        if verbosity == Verbosity::Verbose {
+            for suppressed in &plan.suppressed {
+                let entry = format!("{} {} ({})", suppressed.address, suppressed.path, suppressed.reason);
+                for (index, line) in wrap_to_width(&entry, width - 5).iter().enumerate() {
+                    output.push_str(&format!("   {} {}\n", if index == 0 { "·" } else { " " }, line.dimmed()));
+                }
+            }
+        }
+        output.push('\n');
+    }
+    
+    // Policy checks come before the resources so a failing gate is seen first
+    if !plan.policy_results.is_empty() {
+        output.push_str("🛡️  ");
+        output.push_str(&"POLICY CHECKS".bright_white().bold().to_string());
         output.push('\n');
-        output.push_str(&"═".repeat(80).bright_blue().to_string());
+        output.push_str(&"─".repeat(width).dimmed().to_string());
         output.push('\n');
         output.push('\n');
+        
+        for result in &plan.policy_results {
+            output.push_str(&format_policy_result(result));
+        }
+        output.push('\n');
     }
     
     // Resources with enhanced formatting
     if !plan.resources.is_empty() {
-        output.push_str(&"🔧 ".to_string());
+        output.push_str("🔧 ");
         output.push_str(&"RESOURCES".bright_white().bold().to_string());
         output.push('\n');
-        output.push_str(&"─".repeat(80).dimmed().to_string());
+        output.push_str(&"─".repeat(width).dimmed().to_string());
         output.push('\n');
         output.push('\n');
         
-        for resource in &plan.resources {
-            output.push_str(&format_resource(resource));
-            output.push('\n');
+        for (label, resources) in group_resources(&plan.resources, group_by) {
+            if group_by != GroupBy::None {
+                output.push_str(&format!("{} {} {}\n\n",
+                    "▸".bright_magenta(),
+                    label.bright_magenta().bold(),
+                    format!("({})", resources.len()).dimmed()
+                ));
+            }
+            for resource in resources {
+                let annotations: Vec<&ResourceAnnotation> = plan.annotations.iter()
+                    .filter(|annotation| annotation.address == resource.id)
+                    .collect();
+                output.push_str(&format_resource_with_width(resource, &annotations, width));
+                output.push('\n');
+            }
         }
     }
     
     // Data sources section
     if !plan.data_sources.is_empty() {
-        output.push_str(&"📊 ".to_string());
+        output.push_str("📊 ");
         output.push_str(&"DATA SOURCES".bright_white().bold().to_string());
         output.push('\n');
-        output.push_str(&"─".repeat(80).dimmed().to_string());
+        output.push_str(&"─".repeat(width).dimmed().to_string());
         output.push('\n');
         output.push('\n');
         
         for data_source in &plan.data_sources {
-            output.push_str(&format_data_source(data_source));
+            output.push_str(&format_data_source(data_source, width));
             output.push('\n');
         }
     }
     
     // Warnings section
     if !plan.warnings.is_empty() {
-        output.push_str(&"⚠️  ".to_string());
+        output.push_str("⚠️  ");
         output.push_str(&"WARNINGS".bright_yellow().bold().to_string());
         output.push('\n');
-        output.push_str(&"─".repeat(80).dimmed().to_string());
+        output.push_str(&"─".repeat(width).dimmed().to_string());
         output.push('\n');
         output.push('\n');
         
         for warning in &plan.warnings {
-            output.push_str(&format!("  {} {}\n", 
-                "•".bright_yellow(),
-                warning.message.bright_yellow()
-            ));
+            for (index, line) in wrap_to_width(&warning.message, width - 4).iter().enumerate() {
+                output.push_str(&format!("  {} {}\n", 
+                    if index == 0 { "•".bright_yellow() } else { " ".normal() },
+                    line.bright_yellow()
+                ));
+            }
         }
         output.push('\n');
     }
@@ -115,6 +203,14 @@ pub fn format_terminal_output(plan: &TerraformPlan) -> String {
     output
 }
 
+/// A line of the header box with the text centered in it, cut to fit.
+pub(crate) fn header_line(text: &str, width: usize, color: fn(&str) -> ColoredString) -> String {
+    let inner = width - 2;
+    let text = truncate_to_width(text, inner);
+    let space = inner - display_width(&text);
+    format!("║{}{}{}║\n", " ".repeat(space / 2), color(&text), " ".repeat(space - space / 2))
+}
+
 pub fn format_summary_line(summary: &crate::Summary) -> String {
     let mut parts = Vec::new();
     
@@ -134,7 +230,156 @@ pub fn format_summary_line(summary: &crate::Summary) -> String {
     parts.join("  │  ")
 }
 
+/// How many changes ignore rules left out, and why, e.g.
+/// `3 changes suppressed by ignore rules (2 × ignore rule: tags_all, 1 × whitespace only)`.
+pub fn format_suppressed_note(plan: &TerraformPlan) -> Option<String> {
+    if plan.suppressed.is_empty() {
+        return None;
+    }
+    
+    let mut counts: Vec<(&crate::SuppressionReason, usize)> = Vec::new();
+    for suppressed in &plan.suppressed {
+        match counts.iter_mut().find(|(reason, _)| *reason == &suppressed.reason) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((&suppressed.reason, 1)),
+        }
+    }
+    let reasons: Vec<String> = counts.iter()
+        .map(|(reason, count)| format!("{} × {}", count, reason))
+        .collect();
+    
+    Some(format!("{} change{} suppressed by ignore rules ({})",
+        plan.suppressed.len(),
+        if plan.suppressed.len() == 1 { "" } else { "s" },
+        reasons.join(", ")
+    ))
+}
+
+/// An amount of money, e.g. `$1,234.50` or `1,234.50 EUR`.
+pub fn format_money(amount: f64, currency: &str) -> String {
+    let cents = (amount.abs() * 100.0).round() as u64;
+    let digits = (cents / 100).to_string();
+    let mut whole = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            whole.push(',');
+        }
+        whole.push(digit);
+    }
+    let sign = if amount < 0.0 && cents > 0 { "-" } else { "" };
+    let number = format!("{}.{:02}", whole, cents % 100);
+    
+    match currency {
+        "USD" => format!("{}${}", sign, number),
+        "EUR" => format!("{}€{}", sign, number),
+        "GBP" => format!("{}£{}", sign, number),
+        other => format!("{}{} {}", sign, number, other),
+    }
+}
+
+/// A change in cost with its sign, e.g. `+$12.00` or `-$3.65`.
+pub fn format_money_change(amount: f64, currency: &str) -> String {
+    let money = format_money(amount, currency);
+    if money.starts_with('-') { money } else { format!("+{}", money) }
+}
+
+/// The plan's monthly cost in one line, e.g. `$120.00 → $180.50 (+$60.50)`,
+/// noting changes whose cost is unknown.
+pub fn format_cost_line(cost: &PlanCost) -> String {
+    let mut line = format!("{} → {} ({})",
+        format_money(cost.before, &cost.currency),
+        format_money(cost.after, &cost.currency),
+        format_money_change(cost.delta, &cost.currency)
+    );
+    let unknown = cost.unknown();
+    if unknown > 0 {
+        line.push_str(&format!(", {} change{} of unknown cost", unknown, if unknown == 1 { "" } else { "s" }));
+    }
+    line
+}
+
+/// One resource's monthly cost, e.g. `$0.00 → $15.18 (+$15.18)`; prices
+/// that cannot be worked out show as `?`.
+pub fn format_resource_cost(cost: &crate::ResourceCost, currency: &str) -> String {
+    let price = |amount: Option<f64>| amount.map(|amount| format_money(amount, currency)).unwrap_or_else(|| "?".to_string());
+    match (cost.delta, &cost.note) {
+        (Some(delta), _) => format!("{} → {} ({})", price(cost.before), price(cost.after), format_money_change(delta, currency)),
+        (None, Some(note)) => format!("unknown cost: {}", note),
+        (None, None) => "unknown cost".to_string(),
+    }
+}
+
+/// Policy results counted by outcome, e.g. `1 failed, 1 warning, 2 passed`.
+pub fn format_policy_counts(results: &[PolicyResult]) -> Option<String> {
+    if results.is_empty() {
+        return None;
+    }
+    
+    let count = |outcome: PolicyOutcome| results.iter().filter(|result| result.outcome == outcome).count();
+    let (failed, warned, passed) = (count(PolicyOutcome::Fail), count(PolicyOutcome::Warn), count(PolicyOutcome::Pass));
+    let mut parts = Vec::new();
+    if failed > 0 {
+        parts.push(format!("{} failed", failed));
+    }
+    if warned > 0 {
+        parts.push(format!("{} warning{}", warned, if warned == 1 { "" } else { "s" }));
+    }
+    if passed > 0 {
+        parts.push(format!("{} passed", passed));
+    }
+    
+    Some(parts.join(", "))
+}
+
+fn format_policy_result(result: &PolicyResult) -> String {
+    let badge = match result.outcome {
+        PolicyOutcome::Pass => format!("{} PASS", "✓").bright_green().bold(),
+        PolicyOutcome::Warn => format!("{} WARN", "!").bright_yellow().bold(),
+        PolicyOutcome::Fail => format!("{} FAIL", "✗").bright_red().bold(),
+    };
+    let mut output = format!("  {} {}", badge, result.policy.bright_white().bold());
+    if let Some(description) = &result.description {
+        output.push_str(&format!(" {}", format!("— {}", description).dimmed()));
+    }
+    if let Some(label) = &result.waived_by {
+        output.push_str(&format!(" {}", format!("(allowed by label {})", label).dimmed()));
+    }
+    output.push('\n');
+    
+    for violation in &result.violations {
+        let line = match (result.outcome, result.waived_by.is_some()) {
+            (_, true) => violation.dimmed(),
+            (PolicyOutcome::Warn, _) => violation.yellow(),
+            _ => violation.red(),
+        };
+        output.push_str(&format!("      {} {}\n", "•".dimmed(), line));
+    }
+    
+    output
+}
+
+/// The icon shown next to catalog annotations of a resource class.
+pub fn annotation_icon(class: ResourceClass) -> &'static str {
+    match class {
+        ResourceClass::Stateful => "💾",
+        ResourceClass::SecuritySensitive => "🔐",
+        ResourceClass::NetworkCritical => "🌐",
+        ResourceClass::CheapToRecreate => "♻️",
+    }
+}
+
 pub fn format_resource(resource: &crate::Resource) -> String {
+    format_resource_with_annotations(resource, &[])
+}
+
+/// A resource with its catalog annotations under the header.
+pub fn format_resource_with_annotations(resource: &crate::Resource, annotations: &[&ResourceAnnotation]) -> String {
+    format_resource_with_width(resource, annotations, DEFAULT_WIDTH)
+}
+
+/// A resource with its annotations, laid out in `width` columns.
+pub fn format_resource_with_width(resource: &crate::Resource, annotations: &[&ResourceAnnotation], width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
     let mut output = String::new();
     
     // Resource header with action badge
@@ -155,11 +400,27 @@ pub fn format_resource(resource: &crate::Resource) -> String {
             resource.type_name.bright_blue()
         ));
     }
+    if !resource.provider.is_empty() && resource.provider != "unknown" {
+        output.push_str(&format!("  {} {}\n", 
+            "Provider:".dimmed(),
+            resource.provider.bright_blue()
+        ));
+    }
+    
+    for annotation in annotations {
+        let message = match annotation.class {
+            ResourceClass::Stateful => annotation.message.bright_red(),
+            ResourceClass::SecuritySensitive => annotation.message.bright_magenta(),
+            ResourceClass::NetworkCritical => annotation.message.bright_yellow(),
+            ResourceClass::CheapToRecreate => annotation.message.dimmed(),
+        };
+        output.push_str(&format!("  {} {}\n", annotation_icon(annotation.class), message));
+    }
     
     // If there are changes, show them in a diff-like format
     if !resource.changes.is_empty() {
         output.push('\n');
-        output.push_str(&format_changes(&resource.changes, &resource.action));
+        output.push_str(&format_changes(&resource.changes, &resource.action, width));
     }
     
     // Show attributes if no changes are present (for backwards compatibility)
@@ -189,24 +450,7 @@ pub fn format_resource(resource: &crate::Resource) -> String {
                             key.bright_cyan()
                         ));
                         
-                        let max_width = 40;
-                        let before_truncated = if before.len() > max_width {
-                            format!("{}...", &before[..max_width-3])
-                        } else {
-                            before.to_string()
-                        };
-                        let after_truncated = if after.len() > max_width {
-                            format!("{}...", &after[..max_width-3])
-                        } else {
-                            after.to_string()
-                        };
-                        
-                        output.push_str(&format!("    {} {:<40} │ {} {}\n",
-                            "─".bright_red(),
-                            before_truncated.bright_red(),
-                            "+".bright_green(),
-                            after_truncated.bright_green()
-                        ));
+                        output.push_str(&side_by_side(before, after, width, false));
                         continue;
                     }
                 }
@@ -220,13 +464,34 @@ pub fn format_resource(resource: &crate::Resource) -> String {
         }
     }
     
-    output.push_str(&"─".repeat(80).dimmed().to_string());
+    output.push_str(&"─".repeat(width).dimmed().to_string());
     output.push('\n');
     
     output
 }
 
-fn format_changes(changes: &[Change], _action: &ActionType) -> String {
+/// A value's before and after in two columns, each cut to half the width.
+fn side_by_side(before: &str, after: &str, width: usize, dimmed: bool) -> String {
+    // "    ─ " before " │ + " after
+    let column = (width.saturating_sub(11) / 2).max(1);
+    let before = pad_to_width(&truncate_to_width(before, column), column);
+    let after = truncate_to_width(after, column);
+    if dimmed {
+        format!("    {} {} │ {} {}\n", "─".dimmed(), before.dimmed(), "+".dimmed(), after.dimmed())
+    } else {
+        format!("    {} {} │ {} {}\n", "─".bright_red(), before.bright_red(), "+".bright_green(), after.bright_green())
+    }
+}
+
+/// A value on one line, without colors, for the side-by-side columns.
+fn inline_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s),
+        other => other.to_string(),
+    }
+}
+
+fn format_changes(changes: &[Change], _action: &ActionType, width: usize) -> String {
     let mut output = String::new();
     
     // Group changes by their path for better organization
@@ -237,7 +502,7 @@ fn format_changes(changes: &[Change], _action: &ActionType) -> String {
         } else {
             change.path[0].clone()
         };
-        grouped_changes.entry(path_key).or_insert_with(Vec::new).push(change);
+        grouped_changes.entry(path_key).or_default().push(change);
     }
     
     for change in changes {
@@ -279,35 +544,25 @@ fn format_changes(changes: &[Change], _action: &ActionType) -> String {
                     }
                 }
                 
-                // Modification - show side by side with aligned columns
-                let before_formatted = format_json_value(before, 0);
-                let after_formatted = format_json_value(after, 0);
+                // Cosmetic changes are dimmed so the real ones stand out
+                if let ChangeClass::Cosmetic(reason) = classify_change(change) {
+                    output.push_str(&format!("  {} {} {}\n",
+                        "≈".dimmed(),
+                        path_str.dimmed(),
+                        format!("({})", reason).italic().dimmed()
+                    ));
+                    output.push_str(&side_by_side(&before.to_string(), &after.to_string(), width, true));
+                    continue;
+                }
                 
+                // Modification - show side by side with aligned columns
                 output.push_str(&format!("  {} {}\n", 
                     "~".bright_yellow().bold(),
                     path_str.bright_cyan()
                 ));
                 
-                // Calculate column widths for alignment
-                let max_width = 40; // Maximum width for each column
-                let before_truncated = if before_formatted.len() > max_width {
-                    format!("{}...", &before_formatted[..max_width-3])
-                } else {
-                    before_formatted.clone()
-                };
-                let after_truncated = if after_formatted.len() > max_width {
-                    format!("{}...", &after_formatted[..max_width-3])
-                } else {
-                    after_formatted.clone()
-                };
-                
                 // Show side-by-side with clear visual separation
-                output.push_str(&format!("    {} {:<40} │ {} {}\n",
-                    "─".bright_red(),
-                    before_truncated.bright_red(),
-                    "+".bright_green(),
-                    after_truncated.bright_green()
-                ));
+                output.push_str(&side_by_side(&inline_value(before), &inline_value(after), width, false));
             },
             _ => {
                 // No change or computed values
@@ -323,7 +578,7 @@ fn format_changes(changes: &[Change], _action: &ActionType) -> String {
         
         if change.sensitive {
             output.push_str(&format!("    {} {}\n",
-                "🔒".to_string(),
+                "🔒",
                 "(sensitive value)".italic().dimmed()
             ));
         }
@@ -385,7 +640,7 @@ fn format_json_value(value: &Value, indent_level: usize) -> String {
     }
 }
 
-fn format_data_source(data_source: &crate::DataSource) -> String {
+fn format_data_source(data_source: &crate::DataSource, width: usize) -> String {
     let mut output = String::new();
     
     let action_badge = format!(" {} ", "READ").on_bright_cyan().black().bold();
@@ -407,8 +662,47 @@ fn format_data_source(data_source: &crate::DataSource) -> String {
         ));
     }
     
-    output.push_str(&"─".repeat(80).dimmed().to_string());
+    output.push_str(&"─".repeat(width).dimmed().to_string());
     output.push('\n');
     
     output
-}
\ No newline at end of file
+}
+
+/// The summary counts, policy results and cost in a few plain lines, for `--summary`.
+pub fn format_terminal_summary(plan: &TerraformPlan) -> String {
+    let mut summary = format!("Summary: {} to add, {} to change, {} to destroy",
+        plan.summary.add,
+        plan.summary.change,
+        plan.summary.destroy
+    );
+    if let Some(counts) = format_policy_counts(&plan.policy_results) {
+        summary.push_str(&format!("\nPolicy checks: {}", counts));
+    }
+    if let Some(cost) = &plan.cost {
+        summary.push_str(&format!("\nMonthly cost: {}", format_cost_line(cost)));
+    }
+    summary
+}
+
+/// The default report, drawn with colors and box characters for a terminal.
+pub struct TerminalFormatter;
+
+impl Formatter for TerminalFormatter {
+    fn name(&self) -> &str {
+        "terminal"
+    }
+    
+    fn description(&self) -> &str {
+        "Colored report for the terminal (default)"
+    }
+    
+    fn format(&self, plan: &TerraformPlan, options: &FormatOptions) -> Result<String> {
+        let output = match options.verbosity {
+            Verbosity::Quiet => format_terminal_summary(plan),
+            Verbosity::Normal | Verbosity::Verbose => {
+                terminal_report(plan, options.group_by, options.width.unwrap_or_else(terminal_width), options.verbosity)
+            },
+        };
+        Ok(if options.color { output } else { clean_ansi_codes(&output) })
+    }
+}
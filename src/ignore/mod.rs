@@ -0,0 +1,195 @@
+use crate::config::{load_toml_file, parse_toml};
+use crate::query::{glob, path_matches};
+use crate::semantic::{classify_change, ChangeClass};
+use crate::{ActionType, Change, Result, SuppressedChange, SuppressionReason, TerraformPlan, TfdiffError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Rules for leaving known-benign changes out of a report, read from a TOML file:
+///
+/// ```toml
+/// order_only_lists = true
+/// whitespace_only = true
+/// cosmetic_changes = true
+/// unknown_only_updates = true
+///
+/// [[rule]]
+/// attribute = "tags_all"
+///
+/// [[rule]]
+/// address = "module.legacy.*"
+/// attribute = "metadata"
+/// reason = "managed by the platform team"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IgnoreRules {
+    /// Ignore lists whose elements only changed order
+    pub order_only_lists: bool,
+    /// Ignore strings that only changed in whitespace
+    pub whitespace_only: bool,
+    /// Ignore update attributes whose new value is only `(known after apply)`
+    pub unknown_only_updates: bool,
+    /// Ignore changes that leave the value equivalent, such as reordered JSON keys or `"1"` to `1`
+    pub cosmetic_changes: bool,
+    #[serde(rename = "rule")]
+    pub rules: Vec<IgnoreRule>,
+}
+
+/// Ignores the changes to matching attributes of matching resources.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IgnoreRule {
+    /// Resource addresses or types, wildcards allowed; every resource if not set
+    pub address: Option<String>,
+    /// Attribute path; `tags` also covers `tags.Name`. Every attribute if not set
+    pub attribute: Option<String>,
+    /// Why the changes do not matter, shown in reports
+    pub reason: Option<String>,
+}
+
+impl IgnoreRule {
+    pub fn matches(&self, address: &str, type_name: &str, path: &str) -> bool {
+        let address_matches = self.address.as_deref()
+            .is_none_or(|pattern| glob(pattern, address) || glob(pattern, type_name));
+        let attribute_matches = self.attribute.as_deref()
+            .is_none_or(|pattern| path_matches(pattern, path));
+        address_matches && attribute_matches
+    }
+
+    fn description(&self) -> String {
+        if let Some(reason) = &self.reason {
+            return reason.clone();
+        }
+        match (&self.address, &self.attribute) {
+            (Some(address), Some(attribute)) => format!("{} on {}", attribute, address),
+            (Some(address), None) => address.clone(),
+            (None, Some(attribute)) => attribute.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl IgnoreRules {
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let rules: IgnoreRules = parse_toml(source)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml_file(path, Self::from_toml)
+    }
+
+    /// Reject rules that would hide every change.
+    pub fn validate(&self) -> Result<()> {
+        match self.rules.iter().position(|rule| rule.address.is_none() && rule.attribute.is_none()) {
+            Some(position) => Err(TfdiffError::ConfigError(format!(
+                "rule {} would ignore every change; give it an address or an attribute", position + 1
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the rules ignore nothing.
+    pub fn is_empty(&self) -> bool {
+        !self.order_only_lists
+            && !self.whitespace_only
+            && !self.unknown_only_updates
+            && !self.cosmetic_changes
+            && self.rules.is_empty()
+    }
+
+    /// Why a change to the resource at `address` should be left out, if it should.
+    pub fn suppression_reason(&self, address: &str, type_name: &str, action: &ActionType, change: &Change) -> Option<SuppressionReason> {
+        let path = change.path.join(".");
+        if let Some(rule) = self.rules.iter().find(|rule| rule.matches(address, type_name, &path)) {
+            return Some(SuppressionReason::Rule { description: rule.description() });
+        }
+
+        match (&change.before, &change.after) {
+            (Some(Value::Array(before)), Some(Value::Array(after))) if self.order_only_lists && is_reordered(before, after) => {
+                Some(SuppressionReason::OrderOnlyList)
+            }
+            (Some(Value::String(before)), Some(Value::String(after)))
+                if self.whitespace_only && before != after && before.split_whitespace().eq(after.split_whitespace()) => {
+                Some(SuppressionReason::WhitespaceOnly)
+            }
+            (_, Some(after)) if self.unknown_only_updates && *action == ActionType::Update && is_unknown(change, after) => {
+                Some(SuppressionReason::UnknownOnly)
+            }
+            _ if self.cosmetic_changes => match classify_change(change) {
+                ChangeClass::Cosmetic(reason) => Some(SuppressionReason::Cosmetic { reason }),
+                ChangeClass::Semantic => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Remove the ignored changes from the plan and record them in `plan.suppressed`.
+    ///
+    /// Updates left with no changes at all are removed too, and the summary is
+    /// recounted from the resources that remain. A replacement is never removed,
+    /// however little of it is left: the resource is still destroyed and created.
+    pub fn apply(&self, plan: &mut TerraformPlan) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut collapsed = Vec::new();
+        for resource in &mut plan.resources {
+            let had_changes = !resource.changes.is_empty();
+            let replaced = resource.replace || resource.changes.iter().any(|change| change.forces_replacement);
+            let (address, type_name, action) = (&resource.id, &resource.type_name, &resource.action);
+
+            resource.changes.retain(|change| match self.suppression_reason(address, type_name, action, change) {
+                Some(reason) => {
+                    plan.suppressed.push(SuppressedChange { address: address.clone(), path: change.path.join("."), reason });
+                    false
+                }
+                None => true,
+            });
+
+            // Creates and destroys from text plans carry their values as attributes
+            let mut keys: Vec<String> = resource.attributes.keys().cloned().collect();
+            keys.sort();
+            for key in keys {
+                if let Some(rule) = self.rules.iter().find(|rule| rule.matches(address, type_name, &key)) {
+                    resource.attributes.remove(&key);
+                    plan.suppressed.push(SuppressedChange {
+                        address: address.clone(),
+                        path: key,
+                        reason: SuppressionReason::Rule { description: rule.description() },
+                    });
+                }
+            }
+
+            if *action == ActionType::Update && !replaced && had_changes && resource.changes.is_empty() {
+                collapsed.push(address.clone());
+            }
+        }
+
+        // Take the collapsed updates off both counts, so a gap between what
+        // Terraform reported and what was parsed still shows
+        if !collapsed.is_empty() {
+            plan.resources.retain(|resource| !collapsed.contains(&resource.id));
+            plan.summary.change = plan.summary.change.saturating_sub(collapsed.len());
+            plan.parsed_summary.change = plan.parsed_summary.change.saturating_sub(collapsed.len());
+        }
+    }
+}
+
+/// Both lists hold the same elements, in a different order.
+fn is_reordered(before: &[Value], after: &[Value]) -> bool {
+    let sorted = |values: &[Value]| {
+        let mut elements: Vec<String> = values.iter().map(Value::to_string).collect();
+        elements.sort();
+        elements
+    };
+    before != after && sorted(before) == sorted(after)
+}
+
+fn is_unknown(change: &Change, after: &Value) -> bool {
+    change.computed || after.as_str() == Some("(known after apply)")
+}
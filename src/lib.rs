@@ -1,11 +1,35 @@
 pub mod models;
 pub mod parser;
 pub mod formatter;
+pub mod compare;
+pub mod reconcile;
+pub mod query;
+pub mod ignore;
+pub mod semantic;
+pub mod redact;
+pub mod config;
+pub mod policy;
+pub mod risk;
+pub mod catalog;
+pub mod cost;
+pub mod template;
 pub mod ui;
 
 pub use models::*;
 pub use parser::*;
 pub use formatter::*;
+pub use compare::*;
+pub use reconcile::*;
+pub use query::*;
+pub use ignore::*;
+pub use semantic::*;
+pub use redact::*;
+pub use config::*;
+pub use policy::*;
+pub use risk::*;
+pub use catalog::*;
+pub use cost::*;
+pub use template::*;
 
 pub type Result<T> = std::result::Result<T, TfdiffError>;
 
@@ -14,6 +38,9 @@ pub enum TfdiffError {
     #[error("Failed to parse Terraform output: {0}")]
     ParseError(String),
     
+    #[error("Failed to parse Terraform output: {}", .0.iter().map(|d| d.to_string()).collect::<Vec<_>>().join("; "))]
+    InvalidInput(Vec<parser::ParseDiagnostic>),
+    
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     
@@ -25,4 +52,16 @@ pub enum TfdiffError {
     
     #[error("Web server error: {0}")]
     WebError(String),
+    
+    #[error("Watch error: {0}")]
+    WatchError(String),
+    
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+    
+    #[error("Template error: {0}")]
+    TemplateError(String),
 }
\ No newline at end of file
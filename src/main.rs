@@ -1,12 +1,18 @@
-use clap::Parser;
-use std::path::PathBuf;
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use std::path::{Path, PathBuf};
 use std::io::{self, Read, Write};
 use std::fs;
 use std::process::Command;
-use tfdiff::{parse_terraform_output, Result, TfdiffError};
-use tfdiff::formatter::{format_terminal_output, format_json_output, format_html_output, format_markdown_output};
+use tfdiff::{parse_terraform_output_with_options, policy_outcome, Config, ResourceCatalog, Expression, IgnoreRules, ParseMode, ParseOptions, PolicyOutcome, PlanTemplate, PolicySet, PriceTable, ResourceQuery, Result, TfdiffError};
+use tfdiff::ui::{start_web_server_with_state, InputWatcher, PlanStore, WebState};
+use tfdiff::formatter::{format_html_output_grouped, format_explain_output, terminal_width, FormatOptions, FormatterRegistry, Verbosity};
+use tfdiff::formatter::{format_comparison_terminal_output_with_width, format_comparison_json_output, format_comparison_html_output, format_comparison_markdown_output};
+use tfdiff::formatter::{format_reconciliation_terminal_output_with_width, format_reconciliation_json_output, format_reconciliation_html_output, format_reconciliation_markdown_output};
+use tfdiff::compare::compare_plans;
+use tfdiff::reconcile::reconcile;
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(name = "tfdiff")]
 #[command(about = "Beautiful Terraform plan and apply output formatter")]
 #[command(version)]
@@ -14,14 +20,86 @@ struct Cli {
     /// Input file (reads from stdin if not provided)
     input: Option<PathBuf>,
     
-    /// Output format
-    #[arg(short, long, value_enum, default_value = "terminal")]
-    format: OutputFormat,
+    /// Second plan to compare against the first (with --compare)
+    #[arg(requires = "compare")]
+    compare_input: Option<PathBuf>,
     
-    /// Filter by action type
+    /// Output format (see --list-formats)
+    #[arg(short, long, default_value = "terminal", env = "TFDIFF_FORMAT", value_parser = format_name)]
+    format: String,
+    
+    /// List the output formats and exit
+    #[arg(long)]
+    list_formats: bool,
+    
+    /// Render the report with a Handlebars template file instead of --format
+    #[arg(long, value_name = "PATH", env = "TFDIFF_TEMPLATE", conflicts_with_all = ["compare", "reconcile", "explain", "web", "browser"])]
+    template: Option<PathBuf>,
+    
+    /// Filter by action type (create, update, destroy, read, no-op)
     #[arg(short = 'F', long, value_delimiter = ',')]
     filter: Vec<String>,
     
+    /// Only show resources of these types (wildcards allowed, e.g. aws_iam_*)
+    #[arg(long, value_delimiter = ',', value_name = "TYPE")]
+    resource_type: Vec<String>,
+    
+    /// Only show resources whose address matches (e.g. 'module.network.*')
+    #[arg(long, value_delimiter = ',', value_name = "PATTERN")]
+    address: Vec<String>,
+    
+    /// Only show resources in these modules and their child modules ('root' for the root module)
+    #[arg(long, value_delimiter = ',')]
+    module: Vec<String>,
+    
+    /// Only show resources from these providers
+    #[arg(long, value_delimiter = ',')]
+    provider: Vec<String>,
+    
+    /// Only show resources changing these attributes (e.g. tags.Environment)
+    #[arg(long, value_delimiter = ',', value_name = "PATH")]
+    changed_attribute: Vec<String>,
+    
+    /// Hide resources whose address or type matches (wildcards allowed)
+    #[arg(long, value_delimiter = ',', value_name = "PATTERN")]
+    exclude: Vec<String>,
+    
+    /// Only show resources matching an expression, e.g. 'action == "update" and attr("tags.Environment") changed'
+    #[arg(long = "where", value_name = "EXPRESSION")]
+    where_expression: Option<String>,
+    
+    /// Whether resources must match all filters or any of them
+    #[arg(long = "match", value_enum, default_value = "all")]
+    match_mode: MatchMode,
+    
+    /// TOML file of ignore rules for changes that do not need review (e.g. tags_all churn)
+    #[arg(long, value_name = "PATH")]
+    ignore_file: Option<PathBuf>,
+    
+    /// TOML file of policies the plan must pass; exits with status 4 if one fails
+    #[arg(long, value_name = "PATH")]
+    policy_file: Option<PathBuf>,
+    
+    /// TOML file of monthly prices for cost estimates, used before the built-in prices
+    #[arg(long, value_name = "PATH")]
+    price_file: Option<PathBuf>,
+    
+    /// Labels that allow policy violations (e.g. a pull request label such as iam-approved)
+    #[arg(long, value_delimiter = ',', env = "TFDIFF_LABELS")]
+    label: Vec<String>,
+    
+    /// Hide changes that leave the value equivalent (reordered JSON keys or sets, "1" vs 1) instead of dimming them
+    #[arg(long, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true", action = ArgAction::Set)]
+    hide_cosmetic: bool,
+    
+    /// Group resources in terminal, HTML and Markdown output
+    #[arg(short, long, value_enum)]
+    group_by: Option<GroupBy>,
+    
+    /// Columns to lay the terminal report out in (the terminal's width if not set)
+    #[arg(long, value_name = "COLUMNS", env = "TFDIFF_WIDTH")]
+    width: Option<usize>,
+    
     /// Launch web UI
     #[arg(short, long)]
     web: bool,
@@ -30,6 +108,10 @@ struct Cli {
     #[arg(short, long, default_value = "8080")]
     port: u16,
     
+    /// Directory for plans uploaded to the web UI (kept in memory if not set)
+    #[arg(long)]
+    store: Option<PathBuf>,
+    
     /// Watch file for changes
     #[arg(short = 'W', long)]
     watch: bool,
@@ -49,19 +131,87 @@ struct Cli {
     /// Generate HTML and open in browser
     #[arg(short, long)]
     browser: bool,
+
+    /// Show the raw input lines next to each parsed resource
+    #[arg(long)]
+    explain: bool,
+
+    /// Fail on unrecognized lines, unbalanced braces, summary mismatches or truncated input
+    #[arg(long, num_args = 0..=1, require_equals = true, default_value_t = false, default_missing_value = "true", action = ArgAction::Set)]
+    strict: bool,
+    
+    /// Compare two plans: what changed between the first and the second
+    #[arg(long, requires = "compare_input", conflicts_with_all = ["web", "watch", "explain", "browser"])]
+    compare: bool,
+    
+    /// Check an apply log against the reviewed plan; exits with status 2 if they diverge
+    #[arg(long, value_name = "APPLY_LOG", conflicts_with_all = ["compare", "web", "watch", "explain", "browser"])]
+    reconcile: Option<PathBuf>,
+    
+    /// Colors in terminal output
+    #[arg(long, value_enum, env = "TFDIFF_THEME")]
+    theme: Option<Theme>,
+    
+    /// Project configuration file (default: the nearest .tfdiff.toml)
+    #[arg(long = "config", value_name = "PATH", env = "TFDIFF_CONFIG")]
+    config_path: Option<PathBuf>,
+    
+    /// Ignore .tfdiff.toml
+    #[arg(long, conflicts_with = "config_path")]
+    no_config: bool,
+    
+    /// Settings from the configuration file that have no command line option
+    #[arg(skip)]
+    config: Config,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum MatchMode {
+    All,
+    Any,
+}
+
+impl From<MatchMode> for tfdiff::MatchMode {
+    fn from(match_mode: MatchMode) -> Self {
+        match match_mode {
+            MatchMode::All => Self::All,
+            MatchMode::Any => Self::Any,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Theme {
+    /// Colors unless NO_COLOR is set
+    Auto,
+    /// Always use colors
+    Color,
+    /// No colors
+    Plain,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum GroupBy {
+    Provider,
+    Module,
+    Type,
+    Action,
 }
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum OutputFormat {
-    Terminal,
-    Json,
-    Html,
-    Markdown,
+impl From<GroupBy> for tfdiff::formatter::GroupBy {
+    fn from(group_by: GroupBy) -> Self {
+        match group_by {
+            GroupBy::Provider => Self::Provider,
+            GroupBy::Module => Self::Module,
+            GroupBy::Type => Self::Type,
+            GroupBy::Action => Self::Action,
+        }
+    }
 }
 
-fn open_in_browser(plan: &tfdiff::TerraformPlan) -> Result<()> {
+fn open_in_browser(plan: &tfdiff::TerraformPlan, group_by: tfdiff::formatter::GroupBy) -> Result<()> {
     // Generate HTML content
-    let html_content = format_html_output(plan);
+    let html_content = format_html_output_grouped(plan, group_by);
     
     // Create temporary file
     let temp_dir = std::env::temp_dir();
@@ -105,72 +255,437 @@ fn open_in_browser(plan: &tfdiff::TerraformPlan) -> Result<()> {
     }
 }
 
+/// The resource filters given on the command line.
+fn resource_query(cli: &Cli) -> Result<ResourceQuery> {
+    Ok(ResourceQuery {
+        actions: cli.filter.iter().map(|action| action.parse()).collect::<Result<_>>()?,
+        resource_types: cli.resource_type.clone(),
+        addresses: cli.address.clone(),
+        modules: cli.module.clone(),
+        providers: cli.provider.clone(),
+        changed_attributes: cli.changed_attribute.clone(),
+        exclude: cli.exclude.clone(),
+        expression: cli.where_expression.as_deref().map(Expression::parse).transpose()?,
+        match_mode: cli.match_mode.into(),
+    })
+}
+
+/// The ignore rules from `--ignore-file` or the configuration file, and `--hide-cosmetic`.
+fn ignore_rules(cli: &Cli) -> Result<IgnoreRules> {
+    let mut rules = match (&cli.ignore_file, &cli.config.ignore) {
+        (Some(path), _) => IgnoreRules::load(path)?,
+        (None, Some(rules)) => rules.clone(),
+        (None, None) => IgnoreRules::default(),
+    };
+    rules.cosmetic_changes |= cli.hide_cosmetic;
+    
+    Ok(rules)
+}
+
+/// The policies from `--policy-file` or the configuration file.
+fn policy_set(cli: &Cli) -> Result<PolicySet> {
+    match &cli.policy_file {
+        Some(path) => PolicySet::load(path),
+        None => Ok(PolicySet { policies: cli.config.policies.clone() }),
+    }
+}
+
+/// The built-in resource catalog with the types added in the configuration file.
+fn resource_catalog(cli: &Cli) -> ResourceCatalog {
+    let mut catalog = ResourceCatalog::default();
+    catalog.extend(&cli.config.catalog);
+    catalog
+}
+
+/// The built-in prices with those from `--price-file` or the configuration file ahead of them.
+fn price_table(cli: &Cli) -> Result<PriceTable> {
+    let mut prices = PriceTable::builtin();
+    match (&cli.price_file, &cli.config.cost) {
+        (Some(path), _) => prices.extend(&PriceTable::load(path)?),
+        (None, Some(table)) => prices.extend(table),
+        (None, None) => {}
+    }
+    Ok(prices)
+}
+
+/// Parse the input and redact secrets, leaving every resource and change in.
+fn parse_plan(cli: &Cli, input: &str) -> Result<tfdiff::TerraformPlan> {
+    let options = ParseOptions {
+        mode: if cli.strict { ParseMode::Strict } else { ParseMode::Lenient },
+        prefix_patterns: cli.config.prefix_regexes()?,
+    };
+    let mut plan = parse_terraform_output_with_options(input, &options)?;
+    cli.config.redact.apply(&mut plan)?;
+    Ok(plan)
+}
+
+/// Parse the input, redact secrets, check the policies, drop ignored changes,
+/// apply the resource filters, then annotate and price what is left.
+///
+/// Policies see every resource and change, so neither ignore rules nor a
+/// filtered report can hide a violation.
+fn load_plan(cli: &Cli, input: &str) -> Result<tfdiff::TerraformPlan> {
+    let mut plan = parse_plan(cli, input)?;
+    plan.policy_results = policy_set(cli)?.evaluate(&plan, &cli.label)?;
+    ignore_rules(cli)?.apply(&mut plan);
+    resource_query(cli)?.filter_plan(&mut plan);
+    resource_catalog(cli).annotate(&mut plan);
+    price_table(cli)?.price(&mut plan);
+    
+    Ok(plan)
+}
+
+/// Parse a setting from the configuration file the way clap parses the option.
+fn config_value<T: ValueEnum>(setting: &str, value: &str) -> Result<T> {
+    T::from_str(value, true).map_err(|_| {
+        let expected: Vec<String> = T::value_variants().iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|possible| possible.get_name().to_string())
+            .collect();
+        TfdiffError::ConfigError(format!("unknown {} '{}' (expected {})", setting, value, expected.join(", ")))
+    })
+}
+
+/// Fill in what the command line and environment leave unset from `--config`
+/// or the nearest `.tfdiff.toml`.
+///
+/// Precedence, highest first: command line options, environment variables,
+/// the configuration file, built-in defaults. Each setting is resolved on its
+/// own, so `--module` replaces the file's `modules` but keeps its `exclude`.
+fn apply_config(cli: &mut Cli, matches: &ArgMatches) -> Result<()> {
+    if cli.no_config {
+        return Ok(());
+    }
+    let discovered = std::env::current_dir().ok().and_then(|directory| Config::discover(&directory));
+    let Some(path) = cli.config_path.clone().or(discovered) else { return Ok(()) };
+    let config = Config::load(&path)?;
+    let in_file = |e: TfdiffError| match e {
+        TfdiffError::ConfigError(message) => TfdiffError::ConfigError(format!("{}: {}", path.display(), message)),
+        other => other,
+    };
+    let unset = |id: &str| !matches!(matches.value_source(id), Some(ValueSource::CommandLine | ValueSource::EnvVariable));
+    
+    if let (true, Some(format)) = (unset("format"), &config.format) {
+        cli.format = FormatterRegistry::builtin().lookup(format).map_err(in_file)?.name().to_string();
+    }
+    if let (None, true, Some(template)) = (&cli.template, unset("format"), &config.template) {
+        cli.template = Some(path.parent().unwrap_or(Path::new("")).join(template));
+    }
+    if let (None, Some(group_by)) = (cli.group_by, &config.group_by) {
+        cli.group_by = Some(config_value("group_by", group_by).map_err(in_file)?);
+    }
+    if let (None, Some(theme)) = (cli.theme, &config.theme) {
+        cli.theme = Some(config_value("theme", theme).map_err(in_file)?);
+    }
+    if let (None, Some(width)) = (cli.width, config.width) {
+        cli.width = Some(width);
+    }
+    if let (true, Some(strict)) = (unset("strict"), config.strict) {
+        cli.strict = strict;
+    }
+    if let (true, Some(hide_cosmetic)) = (unset("hide_cosmetic"), config.hide_cosmetic) {
+        cli.hide_cosmetic = hide_cosmetic;
+    }
+    
+    let filter = &config.filter;
+    if unset("filter") {
+        cli.filter = filter.actions.clone();
+    }
+    if unset("resource_type") {
+        cli.resource_type = filter.resource_types.clone();
+    }
+    if unset("address") {
+        cli.address = filter.addresses.clone();
+    }
+    if unset("module") {
+        cli.module = filter.modules.clone();
+    }
+    if unset("provider") {
+        cli.provider = filter.providers.clone();
+    }
+    if unset("changed_attribute") {
+        cli.changed_attribute = filter.changed_attributes.clone();
+    }
+    if unset("exclude") {
+        cli.exclude = filter.exclude.clone();
+    }
+    if unset("where_expression") {
+        cli.where_expression = filter.where_expression.clone();
+    }
+    if let (true, Some(match_mode)) = (unset("match_mode"), &filter.match_mode) {
+        cli.match_mode = config_value("match", match_mode).map_err(in_file)?;
+    }
+    
+    cli.config = config;
+    Ok(())
+}
+
+/// Turn colors on or off for the chosen theme.
+fn apply_theme(theme: Option<Theme>) {
+    match theme {
+        Some(Theme::Color) => colored::control::set_override(true),
+        Some(Theme::Plain) => colored::control::set_override(false),
+        Some(Theme::Auto) | None => {}
+    }
+}
+
+/// Report parse failures; strict-mode diagnostics are listed one per line.
+fn display_load_error(error: &TfdiffError) {
+    match error {
+        TfdiffError::InvalidInput(diagnostics) => {
+            for diagnostic in diagnostics {
+                tfdiff::ui::display_error(&diagnostic.to_string());
+            }
+        }
+        other => tfdiff::ui::display_error(&other.to_string()),
+    }
+}
+
+/// Render the report for the selected output format.
+fn render_output(cli: &Cli, plan: &tfdiff::TerraformPlan, input: &str) -> Result<String> {
+    // Explain mode replaces the regular report
+    if cli.explain {
+        return Ok(format_explain_output(plan, input));
+    }
+    if let Some(path) = &cli.template {
+        return PlanTemplate::load(path)?.render(plan);
+    }
+    
+    FormatterRegistry::builtin().format(&cli.format, plan, &format_options(cli))
+}
+
+/// The output format settings the command line asks for.
+fn format_options(cli: &Cli) -> FormatOptions {
+    let verbosity = if cli.summary || cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+    FormatOptions {
+        width: cli.width,
+        color: colored::control::SHOULD_COLORIZE.should_colorize(),
+        verbosity,
+        group_by: cli.group_by.map(Into::into).unwrap_or_default(),
+        source: cli.input.clone(),
+    }
+}
+
+/// Check a `--format` name against the registered formats.
+fn format_name(name: &str) -> std::result::Result<String, String> {
+    match FormatterRegistry::builtin().lookup(name) {
+        Ok(formatter) => Ok(formatter.name().to_string()),
+        Err(TfdiffError::ConfigError(message)) => Err(message),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Redraw the report every time the input file changes.
+fn watch_terminal(cli: &Cli, path: &Path, mut plan: tfdiff::TerraformPlan, mut input: String) -> Result<()> {
+    let mut watcher = InputWatcher::new(path)?;
+    let redraw = atty::is(atty::Stream::Stdout);
+    
+    loop {
+        if redraw {
+            // Clear the screen and move the cursor home
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("{}", render_output(cli, &plan, &input)?);
+        eprintln!("👀 Watching {} for changes (Ctrl+C to stop)", path.display());
+        io::stdout().flush().map_err(TfdiffError::IoError)?;
+        
+        // Keep showing the last good plan while the file is mid-write
+        loop {
+            input = watcher.next_change()?;
+            match load_plan(cli, &input) {
+                Ok(updated) => {
+                    plan = updated;
+                    break;
+                }
+                Err(e) => display_load_error(&e),
+            }
+        }
+    }
+}
+
+/// Serve the web UI, pushing a fresh plan to connected browsers whenever the input file changes.
+fn run_web_server(cli: &Cli, plan: Option<tfdiff::TerraformPlan>) -> Result<()> {
+    let group_by = cli.group_by.map(Into::into).unwrap_or_default();
+    let store = match &cli.store {
+        Some(directory) => PlanStore::on_disk(directory)?,
+        None => PlanStore::in_memory(),
+    };
+    let state = WebState::new(plan, group_by, store);
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(TfdiffError::IoError)?;
+    
+    if let (true, Some(path)) = (cli.watch, &cli.input) {
+        // Register the watcher before serving so no change is missed
+        let mut watcher = InputWatcher::new(path)?;
+        let handle = runtime.handle().clone();
+        let watched_state = state.clone();
+        let reload_cli = cli.clone();
+        std::thread::spawn(move || loop {
+            let input = match watcher.next_change() {
+                Ok(input) => input,
+                Err(e) => {
+                    tfdiff::ui::display_error(&e.to_string());
+                    return;
+                }
+            };
+            match load_plan(&reload_cli, &input) {
+                Ok(plan) => handle.block_on(watched_state.update_plan(plan)),
+                Err(e) => display_load_error(&e),
+            }
+        });
+    }
+    
+    runtime.block_on(start_web_server_with_state(state, cli.port))
+}
+
+/// The error for an output format that only reports on a single plan.
+fn unsupported_format(format: &str, report: &str) -> TfdiffError {
+    TfdiffError::ConfigError(format!("--format {} does not apply to {}", format, report))
+}
+
+/// Compare two plan files and print the differences in the selected format.
+fn run_comparison(cli: &Cli, previous_path: &Path, current_path: &Path) -> Result<()> {
+    let load = |path: &Path| -> Result<tfdiff::TerraformPlan> {
+        let input = fs::read_to_string(path).map_err(TfdiffError::IoError)?;
+        load_plan(cli, &input)
+    };
+    let comparison = compare_plans(&load(previous_path)?, &load(current_path)?);
+    
+    let output = match cli.format.as_str() {
+        "terminal" => format_comparison_terminal_output_with_width(&comparison, cli.width.unwrap_or_else(terminal_width)),
+        "json" => format_comparison_json_output(&comparison)?,
+        "html" => format_comparison_html_output(&comparison),
+        "markdown" => format_comparison_markdown_output(&comparison),
+        other => return Err(unsupported_format(other, "plan comparisons")),
+    };
+    println!("{}", output);
+    
+    Ok(())
+}
+
+/// Check an apply log against the plan it was meant to apply; returns whether they agree.
+///
+/// Both sides are compared in full: ignore rules and filters could drop a
+/// resource from one side only, e.g. from a plan but not from an apply log
+/// that only has progress lines, and make it look like drift.
+fn run_reconciliation(cli: &Cli, plan_input: &str, apply_path: &Path) -> Result<bool> {
+    let plan = parse_plan(cli, plan_input)?;
+    let apply_input = fs::read_to_string(apply_path).map_err(TfdiffError::IoError)?;
+    let reconciliation = reconcile(&plan, &parse_plan(cli, &apply_input)?)?;
+    
+    let output = match cli.format.as_str() {
+        "terminal" => format_reconciliation_terminal_output_with_width(&reconciliation, cli.width.unwrap_or_else(terminal_width)),
+        "json" => format_reconciliation_json_output(&reconciliation)?,
+        "html" => format_reconciliation_html_output(&reconciliation),
+        "markdown" => format_reconciliation_markdown_output(&reconciliation),
+        other => return Err(unsupported_format(other, "reconciliations")),
+    };
+    println!("{}", output);
+    
+    Ok(reconciliation.is_consistent())
+}
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    
+    if cli.list_formats {
+        for formatter in FormatterRegistry::builtin().formatters() {
+            println!("{:<12} {}", formatter.name(), formatter.description());
+        }
+        return Ok(());
+    }
+    
+    // TFDIFF_NO_COLOR ranks with the other environment variables, above the configuration file
+    if cli.theme.is_none() && std::env::var_os("TFDIFF_NO_COLOR").is_some() {
+        cli.theme = Some(Theme::Plain);
+    }
+    
+    // Report a bad configuration, filter, ignore, policy or price file up front rather than on every (re)load
+    let checked = apply_config(&mut cli, &matches)
+        .and_then(|_| resource_query(&cli))
+        .and_then(|_| ignore_rules(&cli))
+        .and_then(|_| policy_set(&cli))
+        .and_then(|_| price_table(&cli));
+    if let Err(e) = checked {
+        display_load_error(&e);
+        std::process::exit(1);
+    }
+    apply_theme(cli.theme);
+    
+    if cli.compare {
+        if let (Some(previous), Some(current)) = (&cli.input, &cli.compare_input) {
+            return run_comparison(&cli, previous, current);
+        }
+    }
     
     // Read input from file or stdin
-    let input = if let Some(path) = cli.input {
+    let input = if let Some(path) = &cli.input {
         fs::read_to_string(path)
             .map_err(TfdiffError::IoError)?
     } else {
+        if cli.watch {
+            return Err(TfdiffError::WatchError("--watch requires an input file".to_string()));
+        }
         let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer)
-            .map_err(TfdiffError::IoError)?;
+        // Don't wait on an interactive terminal when serving uploads
+        if !(cli.web && atty::is(atty::Stream::Stdin)) {
+            io::stdin().read_to_string(&mut buffer)
+                .map_err(TfdiffError::IoError)?;
+        }
         buffer
     };
     
-    // Handle web UI mode
-    if cli.web {
-        eprintln!("Web UI mode on port {} (not yet implemented)", cli.port);
-        return Ok(());
+    if let Some(apply_path) = &cli.reconcile {
+        match run_reconciliation(&cli, &input, apply_path) {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(2),
+            Err(e) => {
+                display_load_error(&e);
+                std::process::exit(1);
+            }
+        }
     }
     
-    // Parse the Terraform output
-    let plan = parse_terraform_output(&input)?;
-    
-    // Apply filters if specified
-    let mut filtered_plan = plan.clone();
-    if !cli.filter.is_empty() {
-        // Filter resources based on action type
-        filtered_plan.resources.retain(|r| {
-            let action_str = match r.action {
-                tfdiff::ActionType::Create => "create",
-                tfdiff::ActionType::Update => "update",
-                tfdiff::ActionType::Destroy => "destroy",
-                tfdiff::ActionType::Read => "read",
-                tfdiff::ActionType::NoOp => "noop",
-            };
-            cli.filter.iter().any(|f| f == action_str)
-        });
+    // A web server started without any input is only a dashboard for uploaded plans
+    if cli.web && cli.input.is_none() && input.trim().is_empty() {
+        return run_web_server(&cli, None);
     }
     
-    // Format and output based on selected format
-    let output = match cli.format {
-        OutputFormat::Terminal => format_terminal_output(&filtered_plan),
-        OutputFormat::Json => format_json_output(&filtered_plan)?,
-        OutputFormat::Html => format_html_output(&filtered_plan),
-        OutputFormat::Markdown => format_markdown_output(&filtered_plan),
+    // Parse the Terraform output
+    let filtered_plan = match load_plan(&cli, &input) {
+        Err(e @ TfdiffError::InvalidInput(_)) => {
+            display_load_error(&e);
+            std::process::exit(1);
+        }
+        result => result?,
     };
     
-    // Handle browser mode
-    if cli.browser {
-        return open_in_browser(&filtered_plan);
-    }
-    
-    // Print output
-    if cli.summary {
-        // Show only summary for terminal format
-        if matches!(cli.format, OutputFormat::Terminal) {
-            println!("Summary: {} to add, {} to change, {} to destroy", 
-                filtered_plan.summary.add,
-                filtered_plan.summary.change,
-                filtered_plan.summary.destroy
-            );
-        } else {
-            println!("{}", output);
-        }
+    // Handle web UI mode
+    if cli.web {
+        return run_web_server(&cli, Some(filtered_plan));
+    }
+    
+    // Handle browser mode; explain output is text only
+    if cli.browser && !cli.explain {
+        let group_by = cli.group_by.map(Into::into).unwrap_or_default();
+        open_in_browser(&filtered_plan, group_by)?;
+    } else if let (true, Some(path)) = (cli.watch, &cli.input) {
+        return watch_terminal(&cli, path, filtered_plan, input);
     } else {
-        println!("{}", output);
+        // Print output
+        println!("{}", render_output(&cli, &filtered_plan, &input)?);
+    }
+    
+    // A failed policy fails the CI job; warnings are only reported
+    if policy_outcome(&filtered_plan.policy_results) == PolicyOutcome::Fail {
+        std::process::exit(4);
     }
     
     Ok(())
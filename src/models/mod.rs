@@ -4,11 +4,27 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TerraformPlan {
     pub mode: PlanMode,
+    /// Counts as reported by Terraform's own "Plan:" / "Apply complete!" line
     pub summary: Summary,
+    /// Counts derived from the parsed resources, for reconciling against `summary`
+    #[serde(default)]
+    pub parsed_summary: Summary,
     pub resources: Vec<Resource>,
     pub data_sources: Vec<DataSource>,
     pub warnings: Vec<Warning>,
     pub metadata: Metadata,
+    /// Changes left out by ignore rules
+    #[serde(default)]
+    pub suppressed: Vec<SuppressedChange>,
+    /// Outcomes of the policies checked against the plan
+    #[serde(default)]
+    pub policy_results: Vec<PolicyResult>,
+    /// Notes on the impact of changing catalogued resource types
+    #[serde(default)]
+    pub annotations: Vec<ResourceAnnotation>,
+    /// Estimated monthly cost of the changes, if any of them has a price
+    #[serde(default)]
+    pub cost: Option<PlanCost>,
 }
 
 impl Default for TerraformPlan {
@@ -16,10 +32,15 @@ impl Default for TerraformPlan {
         Self {
             mode: PlanMode::Plan,
             summary: Summary::default(),
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         }
     }
 }
@@ -30,7 +51,7 @@ pub enum PlanMode {
     Apply,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Summary {
     pub add: usize,
     pub change: usize,
@@ -38,6 +59,54 @@ pub struct Summary {
     pub read: usize,
 }
 
+impl Summary {
+    /// Count resources by action, the way Terraform's summary line does: a
+    /// replacement counts as one to add and one to destroy.
+    pub fn from_resources(resources: &[Resource]) -> Self {
+        let mut summary = Summary::default();
+        for resource in resources {
+            match resource.action {
+                ActionType::Update if resource.replace => {
+                    summary.add += 1;
+                    summary.destroy += 1;
+                }
+                ActionType::Create => summary.add += 1,
+                ActionType::Update => summary.change += 1,
+                ActionType::Destroy => summary.destroy += 1,
+                ActionType::Read => summary.read += 1,
+                ActionType::NoOp => {}
+            }
+        }
+        summary
+    }
+    
+    /// Whether the add/change/destroy counts agree; `read` is not part of Terraform's summary line.
+    pub fn matches_counts(&self, other: &Summary) -> bool {
+        (self.add, self.change, self.destroy) == (other.add, other.change, other.destroy)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            RiskLevel::None => "none",
+            RiskLevel::Low => "low",
+            RiskLevel::Medium => "medium",
+            RiskLevel::High => "high",
+        };
+        f.write_str(label)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Resource {
     pub id: String,
@@ -48,6 +117,91 @@ pub struct Resource {
     pub changes: Vec<Change>,
     pub attributes: HashMap<String, serde_json::Value>,
     pub applied: bool,
+    /// Terraform destroys and re-creates the resource rather than updating it in place
+    #[serde(default)]
+    pub replace: bool,
+    #[serde(default)]
+    pub span: Option<SourceSpan>,
+}
+
+impl Resource {
+    /// The module path of this resource (e.g. `module.network.module.subnets`), `None` for the root module.
+    pub fn module(&self) -> Option<String> {
+        ResourceAddress::parse(&self.id).and_then(|address| address.module)
+    }
+    
+    /// What happens to the resource, as in Terraform's "will be ..." lines.
+    pub fn action_description(&self) -> &'static str {
+        match self.action {
+            ActionType::Update if self.replace => "replaced",
+            ActionType::Create => "created",
+            ActionType::Update => "updated in-place",
+            ActionType::Destroy => "destroyed",
+            ActionType::Read => "read",
+            ActionType::NoOp => "left unchanged",
+        }
+    }
+}
+
+/// The parts of a resource address such as `module.vpc.aws_subnet.public[0]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceAddress {
+    pub module: Option<String>,
+    pub data: bool,
+    pub type_name: String,
+    pub name: String,
+}
+
+impl ResourceAddress {
+    pub fn parse(address: &str) -> Option<Self> {
+        let segments = split_address(address);
+        let mut rest = segments.as_slice();
+        
+        let mut modules = Vec::new();
+        while rest.len() > 2 && rest[0] == "module" {
+            modules.push(format!("module.{}", rest[1]));
+            rest = &rest[2..];
+        }
+        
+        let data = rest.len() > 2 && rest[rest.len() - 3] == "data";
+        
+        // Anything left in front of `type.name` (e.g. a stack prefix) is not part of the address
+        let [.., type_name, name] = rest else { return None };
+        let valid_type = !type_name.is_empty()
+            && type_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_type || name.is_empty() {
+            return None;
+        }
+        
+        Some(Self {
+            module: if modules.is_empty() { None } else { Some(modules.join(".")) },
+            data,
+            type_name: type_name.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Split an address on dots that are not inside an index like `["a.b"]`.
+fn split_address(address: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    
+    for (i, ch) in address.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '.' if depth == 0 => {
+                segments.push(&address[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&address[start..]);
+    
+    segments
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +222,24 @@ pub enum ActionType {
     NoOp,
 }
 
+impl std::str::FromStr for ActionType {
+    type Err = crate::TfdiffError;
+    
+    /// Parse an action name as used by `--filter`, e.g. `create` or `no-op`.
+    fn from_str(name: &str) -> crate::Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "create" => Ok(ActionType::Create),
+            "update" => Ok(ActionType::Update),
+            "destroy" => Ok(ActionType::Destroy),
+            "read" => Ok(ActionType::Read),
+            "noop" | "no-op" => Ok(ActionType::NoOp),
+            _ => Err(crate::TfdiffError::InvalidQuery(format!(
+                "unknown action '{}' (expected create, update, destroy, read or no-op)", name
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Change {
     pub path: Vec<String>,
@@ -75,12 +247,194 @@ pub struct Change {
     pub after: Option<serde_json::Value>,
     pub sensitive: bool,
     pub computed: bool,
+    /// Terraform must replace the resource to make this change
+    #[serde(default)]
+    pub forces_replacement: bool,
+    #[serde(default)]
+    pub span: Option<SourceSpan>,
+}
+
+/// A change removed from a plan by an ignore rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuppressedChange {
+    pub address: String,
+    /// Dotted attribute path, e.g. `tags_all.Environment`
+    pub path: String,
+    pub reason: SuppressionReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SuppressionReason {
+    /// An address/attribute rule, described by its `reason` or its patterns
+    Rule { description: String },
+    /// A list whose elements only changed order
+    OrderOnlyList,
+    /// A string that only changed in whitespace
+    WhitespaceOnly,
+    /// An update attribute whose new value is only `(known after apply)`
+    UnknownOnly,
+    /// A change that does not alter the value Terraform configures
+    Cosmetic { reason: crate::CosmeticReason },
+}
+
+impl std::fmt::Display for SuppressionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuppressionReason::Rule { description } => write!(f, "ignore rule: {}", description),
+            SuppressionReason::OrderOnlyList => f.write_str("list order only"),
+            SuppressionReason::WhitespaceOnly => f.write_str("whitespace only"),
+            SuppressionReason::UnknownOnly => f.write_str("known after apply only"),
+            SuppressionReason::Cosmetic { reason } => write!(f, "cosmetic: {}", reason),
+        }
+    }
+}
+
+/// How one policy fared against a plan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyResult {
+    pub policy: String,
+    pub description: Option<String>,
+    pub outcome: PolicyOutcome,
+    /// What broke the policy, e.g. `aws_db_instance.main will be destroyed`
+    pub violations: Vec<String>,
+    /// The label that allowed the violations, if one did
+    #[serde(default)]
+    pub waived_by: Option<String>,
+    /// Addresses of the resources that broke the policy; empty when a change limit was exceeded
+    #[serde(default)]
+    pub addresses: Vec<String>,
+}
+
+/// Ordered from best to worst, so the worst outcome of a set is its maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyOutcome {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl std::fmt::Display for PolicyOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            PolicyOutcome::Pass => "pass",
+            PolicyOutcome::Warn => "warn",
+            PolicyOutcome::Fail => "fail",
+        };
+        f.write_str(label)
+    }
+}
+
+/// What a resource type is, for judging the impact of changing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceClass {
+    /// Holds data that is lost when the resource is destroyed
+    Stateful,
+    /// Controls who or what may access other resources
+    SecuritySensitive,
+    /// Carries traffic other resources depend on
+    NetworkCritical,
+    /// Holds no data and can be replaced without impact
+    CheapToRecreate,
+}
+
+impl std::fmt::Display for ResourceClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ResourceClass::Stateful => "stateful",
+            ResourceClass::SecuritySensitive => "security-sensitive",
+            ResourceClass::NetworkCritical => "network-critical",
+            ResourceClass::CheapToRecreate => "cheap-to-recreate",
+        };
+        f.write_str(label)
+    }
+}
+
+/// A note on what changing a resource may cause, e.g.
+/// `stateful resource will be destroyed — data loss possible`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceAnnotation {
+    pub address: String,
+    pub class: ResourceClass,
+    pub message: String,
+}
+
+/// What a change does to the monthly price of a resource.
+///
+/// A price is `None` when it cannot be worked out from the plan, e.g. for an
+/// instance type missing from the price table; `note` says why.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceCost {
+    pub address: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+    pub delta: Option<f64>,
+    pub note: Option<String>,
+}
+
+/// The estimated monthly cost of a plan's changes. The totals add up the
+/// prices that could be worked out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanCost {
+    pub currency: String,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+    /// One entry per priced resource, in plan order
+    pub resources: Vec<ResourceCost>,
+}
+
+impl PlanCost {
+    /// How many priced resources have a change of unknown cost.
+    pub fn unknown(&self) -> usize {
+        self.resources.iter().filter(|cost| cost.delta.is_none()).count()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Warning {
     pub message: String,
     pub level: WarningLevel,
+    #[serde(default)]
+    pub span: Option<SourceSpan>,
+    /// Set when the warning was raised by the parser about the input itself
+    #[serde(default)]
+    pub kind: Option<DiagnosticKind>,
+    /// The resource Terraform attributed the message to, if any
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+/// Where a parsed element came from in the raw input.
+///
+/// Line numbers are 1-based and refer to the input as given to the parser,
+/// before ANSI codes, spinners and stack prefixes were cleaned away.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Stack or log prefix stripped from the first line (e.g. a CDKTF stack name)
+    pub prefix: Option<String>,
+}
+
+impl SourceSpan {
+    pub fn new(start_line: usize, end_line: usize) -> Self {
+        Self { start_line, end_line, prefix: None }
+    }
+
+    pub fn line(line: usize) -> Self {
+        Self::new(line, line)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    UnrecognizedLine,
+    UnbalancedBraces,
+    SummaryMismatch,
+    TruncatedInput,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,146 @@
+use crate::{ActionType, Resource, ResourceAddress, SourceSpan, Warning, WarningLevel};
+use crate::parser::provider::provider_from_type;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// What Terraform reported doing to one resource while applying.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplyProgress {
+    pub address: String,
+    pub action: ActionType,
+    /// Both a destroy and a create were reported for the address
+    pub replaced: bool,
+    /// Every operation started on the resource reported completion
+    pub completed: bool,
+    /// 1-based line of the first progress message for the resource
+    pub line: usize,
+}
+
+lazy_static! {
+    // "<address>: Creating...", "<address>: Destruction complete after 1s", ...
+    static ref PROGRESS_LINE_REGEX: Regex = Regex::new(r"^(\S+): (Creating|Creation complete|Modifying|Modifications complete|Destroying|Destruction complete)").unwrap();
+    // "with <address>," inside an error block
+    static ref ERROR_ADDRESS_REGEX: Regex = Regex::new(r"^with (\S+),$").unwrap();
+}
+
+/// Whether a cleaned line is apply progress for a managed resource.
+pub fn is_apply_progress_line(line: &str) -> bool {
+    PROGRESS_LINE_REGEX.is_match(line.trim())
+}
+
+/// Collect the apply progress messages in cleaned lines, one entry per resource address.
+///
+/// A replacement shows up as a destroy and a create of the same address and is
+/// reported as an update, as the plan parsers do.
+pub fn parse_apply_progress(lines: &[&str]) -> Vec<ApplyProgress> {
+    // Per address: the operations started and the operations completed
+    let mut progress: Vec<(ApplyProgress, Vec<ActionType>, Vec<ActionType>)> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some(captures) = PROGRESS_LINE_REGEX.captures(line.trim()) else { continue };
+        let address = &captures[1];
+        let (action, finished) = match &captures[2] {
+            "Creating" => (ActionType::Create, false),
+            "Creation complete" => (ActionType::Create, true),
+            "Modifying" => (ActionType::Update, false),
+            "Modifications complete" => (ActionType::Update, true),
+            "Destroying" => (ActionType::Destroy, false),
+            _ => (ActionType::Destroy, true),
+        };
+
+        let position = match progress.iter().position(|(entry, _, _)| entry.address == address) {
+            Some(position) => position,
+            None => {
+                let entry = ApplyProgress { address: address.to_string(), action: action.clone(), replaced: false, completed: false, line: index + 1 };
+                progress.push((entry, Vec::new(), Vec::new()));
+                progress.len() - 1
+            }
+        };
+
+        let (_, started, completed) = &mut progress[position];
+        let operations = if finished { completed } else { started };
+        if !operations.contains(&action) {
+            operations.push(action);
+        }
+    }
+
+    progress.into_iter()
+        .map(|(mut entry, started, completed)| {
+            entry.replaced = started.contains(&ActionType::Create) && started.contains(&ActionType::Destroy);
+            if entry.replaced {
+                entry.action = ActionType::Update;
+            }
+            entry.completed = started.iter().all(|action| completed.contains(action));
+            entry
+        })
+        .collect()
+}
+
+/// Mark resources as applied from the progress messages, adding any resource
+/// the log only mentions in progress (e.g. `terraform apply plan.tfplan`).
+pub fn merge_apply_progress(resources: &mut Vec<Resource>, progress: &[ApplyProgress]) {
+    for entry in progress {
+        if let Some(resource) = resources.iter_mut().find(|resource| resource.id == entry.address) {
+            resource.applied = entry.completed;
+            continue;
+        }
+
+        let Some(address) = ResourceAddress::parse(&entry.address) else { continue };
+        if address.data {
+            continue;
+        }
+        resources.push(Resource {
+            id: entry.address.clone(),
+            provider: provider_from_type(&address.type_name),
+            name: address.name,
+            type_name: address.type_name,
+            action: entry.action.clone(),
+            changes: Vec::new(),
+            attributes: HashMap::new(),
+            applied: entry.completed,
+            replace: entry.replaced,
+            span: Some(SourceSpan::line(entry.line)),
+        });
+    }
+}
+
+/// Collect Terraform's `Error:` blocks as error-level warnings, keeping the
+/// resource address from the block's `with <address>,` line.
+pub fn parse_error_blocks(lines: &[&str]) -> Vec<Warning> {
+    let mut errors: Vec<Warning> = Vec::new();
+    let mut in_block = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let boxed = line.trim_start().starts_with('│');
+        let unboxed = line.trim().trim_start_matches(['│', '╷', '╵']).trim();
+
+        if let Some(message) = unboxed.strip_prefix("Error: ") {
+            errors.push(Warning {
+                message: message.trim().to_string(),
+                level: WarningLevel::Error,
+                span: Some(SourceSpan::line(index + 1)),
+                kind: None,
+                address: None,
+            });
+            in_block = true;
+            continue;
+        }
+
+        // Without the box, a block ends at the first line that is not indented
+        if !in_block || line.trim_start().starts_with('╵') || (!boxed && !unboxed.is_empty() && !line.starts_with(' ')) {
+            in_block = false;
+            continue;
+        }
+
+        let Some(error) = errors.last_mut() else { continue };
+        if let Some(span) = error.span.as_mut() {
+            span.end_line = index + 1;
+        }
+        if error.address.is_none() {
+            error.address = ERROR_ADDRESS_REGEX.captures(unboxed).map(|captures| captures[1].to_string());
+        }
+    }
+
+    errors
+}
@@ -1,25 +1,91 @@
 use crate::Result;
+use lazy_static::lazy_static;
 use regex::Regex;
 
+lazy_static! {
+    // Common stack prefixes like "base14-aws-use1-eks-scout"
+    static ref PREFIX_REGEX: Regex = Regex::new(r"^base14-[a-zA-Z0-9-]+\s+").unwrap();
+}
+
+/// A cleaned line of input together with the raw lines it was built from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanedLine {
+    pub text: String,
+    /// 1-based line number of the first raw line
+    pub start_line: usize,
+    /// 1-based line number of the last raw line (differs when broken lines were merged)
+    pub end_line: usize,
+    /// Stack/log prefix removed from the line, if any
+    pub prefix: Option<String>,
+}
+
 pub fn clean_input(input: &str) -> Result<String> {
-    let mut cleaned = input.to_string();
-    
-    // Remove ANSI escape codes
-    cleaned = clean_ansi_codes(&cleaned);
+    let lines: Vec<String> = clean_input_lines(input)
+        .into_iter()
+        .map(|line| line.text)
+        .collect();
     
-    // Remove spinner characters
-    cleaned = clean_spinner_chars(&cleaned);
+    Ok(lines.join("\n"))
+}
+
+/// Clean the input line by line, keeping track of which raw lines each cleaned line came from.
+pub fn clean_input_lines(input: &str) -> Vec<CleanedLine> {
+    clean_input_lines_with_prefixes(input, &[])
+}
+
+/// Like `clean_input_lines`, also stripping prefixes matching `prefix_patterns`
+/// from the start of each line's content.
+pub fn clean_input_lines_with_prefixes(input: &str, prefix_patterns: &[Regex]) -> Vec<CleanedLine> {
+    let mut lines = Vec::new();
     
-    // Fix broken lines (lines that are split in the middle of resource declarations)
-    cleaned = fix_broken_lines(&cleaned);
+    for (index, raw) in input.lines().enumerate() {
+        // Normalize line endings: a bare carriage return also starts a new line
+        for segment in raw.split('\r') {
+            // Remove ANSI escape codes and spinner characters
+            let mut text = clean_spinner_chars(&clean_ansi_codes(segment));
+            
+            // Remove stack prefixes, keeping the indentation of the remaining content
+            let indent = text.len() - text.trim_start().len();
+            let mut prefix = None;
+            let found = std::iter::once(&*PREFIX_REGEX)
+                .chain(prefix_patterns)
+                .find_map(|pattern| pattern.find(&text[indent..]).filter(|found| found.start() == 0 && !found.is_empty()));
+            if let Some(found) = found {
+                prefix = Some(found.as_str().trim_end().to_string());
+                text = format!("{}{}", &text[..indent], &text[indent + found.end()..]);
+            }
+            
+            lines.push(CleanedLine {
+                text,
+                start_line: index + 1,
+                end_line: index + 1,
+                prefix,
+            });
+        }
+    }
     
-    // Clean CDK/CDKTF prefixes and other prefixes
-    cleaned = clean_prefixes(&cleaned);
+    merge_broken_lines(lines)
+}
+
+fn merge_broken_lines(lines: Vec<CleanedLine>) -> Vec<CleanedLine> {
+    let mut merged: Vec<CleanedLine> = Vec::with_capacity(lines.len());
+    let mut iter = lines.into_iter().peekable();
     
-    // Normalize line endings
-    cleaned = cleaned.replace("\r\n", "\n").replace('\r', "\n");
+    while let Some(mut line) = iter.next() {
+        if is_broken_resource_line(&line.text) {
+            if let Some(next) = iter.next_if(|next| next.text.contains(") will be ")) {
+                line.text = format!("{}{}", line.text.trim_end(), next.text.trim());
+                line.end_line = next.end_line;
+            }
+        }
+        merged.push(line);
+    }
     
-    Ok(cleaned)
+    merged
+}
+
+fn is_broken_resource_line(line: &str) -> bool {
+    line.contains("# aws_") && !line.contains(" will be ")
 }
 
 pub fn clean_ansi_codes(input: &str) -> String {
@@ -50,7 +116,7 @@ pub fn fix_broken_lines(input: &str) -> String {
         
         // Check if this line looks like it might be part of a broken resource declaration
         // If a line contains a partial resource declaration pattern (ending mid-word or mid-path)
-        if line.contains("# aws_") && !line.contains(" will be ") && i + 1 < lines.len() {
+        if is_broken_resource_line(line) && i + 1 < lines.len() {
             // This might be a broken line, check the next line
             let next_line = lines[i + 1];
             if next_line.contains(") will be ") {
@@ -78,8 +144,7 @@ pub fn clean_prefixes(input: &str) -> String {
         let mut cleaned = line.to_string();
         
         // Remove common prefixes like "base14-aws-use1-eks-scout" or similar
-        let prefix_regex = Regex::new(r"^base14-[a-zA-Z0-9-]+\s+").unwrap();
-        cleaned = prefix_regex.replace(&cleaned, "").to_string();
+        cleaned = PREFIX_REGEX.replace(&cleaned, "").to_string();
         
         // Also handle lines that are indented with spaces but have content
         // Keep the indentation but clean the prefix
@@ -87,7 +152,7 @@ pub fn clean_prefixes(input: &str) -> String {
             let indent = cleaned.len() - cleaned.trim_start().len();
             let spaces = " ".repeat(indent);
             let content = cleaned.trim_start();
-            let content_cleaned = prefix_regex.replace(content, "").to_string();
+            let content_cleaned = PREFIX_REGEX.replace(content, "").to_string();
             cleaned = format!("{}{}", spaces, content_cleaned);
         }
         
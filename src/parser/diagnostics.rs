@@ -0,0 +1,260 @@
+use crate::{DiagnosticKind, PlanMode, Resource, SourceSpan, Summary, Warning, WarningLevel};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+
+/// A problem found in the input while parsing.
+///
+/// Spans are 1-based line numbers; the parser produces them against the cleaned
+/// lines and remaps them onto the raw input before handing them out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDiagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub span: SourceSpan,
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.span.start_line == self.span.end_line {
+            write!(f, "line {}: {}", self.span.start_line, self.message)
+        } else {
+            write!(f, "lines {}-{}: {}", self.span.start_line, self.span.end_line, self.message)
+        }
+    }
+}
+
+impl From<ParseDiagnostic> for Warning {
+    fn from(diagnostic: ParseDiagnostic) -> Self {
+        let level = match diagnostic.kind {
+            DiagnosticKind::UnrecognizedLine => WarningLevel::Info,
+            _ => WarningLevel::Warning,
+        };
+        Warning {
+            message: diagnostic.message,
+            level,
+            span: Some(diagnostic.span),
+            kind: Some(diagnostic.kind),
+            address: None,
+        }
+    }
+}
+
+lazy_static! {
+    static ref RESOURCE_HEADER_REGEX: Regex = Regex::new(r"^#\s+\S+.*\s(will be|must be|has moved to|is tainted)").unwrap();
+    static ref HEADER_NOTE_REGEX: Regex = Regex::new(r"^#\s+\(.*\)$").unwrap();
+    static ref BLOCK_START_REGEX: Regex = Regex::new(r#"^(?:[+~-]|-/\+|\+/-|<=)?\s*(?:resource|data)\s+"[^"]+"\s+"[^"]+"\s*\{"#).unwrap();
+    static ref PROGRESS_REGEX: Regex = Regex::new(r"^\S+: (Refreshing state|Reading|Read complete|Creating|Creation complete|Modifying|Modifications complete|Destroying|Destruction complete|Still \w+)").unwrap();
+    static ref LEGEND_REGEX: Regex = Regex::new(r"^(\+|~|-|-/\+|\+/-|<=)\s+(create|update in-place|destroy|read \(data resources\)|destroy and then create replacement|create replacement and then destroy)").unwrap();
+    static ref HEREDOC_REGEX: Regex = Regex::new(r"<<-?([A-Z_]+)\s*$").unwrap();
+}
+
+// Lines Terraform prints around the plan itself
+const KNOWN_PREFIXES: &[&str] = &[
+    "Terraform used the selected providers",
+    "Terraform will perform the following actions",
+    "Terraform will perform the actions described above",
+    "Terraform has ",
+    "Terraform detected the following changes",
+    "Resource actions are indicated",
+    "Initializing ",
+    "Finding ",
+    "Installing ",
+    "Installed ",
+    "Reusing previous version",
+    "Using previously-installed",
+    "You may now begin working with Terraform",
+    "Plan:",
+    "Apply complete!",
+    "Destroy complete!",
+    "No changes.",
+    "Your infrastructure matches the configuration",
+    "Note:",
+    "Saved the plan to:",
+    "To perform exactly these actions",
+    "terraform apply",
+    "Do you want to perform these actions",
+    "Only 'yes' will be accepted",
+    "Enter a value:",
+    "Apply cancelled.",
+];
+
+/// Check cleaned plan lines for anything the parser could not account for.
+pub fn collect_diagnostics(
+    lines: &[&str],
+    mode: &PlanMode,
+    summary: &Summary,
+    resources: &[Resource],
+) -> Vec<ParseDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut has_summary_line = false;
+    let mut has_actions = false;
+
+    // Brace depth inside the current resource block and where that block started
+    let mut depth: i64 = 0;
+    let mut block_start = 0;
+    let mut heredoc_end: Option<String> = None;
+    // Free-form sections (diagnostic messages, outputs) run until the next known line
+    let mut in_free_text = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some(marker) = &heredoc_end {
+            // The closing marker may be followed by list/call punctuation, e.g. `EOT,`
+            if trimmed.trim_end_matches([',', ')', ']']) == marker {
+                heredoc_end = None;
+            }
+            continue;
+        }
+
+        if depth > 0 {
+            if RESOURCE_HEADER_REGEX.is_match(trimmed) {
+                // A new resource started before the previous block was closed
+                diagnostics.push(unbalanced(block_start, line_number - 1, depth));
+                depth = 0;
+            } else {
+                depth += brace_delta(trimmed);
+                if let Some(captures) = HEREDOC_REGEX.captures(trimmed) {
+                    heredoc_end = Some(captures[1].to_string());
+                }
+                if depth < 0 {
+                    diagnostics.push(ParseDiagnostic {
+                        kind: DiagnosticKind::UnbalancedBraces,
+                        message: "closing brace without a matching opening brace".to_string(),
+                        span: SourceSpan::line(line_number),
+                    });
+                    depth = 0;
+                }
+                continue;
+            }
+        }
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed.contains("Plan:") || trimmed.starts_with("Apply complete!")
+            || trimmed.starts_with("Destroy complete!") || trimmed.starts_with("No changes.") {
+            has_summary_line = true;
+        }
+
+        if RESOURCE_HEADER_REGEX.is_match(trimmed) {
+            has_actions = true;
+            in_free_text = false;
+            continue;
+        }
+
+        if BLOCK_START_REGEX.is_match(trimmed) {
+            depth = brace_delta(trimmed);
+            block_start = line_number;
+            in_free_text = false;
+            continue;
+        }
+
+        if trimmed.starts_with("Warning:") || trimmed.starts_with("Error:")
+            || trimmed.starts_with("Changes to Outputs:") || trimmed.starts_with("Outputs:") {
+            in_free_text = true;
+            continue;
+        }
+
+        if is_known_line(trimmed) {
+            in_free_text = false;
+            continue;
+        }
+
+        if in_free_text {
+            continue;
+        }
+
+        diagnostics.push(ParseDiagnostic {
+            kind: DiagnosticKind::UnrecognizedLine,
+            message: format!("unrecognized line: {}", trimmed),
+            span: SourceSpan::line(line_number),
+        });
+    }
+
+    if depth > 0 {
+        diagnostics.push(unbalanced(block_start, lines.len(), depth));
+    }
+
+    if depth > 0 || heredoc_end.is_some() || ((has_actions || !resources.is_empty()) && !has_summary_line) {
+        let last_line = lines.len().max(1);
+        diagnostics.push(ParseDiagnostic {
+            kind: DiagnosticKind::TruncatedInput,
+            message: "input ends before the plan summary; the log may be truncated".to_string(),
+            span: SourceSpan::line(last_line),
+        });
+    }
+
+    if has_summary_line && *mode == PlanMode::Plan {
+        if let Some(diagnostic) = check_summary_counts(lines, summary, resources) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    diagnostics
+}
+
+fn check_summary_counts(lines: &[&str], summary: &Summary, resources: &[Resource]) -> Option<ParseDiagnostic> {
+    let parsed = Summary::from_resources(resources);
+
+    if summary.matches_counts(&parsed) {
+        return None;
+    }
+
+    let summary_line = lines.iter()
+        .position(|line| line.contains("Plan:"))
+        .map(|index| index + 1)
+        .unwrap_or(lines.len());
+
+    Some(ParseDiagnostic {
+        kind: DiagnosticKind::SummaryMismatch,
+        message: format!(
+            "summary reports {} to add, {} to change, {} to destroy but {} created, {} updated, {} destroyed resources were parsed",
+            summary.add, summary.change, summary.destroy, parsed.add, parsed.change, parsed.destroy
+        ),
+        span: SourceSpan::line(summary_line),
+    })
+}
+
+fn unbalanced(start: usize, end: usize, depth: i64) -> ParseDiagnostic {
+    ParseDiagnostic {
+        kind: DiagnosticKind::UnbalancedBraces,
+        message: format!("resource block is missing {} closing brace{}", depth, if depth == 1 { "" } else { "s" }),
+        span: SourceSpan::new(start, end.max(start)),
+    }
+}
+
+fn is_known_line(trimmed: &str) -> bool {
+    let unboxed = trimmed.trim_start_matches(['│', '╷', '╵']).trim();
+
+    unboxed.is_empty()
+        || unboxed.chars().all(|ch| ch == '─')
+        || HEADER_NOTE_REGEX.is_match(unboxed)
+        || PROGRESS_REGEX.is_match(unboxed)
+        || LEGEND_REGEX.is_match(unboxed)
+        || KNOWN_PREFIXES.iter().any(|prefix| unboxed.starts_with(prefix))
+        || (unboxed.starts_with("- ") && KNOWN_PREFIXES.iter().any(|prefix| unboxed[2..].starts_with(prefix)))
+}
+
+/// Net change in brace depth for a line, ignoring braces inside quoted strings.
+fn brace_delta(line: &str) -> i64 {
+    let mut delta = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for ch in line.chars() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => delta += 1,
+            '}' if !in_string => delta -= 1,
+            _ => {}
+        }
+    }
+
+    delta
+}
@@ -1,34 +1,49 @@
-use crate::{Resource, ActionType, Change};
+use crate::{Resource, ResourceAddress, ActionType, Change, SourceSpan};
+use crate::parser::provider::{provider_from_type, provider_from_reference};
 use regex::Regex;
 use std::collections::HashMap;
 use serde_json::{Value, json};
 use lazy_static::lazy_static;
 
+/// Parse the resource blocks out of cleaned plan lines.
+///
+/// Spans on the returned resources and changes are 1-based indices into `lines`.
 pub fn parse_resource_changes(lines: &[&str]) -> Vec<Resource> {
     let mut resources = Vec::new();
     let mut current_resource: Option<Resource> = None;
     let mut in_resource_block = false;
     let mut brace_level = 0;
+    // Names of the maps, lists and nested blocks open around the current update line
+    let mut nested: Vec<String> = Vec::new();
+    // Lists open around the current update line, with the elements read so far
+    let mut lists: Vec<ListElements> = Vec::new();
     
-    for line in lines {
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
         let trimmed = line.trim();
         
         // Detect resource action lines
-        if let Some(resource) = parse_resource_action_line(line) {
+        if let Some(mut resource) = parse_resource_action_line(line) {
             if let Some(existing) = current_resource.take() {
                 resources.push(existing);
             }
+            resource.span = Some(SourceSpan::line(line_number));
             current_resource = Some(resource);
             in_resource_block = false;
             brace_level = 0;
         }
-        // Detect start of resource block (+ resource, ~ resource, - resource)
+        // Detect start of resource block (+ resource, ~ resource, - resource, -/+ resource)
         else if current_resource.is_some() && (
                  trimmed.starts_with("+ resource") || 
                  trimmed.starts_with("~ resource") || 
-                 trimmed.starts_with("- resource")) {
+                 trimmed.starts_with("- resource") ||
+                 trimmed.starts_with("-/+ resource") ||
+                 trimmed.starts_with("+/- resource")) {
             in_resource_block = true;
             brace_level = 0;
+            nested.clear();
+            lists.clear();
+            extend_span(current_resource.as_mut(), line_number);
             // Count braces on this line too
             for ch in trimmed.chars() {
                 match ch {
@@ -40,6 +55,7 @@ pub fn parse_resource_changes(lines: &[&str]) -> Vec<Resource> {
         }
         // Track brace levels to know when we're inside the resource
         else if in_resource_block {
+            extend_span(current_resource.as_mut(), line_number);
             for ch in trimmed.chars() {
                 match ch {
                     '{' => brace_level += 1,
@@ -56,9 +72,34 @@ pub fn parse_resource_changes(lines: &[&str]) -> Vec<Resource> {
             // Parse attribute changes within the resource block
             if brace_level > 0 {
                 if let Some(ref mut resource) = current_resource {
+                    // An explicit provider["..."].alias reference beats the type-prefix guess
+                    if let Some(provider) = provider_from_reference(trimmed) {
+                        resource.provider = provider;
+                    }
                     if resource.action == ActionType::Update {
-                        // For updates, parse changes with before/after values
-                        parse_update_change(trimmed, &mut resource.changes);
+                        // For updates, parse changes with before/after values, keeping track
+                        // of the enclosing attributes so `tags.Name` is not reported as `Name`
+                        let in_list = lists.last_mut().filter(|list| list.depth == nested.len());
+                        if let Some(name) = nested_block_name(trimmed) {
+                            // Maps and nested lists inside a list are not collected as elements
+                            if let Some(list) = in_list {
+                                list.complex = true;
+                            }
+                            nested.push(name);
+                            if trimmed.ends_with('[') && !nested.last().is_some_and(String::is_empty) {
+                                lists.push(ListElements::open(trimmed, &nested, line_number));
+                            }
+                        } else if trimmed.starts_with(['}', ']', ')']) {
+                            if in_list.is_some() {
+                                let list = lists.pop().expect("list is open");
+                                resource.changes.extend(list.close(line_number));
+                            }
+                            nested.pop();
+                        } else if let Some(list) = in_list {
+                            list.push(trimmed);
+                        } else {
+                            parse_update_change(trimmed, line_number, &nested, &mut resource.changes);
+                        }
                     } else {
                         // For create/destroy, just parse attributes
                         parse_attribute_change(trimmed, &mut resource.attributes);
@@ -75,10 +116,15 @@ pub fn parse_resource_changes(lines: &[&str]) -> Vec<Resource> {
     resources
 }
 
+fn extend_span(resource: Option<&mut Resource>, line_number: usize) {
+    if let Some(span) = resource.and_then(|r| r.span.as_mut()) {
+        span.end_line = line_number;
+    }
+}
+
 lazy_static! {
-    static ref CREATE_REGEX: Regex = Regex::new(r"#\s+([a-zA-Z0-9_]+)\.([a-zA-Z0-9_\-\.]+(?:_[A-F0-9]+)?)\s+(?:\([^)]+\)\s+)?will be created").unwrap();
-    static ref UPDATE_REGEX: Regex = Regex::new(r"#\s+([a-zA-Z0-9_]+)\.([a-zA-Z0-9_\-\.]+(?:_[A-F0-9]+)?)\s+(?:\([^)]+\)\s+)?will be (?:updated|updated in-place)").unwrap();
-    static ref DESTROY_REGEX: Regex = Regex::new(r"#\s+([a-zA-Z0-9_]+)\.([a-zA-Z0-9_\-\.]+(?:_[A-F0-9]+)?)\s+(?:\([^)]+\)\s+)?will be destroyed").unwrap();
+    // "# <address> [(extra info)] will be created|updated [in-place]|destroyed" or "must be replaced"
+    static ref RESOURCE_ACTION_REGEX: Regex = Regex::new(r"#\s+((?:[^\s\[]|\[[^\]]*\])+)\s+(?:\([^)]+\)\s+)?(?:will|must) be (created|updated|destroyed|replaced)").unwrap();
     static ref ARROW_CHANGE_REGEX: Regex = Regex::new(r"[~+-]?\s*(.+?)\s*=\s*(.+?)\s*->\s*(.+)").unwrap();
     static ref SIMPLE_ATTR_REGEX: Regex = Regex::new(r"([~+-]?)\s*(.+?)\s*=\s*(.+)").unwrap();
     static ref KNOWN_AFTER_APPLY_REGEX: Regex = Regex::new(r"[~+-]?\s*(.+?)\s*=").unwrap();
@@ -86,38 +132,31 @@ lazy_static! {
 
 fn parse_resource_action_line(line: &str) -> Option<Resource> {
     // Parse lines like "# aws_s3_bucket.example will be created"
-    // The format is: resource_type.resource_name
+    // or "# module.vpc.aws_subnet.public[0] will be updated in-place"
+    let captures = RESOURCE_ACTION_REGEX.captures(line)?;
+    let address_text = captures.get(1)?.as_str();
+    let address = ResourceAddress::parse(address_text)?;
     
-    if let Some(captures) = CREATE_REGEX.captures(line) {
-        return Some(create_resource_from_captures(&captures, ActionType::Create));
-    }
-    
-    if let Some(captures) = UPDATE_REGEX.captures(line) {
-        return Some(create_resource_from_captures(&captures, ActionType::Update));
-    }
-    
-    if let Some(captures) = DESTROY_REGEX.captures(line) {
-        return Some(create_resource_from_captures(&captures, ActionType::Destroy));
-    }
-    
-    None
-}
-
-fn create_resource_from_captures(captures: &regex::Captures, action: ActionType) -> Resource {
-    let resource_type = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
-    let resource_name = captures.get(2).map(|m| m.as_str()).unwrap_or("unknown");
-    let resource_id = format!("{}.{}", resource_type, resource_name);
+    // Replacements are reported as updates, as in the JSON plan parser
+    let replace = &captures[2] == "replaced";
+    let action = match &captures[2] {
+        "created" => ActionType::Create,
+        "updated" | "replaced" => ActionType::Update,
+        _ => ActionType::Destroy,
+    };
     
-    Resource {
-        id: resource_id,
-        name: resource_name.to_string(),
-        type_name: resource_type.to_string(),
-        provider: "unknown".to_string(),
+    Some(Resource {
+        id: address_text.to_string(),
+        provider: provider_from_type(&address.type_name),
+        name: address.name,
+        type_name: address.type_name,
         action,
         changes: Vec::new(),
         attributes: HashMap::new(),
         applied: false,
-    }
+        replace,
+        span: None,
+    })
 }
 
 fn parse_attribute_change(line: &str, attributes: &mut HashMap<String, Value>) {
@@ -167,7 +206,88 @@ fn parse_attribute_change(line: &str, attributes: &mut HashMap<String, Value>) {
     }
 }
 
-fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
+/// A multi-line list in an update, e.g. `~ security_groups = [`, read one
+/// element line at a time.
+struct ListElements {
+    /// Length of the nested names inside the list
+    depth: usize,
+    path: Vec<String>,
+    /// `+` for a list being added, `-` for one being removed, `~` otherwise
+    marker: char,
+    before: Vec<Value>,
+    after: Vec<Value>,
+    /// Whether the list holds maps or lists, which are not collected
+    complex: bool,
+    start_line: usize,
+}
+
+impl ListElements {
+    fn open(line: &str, nested: &[String], line_number: usize) -> Self {
+        ListElements {
+            depth: nested.len(),
+            path: nested.iter().filter(|name| !name.is_empty()).cloned().collect(),
+            marker: line.chars().next().filter(|marker| ['+', '-'].contains(marker)).unwrap_or('~'),
+            before: Vec::new(),
+            after: Vec::new(),
+            complex: false,
+            start_line: line_number,
+        }
+    }
+
+    /// Add an element line such as `- "sg-1",`; unchanged elements are in both lists.
+    fn push(&mut self, line: &str) {
+        // "# (2 unchanged elements hidden)"
+        if line.starts_with('#') {
+            return;
+        }
+        let (marker, element) = match line.split_at_checked(2) {
+            Some((marker @ ("+ " | "- " | "~ "), element)) => (marker.trim(), element),
+            _ => ("", line),
+        };
+        let value = parse_terraform_value(element.trim().trim_end_matches(','));
+        match marker {
+            "+" => self.after.push(value),
+            "-" => self.before.push(value),
+            _ => {
+                self.before.push(value.clone());
+                self.after.push(value);
+            }
+        }
+    }
+
+    /// The change to the whole list, if its elements could be read and changed.
+    fn close(self, line_number: usize) -> Option<Change> {
+        if self.complex || self.before == self.after {
+            return None;
+        }
+        Some(Change {
+            path: self.path,
+            before: (self.marker != '+').then_some(Value::Array(self.before)),
+            after: (self.marker != '-').then_some(Value::Array(self.after)),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: Some(SourceSpan::new(self.start_line, line_number)),
+        })
+    }
+}
+
+/// The attribute a line opens a map, list or nested block for, e.g. `tags` for
+/// `~ tags = {`; empty for an anonymous block such as a list element `~ {`.
+fn nested_block_name(line: &str) -> Option<String> {
+    let opened = line.strip_suffix(['{', '[', '('])?;
+    let unmarked = ["~ ", "+ ", "- ", "-/+ ", "+/- "].iter()
+        .find_map(|marker| opened.strip_prefix(marker))
+        .unwrap_or(opened);
+    let name = unmarked.split(" = ").next().unwrap_or_default().trim();
+    Some(unquote(name).to_string())
+}
+
+fn unquote(name: &str) -> &str {
+    name.strip_prefix('"').and_then(|name| name.strip_suffix('"')).unwrap_or(name)
+}
+
+fn parse_update_change(line: &str, line_number: usize, parents: &[String], changes: &mut Vec<Change>) {
     let line = line.trim();
     
     // Skip empty lines, comments, and structural lines
@@ -181,15 +301,15 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
     // - attribute = "value"           (removal)
     
     // Extract the attribute path (handle nested attributes)
-    let mut path = Vec::new();
+    let mut path: Vec<String> = parents.iter().filter(|name| !name.is_empty()).cloned().collect();
     
     // Remove leading change indicator
-    let (change_type, clean_line) = if line.starts_with("~ ") {
-        ("~", &line[2..])
-    } else if line.starts_with("+ ") {
-        ("+", &line[2..])
-    } else if line.starts_with("- ") {
-        ("-", &line[2..])
+    let (change_type, clean_line) = if let Some(rest) = line.strip_prefix("~ ") {
+        ("~", rest)
+    } else if let Some(rest) = line.strip_prefix("+ ") {
+        ("+", rest)
+    } else if let Some(rest) = line.strip_prefix("- ") {
+        ("-", rest)
     } else {
         ("", line)
     };
@@ -207,9 +327,9 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
             
             if let Some(arrow_pos) = values_part.find(" -> ") {
                 let old_value = values_part[..arrow_pos].trim();
-                let new_value = values_part[arrow_pos + 4..].trim();
+                let (new_value, forces_replacement) = strip_forces_replacement(&values_part[arrow_pos + 4..]);
                 
-                path.push(attr_name.to_string());
+                path.push(unquote(attr_name).to_string());
                 
                 let change = Change {
                     path: path.clone(),
@@ -217,6 +337,8 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
                     after: Some(parse_terraform_value(new_value)),
                     sensitive: false,
                     computed: false,
+                    forces_replacement,
+                    span: Some(SourceSpan::line(line_number)),
                 };
                 
                 changes.push(change);
@@ -227,9 +349,9 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
     else if change_type == "+" && clean_line.contains(" = ") {
         if let Some(eq_pos) = clean_line.find(" = ") {
             let attr_name = clean_line[..eq_pos].trim();
-            let value = clean_line[eq_pos + 3..].trim();
+            let (value, forces_replacement) = strip_forces_replacement(&clean_line[eq_pos + 3..]);
             
-            path.push(attr_name.to_string());
+            path.push(unquote(attr_name).to_string());
             
             let change = Change {
                 path: path.clone(),
@@ -237,6 +359,8 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
                 after: Some(parse_terraform_value(value)),
                 sensitive: false,
                 computed: value == "(known after apply)",
+                forces_replacement,
+                span: Some(SourceSpan::line(line_number)),
             };
             
             changes.push(change);
@@ -246,9 +370,9 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
     else if change_type == "-" && clean_line.contains(" = ") {
         if let Some(eq_pos) = clean_line.find(" = ") {
             let attr_name = clean_line[..eq_pos].trim();
-            let value = clean_line[eq_pos + 3..].trim();
+            let (value, forces_replacement) = strip_forces_replacement(&clean_line[eq_pos + 3..]);
             
-            path.push(attr_name.to_string());
+            path.push(unquote(attr_name).to_string());
             
             let change = Change {
                 path: path.clone(),
@@ -256,6 +380,8 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
                 after: None,
                 sensitive: false,
                 computed: false,
+                forces_replacement,
+                span: Some(SourceSpan::line(line_number)),
             };
             
             changes.push(change);
@@ -263,7 +389,16 @@ fn parse_update_change(line: &str, changes: &mut Vec<Change>) {
     }
 }
 
-fn parse_terraform_value(value_str: &str) -> Value {
+/// Split Terraform's `# forces replacement` note off a printed value.
+fn strip_forces_replacement(value: &str) -> (&str, bool) {
+    match value.trim().strip_suffix("# forces replacement") {
+        Some(value) => (value.trim(), true),
+        None => (value.trim(), false),
+    }
+}
+
+/// Convert a value as printed by Terraform (quoted string, number, bool, null) to JSON.
+pub fn parse_terraform_value(value_str: &str) -> Value {
     let trimmed = value_str.trim();
     
     // Remove quotes if present
@@ -0,0 +1,223 @@
+use crate::{ActionType, Change, DataSource, Metadata, PlanMode, Resource, ResourceAddress, Result, Summary, TerraformPlan};
+use crate::parser::provider::{provider_from_source, provider_from_type};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Whether the input is a `terraform show -json` plan rather than human-readable output.
+pub fn is_json_plan(input: &str) -> bool {
+    if !input.trim_start().starts_with('{') {
+        return false;
+    }
+    match serde_json::from_str::<Value>(input) {
+        Ok(value) => value.get("resource_changes").is_some() || value.get("format_version").is_some(),
+        Err(_) => false,
+    }
+}
+
+/// Parse the JSON plan representation produced by `terraform show -json plan.tfplan`.
+///
+/// JSON plans carry no summary line, so `summary` is derived from the resources;
+/// replacements (`["delete", "create"]`) are reported as updates marked `replace`.
+pub fn parse_json_plan(input: &str) -> Result<TerraformPlan> {
+    let plan: Value = serde_json::from_str(input)?;
+    let provider_keys = collect_provider_config_keys(&plan);
+
+    let mut resources = Vec::new();
+    let mut data_sources = Vec::new();
+
+    let resource_changes = plan.get("resource_changes").and_then(Value::as_array).cloned().unwrap_or_default();
+    for resource_change in &resource_changes {
+        let Some(address) = resource_change.get("address").and_then(Value::as_str) else { continue };
+        let Some(parsed_address) = ResourceAddress::parse(address) else { continue };
+        let change = resource_change.get("change").cloned().unwrap_or(Value::Null);
+        let actions: Vec<&str> = change.get("actions")
+            .and_then(Value::as_array)
+            .map(|actions| actions.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let provider = match resource_change.get("provider_name").and_then(Value::as_str) {
+            Some(source) => {
+                let alias = provider_keys.get(&config_address(resource_change)).and_then(|key| provider_alias(key));
+                provider_from_source(source, alias)
+            }
+            None => provider_from_type(&parsed_address.type_name),
+        };
+
+        let before = change.get("before").and_then(Value::as_object).cloned().unwrap_or_default();
+        let after = change.get("after").and_then(Value::as_object).cloned().unwrap_or_default();
+
+        let replace = actions.contains(&"delete") && actions.contains(&"create");
+        let action = match actions.as_slice() {
+            ["create"] => ActionType::Create,
+            ["delete"] => ActionType::Destroy,
+            ["read"] => ActionType::Read,
+            ["no-op"] | [] => continue,
+            _ => ActionType::Update,
+        };
+
+        if parsed_address.data {
+            data_sources.push(DataSource {
+                id: address.to_string(),
+                name: parsed_address.name,
+                type_name: parsed_address.type_name,
+                provider,
+                attributes: with_unknowns(&after, change.get("after_unknown")).into_iter().collect(),
+            });
+            continue;
+        }
+
+        let (changes, attributes) = match action {
+            ActionType::Create => (Vec::new(), with_unknowns(&after, change.get("after_unknown")).into_iter().collect()),
+            ActionType::Destroy => (Vec::new(), before.into_iter().collect()),
+            _ => (diff_objects(&before, &after, &change), HashMap::new()),
+        };
+
+        resources.push(Resource {
+            id: address.to_string(),
+            name: parsed_address.name,
+            type_name: parsed_address.type_name,
+            provider,
+            action,
+            changes,
+            attributes,
+            applied: false,
+            replace,
+            span: None,
+        });
+    }
+
+    let mut summary = Summary::from_resources(&resources);
+    summary.read = data_sources.len();
+
+    Ok(TerraformPlan {
+        mode: PlanMode::Plan,
+        parsed_summary: summary.clone(),
+        summary,
+        resources,
+        data_sources,
+        warnings: Vec::new(),
+        metadata: Metadata {
+            terraform_version: plan.get("terraform_version").and_then(Value::as_str).map(str::to_string),
+            timestamp: plan.get("timestamp").and_then(Value::as_str).map(str::to_string),
+            duration: None,
+        },
+        suppressed: Vec::new(),
+        policy_results: Vec::new(),
+        annotations: Vec::new(),
+        cost: None,
+    })
+}
+
+fn diff_objects(before: &Map<String, Value>, after: &Map<String, Value>, change: &Value) -> Vec<Change> {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut changes = Vec::new();
+    for key in keys {
+        let computed = is_flagged(change.get("after_unknown"), key);
+        let sensitive = is_flagged(change.get("before_sensitive"), key) || is_flagged(change.get("after_sensitive"), key);
+        let old = before.get(key).filter(|value| !value.is_null());
+        let new = after.get(key).filter(|value| !value.is_null());
+
+        if old == new && !computed {
+            continue;
+        }
+
+        let hide = |value: Option<&Value>| value.map(|v| if sensitive { Value::String("(sensitive value)".to_string()) } else { v.clone() });
+        changes.push(Change {
+            path: vec![key.clone()],
+            before: hide(old),
+            after: if computed { Some(Value::String("(known after apply)".to_string())) } else { hide(new) },
+            sensitive,
+            computed,
+            forces_replacement: forces_replacement(change, key),
+            span: None,
+        });
+    }
+
+    changes
+}
+
+fn with_unknowns(values: &Map<String, Value>, unknown: Option<&Value>) -> Map<String, Value> {
+    let mut values = values.clone();
+    if let Some(unknown) = unknown.and_then(Value::as_object) {
+        for (key, flag) in unknown {
+            if flag == &Value::Bool(true) {
+                values.insert(key.clone(), Value::String("(known after apply)".to_string()));
+            }
+        }
+    }
+    values
+}
+
+/// Whether `replace_paths` names the attribute or something inside it.
+fn forces_replacement(change: &Value, key: &str) -> bool {
+    change.get("replace_paths").and_then(Value::as_array).into_iter().flatten()
+        .any(|path| path.get(0).and_then(Value::as_str) == Some(key))
+}
+
+fn is_flagged(flags: Option<&Value>, key: &str) -> bool {
+    flags.and_then(|flags| flags.get(key)) == Some(&Value::Bool(true))
+}
+
+/// Address of the resource's configuration block, without instance keys.
+fn config_address(resource_change: &Value) -> String {
+    let field = |name: &str| resource_change.get(name).and_then(Value::as_str);
+    let mut parts = Vec::new();
+    if let Some(module) = field("module_address") {
+        // Drop instance keys such as module.app["blue"]
+        parts.push(strip_indexes(module));
+    }
+    if field("mode") == Some("data") {
+        parts.push("data".to_string());
+    }
+    parts.push(field("type").unwrap_or_default().to_string());
+    parts.push(field("name").unwrap_or_default().to_string());
+    parts.join(".")
+}
+
+fn strip_indexes(address: &str) -> String {
+    let mut result = String::new();
+    let mut depth = 0;
+    for ch in address.chars() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ if depth == 0 => result.push(ch),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Map configuration addresses to their `provider_config_key` (e.g. `aws.west`).
+fn collect_provider_config_keys(plan: &Value) -> HashMap<String, String> {
+    let mut keys = HashMap::new();
+    if let Some(root) = plan.pointer("/configuration/root_module") {
+        collect_module_provider_keys(root, "", &mut keys);
+    }
+    keys
+}
+
+fn collect_module_provider_keys(module: &Value, prefix: &str, keys: &mut HashMap<String, String>) {
+    for resource in module.get("resources").and_then(Value::as_array).into_iter().flatten() {
+        let address = resource.get("address").and_then(Value::as_str);
+        let key = resource.get("provider_config_key").and_then(Value::as_str);
+        if let (Some(address), Some(key)) = (address, key) {
+            keys.insert(format!("{}{}", prefix, address), key.to_string());
+        }
+    }
+
+    for (name, call) in module.get("module_calls").and_then(Value::as_object).into_iter().flatten() {
+        if let Some(child) = call.get("module") {
+            collect_module_provider_keys(child, &format!("{}module.{}.", prefix, name), keys);
+        }
+    }
+}
+
+/// The alias part of a provider config key: `aws.west` or `network:aws.west` -> `west`.
+fn provider_alias(key: &str) -> Option<&str> {
+    let local = key.rsplit(':').next().unwrap_or(key);
+    local.split_once('.').map(|(_, alias)| alias)
+}
@@ -1,7 +1,17 @@
 pub mod cleaner;
 pub mod terraform;
 pub mod diff;
+pub mod diagnostics;
+pub mod provider;
+pub mod json_plan;
+pub mod ndjson;
+pub mod apply;
 
 pub use cleaner::*;
 pub use terraform::*;
-pub use diff::*;
\ No newline at end of file
+pub use diff::*;
+pub use diagnostics::*;
+pub use provider::*;
+pub use json_plan::*;
+pub use ndjson::*;
+pub use apply::*;
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::{ActionType, DataSource, Metadata, PlanMode, Resource, ResourceAddress, Result, Summary, TerraformPlan, TfdiffError, Warning, WarningLevel};
+use crate::parser::provider::provider_from_type;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Whether the input is a machine-readable log from `terraform plan -json` or `terraform apply -json`.
+pub fn is_ndjson_log(input: &str) -> bool {
+    let Some(first) = input.lines().map(str::trim).find(|line| !line.is_empty()) else {
+        return false;
+    };
+    match serde_json::from_str::<Value>(first) {
+        Ok(Value::Object(message)) => message.contains_key("@message") && message.contains_key("type"),
+        _ => false,
+    }
+}
+
+/// Parse a newline-delimited JSON log from `terraform plan -json` or `terraform apply -json`.
+///
+/// The log only carries addresses and actions, so resources have no attribute changes.
+/// Lines that are not JSON (e.g. a stray shell prompt) are skipped.
+pub fn parse_ndjson_log(input: &str) -> Result<TerraformPlan> {
+    let mut mode = PlanMode::Plan;
+    let mut summary = None;
+    let mut metadata = Metadata::default();
+    let mut warnings = Vec::new();
+    let mut resources: Vec<Resource> = Vec::new();
+    let mut data_sources = Vec::new();
+    let mut applied: Vec<String> = Vec::new();
+
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let Ok(message) = serde_json::from_str::<Value>(line) else { continue };
+        let field = |pointer: &str| message.pointer(pointer).and_then(Value::as_str);
+
+        if metadata.timestamp.is_none() {
+            metadata.timestamp = field("/@timestamp").map(str::to_string);
+        }
+
+        match field("/type").unwrap_or_default() {
+            "version" => metadata.terraform_version = field("/terraform").map(str::to_string),
+            "planned_change" => {
+                let Some(change) = message.get("change") else { continue };
+                let Some(resource) = resource_from_change(change) else { continue };
+                match resource {
+                    Planned::Resource(resource) => {
+                        // Apply logs repeat the plan; keep the first entry per address
+                        if !resources.iter().any(|existing| existing.id == resource.id) {
+                            resources.push(resource);
+                        }
+                    }
+                    Planned::DataSource(data_source) => data_sources.push(data_source),
+                }
+            }
+            "apply_start" => mode = PlanMode::Apply,
+            "apply_complete" => {
+                mode = PlanMode::Apply;
+                if let Some(address) = field("/hook/resource/addr") {
+                    applied.push(address.to_string());
+                }
+            }
+            "apply_errored" => {
+                mode = PlanMode::Apply;
+                warnings.push(Warning {
+                    message: field("/@message").unwrap_or_default().to_string(),
+                    level: WarningLevel::Error,
+                    span: None,
+                    kind: None,
+                    address: field("/hook/resource/addr").map(str::to_string),
+                });
+            }
+            "change_summary" => {
+                let count = |name: &str| message.pointer(&format!("/changes/{}", name)).and_then(Value::as_u64).unwrap_or(0) as usize;
+                if matches!(field("/changes/operation"), Some("apply") | Some("destroy")) {
+                    mode = PlanMode::Apply;
+                }
+                summary = Some(Summary {
+                    add: count("add"),
+                    change: count("change"),
+                    destroy: count("remove"),
+                    read: 0,
+                });
+            }
+            "diagnostic" => {
+                let level = match field("/diagnostic/severity") {
+                    Some("error") => WarningLevel::Error,
+                    _ => WarningLevel::Warning,
+                };
+                let text = match (field("/diagnostic/summary"), field("/diagnostic/detail")) {
+                    (Some(summary), Some(detail)) if !detail.is_empty() => format!("{}: {}", summary, detail),
+                    (Some(summary), _) => summary.to_string(),
+                    _ => field("/@message").unwrap_or_default().to_string(),
+                };
+                let address = field("/diagnostic/address").map(str::to_string);
+                warnings.push(Warning { message: text, level, span: None, kind: None, address });
+            }
+            _ => {}
+        }
+    }
+
+    for resource in &mut resources {
+        resource.applied = applied.contains(&resource.id);
+    }
+
+    let parsed_summary = Summary::from_resources(&resources);
+    let Some(mut summary) = summary.or_else(|| (!resources.is_empty()).then(|| parsed_summary.clone())) else {
+        return Err(TfdiffError::ParseError("No change summary or planned changes found in JSON log".to_string()));
+    };
+    summary.read = data_sources.len();
+
+    Ok(TerraformPlan {
+        mode,
+        summary,
+        parsed_summary,
+        resources,
+        data_sources,
+        warnings,
+        metadata,
+        suppressed: Vec::new(),
+        policy_results: Vec::new(),
+        annotations: Vec::new(),
+        cost: None,
+    })
+}
+
+enum Planned {
+    Resource(Resource),
+    DataSource(DataSource),
+}
+
+fn resource_from_change(change: &Value) -> Option<Planned> {
+    let address = change.pointer("/resource/addr").and_then(Value::as_str)?;
+    let parsed = ResourceAddress::parse(address)?;
+    let provider = change.pointer("/resource/implied_provider")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| provider_from_type(&parsed.type_name));
+
+    // Replacements are reported as updates, as in the JSON plan parser
+    let planned_action = change.get("action").and_then(Value::as_str)?;
+    let action = match planned_action {
+        "create" => ActionType::Create,
+        "update" | "replace" => ActionType::Update,
+        "delete" => ActionType::Destroy,
+        "read" => ActionType::Read,
+        _ => return None,
+    };
+
+    if parsed.data {
+        return Some(Planned::DataSource(DataSource {
+            id: address.to_string(),
+            name: parsed.name,
+            type_name: parsed.type_name,
+            provider,
+            attributes: HashMap::new(),
+        }));
+    }
+
+    Some(Planned::Resource(Resource {
+        id: address.to_string(),
+        name: parsed.name,
+        type_name: parsed.type_name,
+        provider,
+        action,
+        changes: Vec::new(),
+        attributes: HashMap::new(),
+        applied: false,
+        replace: planned_action == "replace",
+        span: None,
+    }))
+}
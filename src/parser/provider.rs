@@ -0,0 +1,31 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // provider["registry.terraform.io/hashicorp/aws"].west
+    static ref PROVIDER_REFERENCE_REGEX: Regex = Regex::new(r#"provider\["([^"]+)"\](?:\.([A-Za-z0-9_-]+))?"#).unwrap();
+}
+
+/// Guess the provider from the resource type prefix, e.g. `aws_s3_bucket` -> `aws`.
+pub fn provider_from_type(type_name: &str) -> String {
+    match type_name.split_once('_') {
+        Some((prefix, _)) if !prefix.is_empty() => prefix.to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Provider name from a source address like `registry.terraform.io/hashicorp/aws`, keeping the alias.
+pub fn provider_from_source(source: &str, alias: Option<&str>) -> String {
+    let name = source.rsplit('/').next().unwrap_or(source);
+    match alias {
+        Some(alias) if !alias.is_empty() => format!("{}.{}", name, alias),
+        _ => name.to_string(),
+    }
+}
+
+/// Find a `provider["source"].alias` reference in a line of text output.
+pub fn provider_from_reference(line: &str) -> Option<String> {
+    PROVIDER_REFERENCE_REGEX.captures(line).map(|captures| {
+        provider_from_source(&captures[1], captures.get(2).map(|m| m.as_str()))
+    })
+}
@@ -1,33 +1,102 @@
-use crate::{Result, TerraformPlan, PlanMode, Summary};
+use crate::{Result, TfdiffError, TerraformPlan, PlanMode, Summary, SourceSpan, Warning};
+use crate::parser::{CleanedLine, collect_diagnostics, is_apply_progress_line, is_json_plan, is_ndjson_log, merge_apply_progress, parse_apply_progress, parse_error_blocks, parse_json_plan, parse_ndjson_log};
 use lazy_static::lazy_static;
 use regex::Regex;
 
+/// How the parser treats input it cannot fully account for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Keep going and record problems as warnings on the plan
+    #[default]
+    Lenient,
+    /// Fail with `TfdiffError::InvalidInput` listing every problem found
+    Strict,
+}
+
+/// How to parse Terraform output.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+    /// Stack or log prefixes to strip from text output, besides the built-in ones
+    pub prefix_patterns: Vec<Regex>,
+}
+
 pub fn parse_terraform_output(input: &str) -> Result<TerraformPlan> {
-    let cleaned = crate::parser::clean_input(input)?;
-    let lines: Vec<&str> = cleaned.lines().collect();
+    parse_terraform_output_with_mode(input, ParseMode::Lenient)
+}
+
+pub fn parse_terraform_output_with_mode(input: &str, parse_mode: ParseMode) -> Result<TerraformPlan> {
+    parse_terraform_output_with_options(input, &ParseOptions { mode: parse_mode, ..Default::default() })
+}
+
+pub fn parse_terraform_output_with_options(input: &str, options: &ParseOptions) -> Result<TerraformPlan> {
+    let parse_mode = options.mode;
+    if is_json_plan(input) {
+        return parse_json_plan(input);
+    }
+    if is_ndjson_log(input) {
+        return parse_ndjson_log(input);
+    }
+    
+    let cleaned = crate::parser::clean_input_lines_with_prefixes(input, &options.prefix_patterns);
+    let lines: Vec<&str> = cleaned.iter().map(|line| line.text.as_str()).collect();
     
     let mode = detect_mode(&lines)?;
     let summary = extract_summary(&lines)?;
-    let resources = crate::parser::diff::parse_resource_changes(&lines);
-    let warnings = Vec::new(); // TODO: Implement warning extraction
+    let mut resources = crate::parser::diff::parse_resource_changes(&lines);
+    let mut diagnostics = collect_diagnostics(&lines, &mode, &summary, &resources);
+    let mut errors = parse_error_blocks(&lines);
+    merge_apply_progress(&mut resources, &parse_apply_progress(&lines));
+    
+    // Point spans back at the raw input rather than the cleaned lines
+    for resource in &mut resources {
+        remap_span(resource.span.as_mut(), &cleaned);
+        for change in &mut resource.changes {
+            remap_span(change.span.as_mut(), &cleaned);
+        }
+    }
+    for diagnostic in &mut diagnostics {
+        remap_span(Some(&mut diagnostic.span), &cleaned);
+    }
+    for error in &mut errors {
+        remap_span(error.span.as_mut(), &cleaned);
+    }
+    
+    if parse_mode == ParseMode::Strict && !diagnostics.is_empty() {
+        return Err(TfdiffError::InvalidInput(diagnostics));
+    }
+    
+    let parsed_summary = Summary::from_resources(&resources);
+    let warnings = errors.into_iter().chain(diagnostics.into_iter().map(Warning::from)).collect();
     
     Ok(TerraformPlan {
         mode,
         summary,
+        parsed_summary,
         resources,
         warnings,
         ..Default::default()
     })
 }
 
+fn remap_span(span: Option<&mut SourceSpan>, cleaned: &[CleanedLine]) {
+    let Some(span) = span else { return };
+    let start = span.start_line.checked_sub(1).and_then(|i| cleaned.get(i));
+    let end = span.end_line.checked_sub(1).and_then(|i| cleaned.get(i));
+    
+    if let (Some(start), Some(end)) = (start, end) {
+        *span = SourceSpan {
+            start_line: start.start_line,
+            end_line: end.end_line,
+            prefix: start.prefix.clone(),
+        };
+    }
+}
+
 pub fn detect_mode(lines: &[&str]) -> Result<PlanMode> {
-    for line in lines {
-        if line.contains("Plan:") {
-            return Ok(PlanMode::Plan);
-        }
-        if line.contains("Apply complete!") {
-            return Ok(PlanMode::Apply);
-        }
+    // Apply output repeats the plan, including its "Plan:" line, before applying it
+    if lines.iter().any(|line| line.contains("Apply complete!") || is_apply_progress_line(line)) {
+        return Ok(PlanMode::Apply);
     }
     
     // Default to Plan if unclear
@@ -35,13 +104,15 @@ pub fn detect_mode(lines: &[&str]) -> Result<PlanMode> {
 }
 
 pub fn extract_summary(lines: &[&str]) -> Result<Summary> {
+    // What was applied takes precedence over what was planned
+    if let Some(line) = lines.iter().find(|line| line.contains("Apply complete!")) {
+        return parse_apply_summary(line);
+    }
+    
     for line in lines {
         if line.contains("Plan:") {
             return parse_plan_summary(line);
         }
-        if line.contains("Apply complete!") {
-            return parse_apply_summary(line);
-        }
     }
     
     Ok(Summary::default())
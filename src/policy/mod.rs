@@ -0,0 +1,180 @@
+use crate::config::{load_toml_file, parse_toml};
+use crate::{ActionType, Expression, PolicyOutcome, PolicyResult, Resource, ResourceQuery, Result, TerraformPlan, TfdiffError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Rules a plan must follow before it may be applied, read from a TOML file:
+///
+/// ```toml
+/// [[policy]]
+/// name = "protect-databases"
+/// description = "Databases are never destroyed or replaced"
+/// actions = ["destroy", "replace"]
+/// resource_types = ["aws_db_instance"]
+///
+/// [[policy]]
+/// name = "small-plans"
+/// level = "warn"
+/// max_changes = 20
+///
+/// [[policy]]
+/// name = "iam-review"
+/// resource_types = ["aws_iam_*"]
+/// unless_label = "iam-approved"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicySet {
+    #[serde(rename = "policy")]
+    pub policies: Vec<Policy>,
+}
+
+/// Denies changes to the resources it selects or, with `max_changes`, more
+/// than that many of them.
+///
+/// The selectors work like the resource filters of the same name and must all
+/// match; a policy without selectors covers every resource the plan changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    pub name: String,
+    /// What the policy protects, shown in reports
+    pub description: Option<String>,
+    pub level: PolicyLevel,
+    /// Action names as for `--filter`, plus `replace`; replacements also count as updates
+    pub actions: Vec<String>,
+    pub resource_types: Vec<String>,
+    pub addresses: Vec<String>,
+    pub modules: Vec<String>,
+    pub providers: Vec<String>,
+    pub changed_attributes: Vec<String>,
+    pub exclude: Vec<String>,
+    #[serde(rename = "where")]
+    pub where_expression: Option<String>,
+    /// Allow up to this many selected resources to change instead of none
+    pub max_changes: Option<usize>,
+    /// A label, given with `--label`, that allows the violations
+    pub unless_label: Option<String>,
+}
+
+/// How a broken policy is reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyLevel {
+    Warn,
+    #[default]
+    Fail,
+}
+
+impl Policy {
+    /// Check the name, actions and `where` expression.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(TfdiffError::ConfigError("every policy needs a name".to_string()));
+        }
+        self.selector().map(|_| ())
+    }
+
+    /// The resources the policy selects, and whether it selects replacements.
+    fn selector(&self) -> Result<(ResourceQuery, Vec<ActionType>, bool)> {
+        let in_policy = |e: TfdiffError| match e {
+            TfdiffError::InvalidQuery(message) => TfdiffError::ConfigError(format!("policy '{}': {}", self.name, message)),
+            other => other,
+        };
+        let replace = self.actions.iter().any(|action| action.trim().eq_ignore_ascii_case("replace"));
+        let actions = self.actions.iter()
+            .filter(|action| !action.trim().eq_ignore_ascii_case("replace"))
+            .map(|action| action.parse())
+            .collect::<Result<Vec<ActionType>>>()
+            .map_err(in_policy)?;
+        let query = ResourceQuery {
+            resource_types: self.resource_types.clone(),
+            addresses: self.addresses.clone(),
+            modules: self.modules.clone(),
+            providers: self.providers.clone(),
+            changed_attributes: self.changed_attributes.clone(),
+            exclude: self.exclude.clone(),
+            expression: self.where_expression.as_deref().map(Expression::parse).transpose().map_err(in_policy)?,
+            ..ResourceQuery::default()
+        };
+        Ok((query, actions, replace))
+    }
+
+    /// Check the policy against a plan; `labels` are the labels given to the run.
+    pub fn evaluate(&self, plan: &TerraformPlan, labels: &[String]) -> Result<PolicyResult> {
+        let (query, actions, replace) = self.selector()?;
+        let selected: Vec<&Resource> = plan.resources.iter()
+            .filter(|resource| matches!(resource.action, ActionType::Create | ActionType::Update | ActionType::Destroy))
+            .filter(|resource| (actions.is_empty() && !replace)
+                || actions.contains(&resource.action)
+                || (replace && resource.replace))
+            .filter(|resource| query.matches(resource))
+            .collect();
+
+        let (violations, addresses) = match self.max_changes {
+            Some(limit) if selected.len() > limit => (vec![format!(
+                "{} resources change, more than the limit of {}", selected.len(), limit
+            )], Vec::new()),
+            Some(_) => (Vec::new(), Vec::new()),
+            None => (
+                selected.iter()
+                    .map(|resource| format!("{} will be {}", resource.id, resource.action_description()))
+                    .collect(),
+                selected.iter().map(|resource| resource.id.clone()).collect(),
+            ),
+        };
+
+        let waived_by = self.unless_label.as_ref()
+            .filter(|label| !violations.is_empty() && labels.contains(label))
+            .cloned();
+        let outcome = match (violations.is_empty() || waived_by.is_some(), self.level) {
+            (true, _) => PolicyOutcome::Pass,
+            (false, PolicyLevel::Warn) => PolicyOutcome::Warn,
+            (false, PolicyLevel::Fail) => PolicyOutcome::Fail,
+        };
+
+        Ok(PolicyResult {
+            policy: self.name.clone(),
+            description: self.description.clone(),
+            outcome,
+            violations,
+            waived_by,
+            addresses,
+        })
+    }
+}
+
+impl PolicySet {
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let policies: PolicySet = parse_toml(source)?;
+        policies.validate()?;
+        Ok(policies)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        load_toml_file(path, Self::from_toml)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.policies.iter().try_for_each(Policy::validate)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Check every policy against a plan, in the order they are defined.
+    pub fn evaluate(&self, plan: &TerraformPlan, labels: &[String]) -> Result<Vec<PolicyResult>> {
+        self.policies.iter()
+            .map(|policy| policy.evaluate(plan, labels))
+            .collect()
+    }
+}
+
+/// The worst outcome among the results; a plan without policies passes.
+pub fn policy_outcome(results: &[PolicyResult]) -> PolicyOutcome {
+    results.iter()
+        .map(|result| result.outcome)
+        .max()
+        .unwrap_or(PolicyOutcome::Pass)
+}
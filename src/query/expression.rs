@@ -0,0 +1,529 @@
+use crate::compare::{proposed_values, ProposedValue};
+use crate::{ActionType, Resource, Result, TfdiffError};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A boolean expression selecting resources, as given to `--where`.
+///
+/// ```text
+/// action == "update" and attr("tags.Environment") changed and module ~ "prod"
+/// ```
+///
+/// Fields: `action`, `address`, `type`, `name`, `provider`, `module` (null in the
+/// root module), `applied` and `changes` (number of changed attributes).
+/// `attr(path)` is an attribute's planned value, `before(path)` and `after(path)`
+/// either side of its change; `attr(path) changed` holds when the plan changes the
+/// attribute or anything under it. Operators: `==`, `!=`, `~` and `!~` (regular
+/// expression match), `<`, `<=`, `>`, `>=`, combined with `and`, `or`, `not` and
+/// parentheses.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { source, tokens, position: 0, depth: 0 };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(parser.error_at(token.offset, &format!("unexpected {}", token.kind)));
+        }
+        Ok(Self { source: source.to_string(), root })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, resource: &Resource) -> bool {
+        let mut context = Context { resource, values: None };
+        self.root.evaluate(&mut context)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = TfdiffError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        Self::parse(source)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+const FIELDS: &[&str] = &["action", "address", "type", "name", "provider", "module", "applied", "changes"];
+const FUNCTIONS: &[&str] = &["attr", "before", "after"];
+
+#[derive(Debug, Clone)]
+enum Node {
+    /// Operands of a chain of `and`s or `or`s, kept flat so a long chain does not nest
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Not(Box<Node>),
+    Compare(Operand, Operator, Operand),
+    Matches(Operand, Regex, bool),
+    Changed(String),
+    Truthy(Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Field(&'static str),
+    Attribute(Side, String),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Planned,
+    Before,
+    After,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// A resource being evaluated, with its attribute values worked out on first use.
+struct Context<'a> {
+    resource: &'a Resource,
+    values: Option<BTreeMap<String, ProposedValue>>,
+}
+
+impl Context<'_> {
+    fn values(&mut self) -> &BTreeMap<String, ProposedValue> {
+        let resource = self.resource;
+        self.values.get_or_insert_with(|| proposed_values(resource))
+    }
+}
+
+impl Node {
+    fn evaluate(&self, context: &mut Context) -> bool {
+        match self {
+            Node::And(nodes) => nodes.iter().all(|node| node.evaluate(context)),
+            Node::Or(nodes) => nodes.iter().any(|node| node.evaluate(context)),
+            Node::Not(inner) => !inner.evaluate(context),
+            Node::Compare(left, operator, right) => compare(&left.value(context), *operator, &right.value(context)),
+            Node::Matches(operand, regex, expected) => match operand.value(context) {
+                Value::Null => false,
+                value => regex.is_match(&display(&value)) == *expected,
+            },
+            Node::Changed(path) => {
+                if lookup(context.values(), path).is_some_and(|value| value.before != value.after) {
+                    return true;
+                }
+                let prefix = format!("{}.", path);
+                context.values().iter()
+                    .filter(|(key, _)| key.starts_with(&prefix))
+                    .any(|(_, value)| value.before != value.after)
+            }
+            Node::Truthy(operand) => operand.value(context) == Value::Bool(true),
+        }
+    }
+}
+
+impl Operand {
+    fn value(&self, context: &mut Context) -> Value {
+        let resource = context.resource;
+        match self {
+            Operand::Field(name) => match *name {
+                "action" => Value::from(action_name(&resource.action)),
+                "address" => Value::from(resource.id.as_str()),
+                "type" => Value::from(resource.type_name.as_str()),
+                "name" => Value::from(resource.name.as_str()),
+                "provider" => Value::from(resource.provider.as_str()),
+                "module" => resource.module().map(Value::from).unwrap_or(Value::Null),
+                "applied" => Value::Bool(resource.applied),
+                _ => Value::from(context.values().values().filter(|value| value.before != value.after).count()),
+            },
+            Operand::Attribute(side, path) => {
+                let Some(value) = lookup(context.values(), path) else { return Value::Null };
+                let side = match side {
+                    Side::Planned => value.after.as_ref().or(value.before.as_ref()),
+                    Side::Before => value.before.as_ref(),
+                    Side::After => value.after.as_ref(),
+                };
+                side.cloned().unwrap_or(Value::Null)
+            }
+            Operand::Literal(value) => value.clone(),
+        }
+    }
+}
+
+/// The proposed value at a dotted path, descending into object and list values
+/// when the plan records the change on a parent (e.g. `tags` for `tags.Environment`).
+pub(crate) fn lookup(values: &BTreeMap<String, ProposedValue>, path: &str) -> Option<ProposedValue> {
+    if let Some(value) = values.get(path) {
+        return Some(value.clone());
+    }
+
+    let (parent, value) = values.iter()
+        .filter(|(key, _)| path.strip_prefix(key.as_str()).is_some_and(|rest| rest.starts_with('.')))
+        .max_by_key(|(key, _)| key.len())?;
+    let rest = &path[parent.len() + 1..];
+    let descend = |side: &Option<Value>| side.as_ref().and_then(|value| descend(value, rest));
+
+    let (before, after) = (descend(&value.before), descend(&value.after));
+    (before.is_some() || after.is_some()).then_some(ProposedValue { before, after })
+}
+
+fn descend(value: &Value, path: &str) -> Option<Value> {
+    path.split('.').try_fold(value, |value, segment| match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => None,
+    }).cloned()
+}
+
+fn action_name(action: &ActionType) -> &'static str {
+    match action {
+        ActionType::Create => "create",
+        ActionType::Update => "update",
+        ActionType::Destroy => "destroy",
+        ActionType::Read => "read",
+        ActionType::NoOp => "no-op",
+    }
+}
+
+/// Strings as their contents, everything else as JSON.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+fn compare(left: &Value, operator: Operator, right: &Value) -> bool {
+    // Plans print some numbers and booleans as strings, so compare loosely
+    let equal = left == right
+        || (!left.is_null() && !right.is_null() && display(left) == display(right))
+        || matches!((number(left), number(right)), (Some(a), Some(b)) if a == b);
+
+    match operator {
+        Operator::Equal => equal,
+        Operator::NotEqual => !equal,
+        ordering => match (number(left), number(right)) {
+            (Some(a), Some(b)) => match ordering {
+                Operator::Less => a < b,
+                Operator::LessOrEqual => a <= b,
+                Operator::Greater => a > b,
+                _ => a >= b,
+            },
+            _ => false,
+        },
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Identifier(String),
+    Str(String),
+    Number(f64),
+    Operator(&'static str),
+    OpenParen,
+    CloseParen,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Identifier(name) => write!(f, "'{}'", name),
+            TokenKind::Str(text) => write!(f, "string \"{}\"", text),
+            TokenKind::Number(number) => write!(f, "number {}", number),
+            TokenKind::Operator(operator) => write!(f, "'{}'", operator),
+            TokenKind::OpenParen => f.write_str("'('"),
+            TokenKind::CloseParen => f.write_str("')'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// Byte offset into the source
+    offset: usize,
+}
+
+const OPERATORS: &[&str] = &["==", "!=", "!~", "<=", ">=", "~", "<", ">"];
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(offset, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let kind = if ch == '(' {
+            chars.next();
+            TokenKind::OpenParen
+        } else if ch == ')' {
+            chars.next();
+            TokenKind::CloseParen
+        } else if ch == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, escaped)) => text.push(escaped),
+                        None => return Err(error_at(source, offset, "unterminated string")),
+                    },
+                    Some((_, other)) => text.push(other),
+                    None => return Err(error_at(source, offset, "unterminated string")),
+                }
+            }
+            TokenKind::Str(text)
+        } else if ch.is_ascii_digit() || (ch == '-' && source[offset + 1..].starts_with(|c: char| c.is_ascii_digit())) {
+            let mut end = offset + ch.len_utf8();
+            chars.next();
+            while let Some(&(index, next)) = chars.peek() {
+                if !(next.is_ascii_digit() || next == '.') {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+            let text = &source[offset..end];
+            let number = text.parse().map_err(|_| error_at(source, offset, &format!("invalid number '{}'", text)))?;
+            TokenKind::Number(number)
+        } else if ch.is_ascii_alphabetic() || ch == '_' {
+            let mut end = offset;
+            while let Some(&(index, next)) = chars.peek() {
+                if !(next.is_ascii_alphanumeric() || next == '_') {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+            TokenKind::Identifier(source[offset..end].to_string())
+        } else if let Some(operator) = OPERATORS.iter().find(|operator| source[offset..].starts_with(**operator)) {
+            for _ in 0..operator.len() {
+                chars.next();
+            }
+            TokenKind::Operator(operator)
+        } else if ch == '=' {
+            return Err(error_at(source, offset, "unexpected '=' (use '==' to compare)"));
+        } else {
+            return Err(error_at(source, offset, &format!("unexpected character '{}'", ch)));
+        };
+
+        tokens.push(Token { kind, offset });
+    }
+
+    Ok(tokens)
+}
+
+fn error_at(source: &str, offset: usize, message: &str) -> TfdiffError {
+    let column = source[..offset.min(source.len())].chars().count() + 1;
+    TfdiffError::InvalidQuery(format!("{} at column {} of `{}`", message, column, source))
+}
+
+/// How deeply parentheses and `not` may nest, so a hostile expression cannot
+/// overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+    /// Parentheses and `not`s open around the current token
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn error_at(&self, offset: usize, message: &str) -> TfdiffError {
+        error_at(self.source, offset, message)
+    }
+
+    fn end_error(&self, message: &str) -> TfdiffError {
+        self.error_at(self.source.len(), message)
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token { kind: TokenKind::Identifier(name), .. }) if name == keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn parse_or(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_and()?];
+        while self.keyword("or") {
+            nodes.push(self.parse_and()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::Or(nodes) })
+    }
+
+    fn parse_and(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_not()?];
+        while self.keyword("and") {
+            nodes.push(self.parse_not()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::And(nodes) })
+    }
+
+    /// Parse what `parse` parses one level deeper, failing past `MAX_DEPTH`.
+    fn nested<T>(&mut self, offset: usize, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error_at(offset, &format!("expression nests deeper than {} levels", MAX_DEPTH)));
+        }
+        self.depth += 1;
+        let node = parse(self);
+        self.depth -= 1;
+        node
+    }
+
+    fn parse_not(&mut self) -> Result<Node> {
+        let offset = self.peek().map(|token| token.offset).unwrap_or(self.source.len());
+        if self.keyword("not") {
+            return Ok(Node::Not(Box::new(self.nested(offset, Self::parse_not)?)));
+        }
+        self.parse_condition()
+    }
+
+    fn parse_condition(&mut self) -> Result<Node> {
+        if matches!(self.peek(), Some(Token { kind: TokenKind::OpenParen, .. })) {
+            let open = self.next().expect("peeked token");
+            let node = self.nested(open.offset, Self::parse_or)?;
+            return match self.next() {
+                Some(Token { kind: TokenKind::CloseParen, .. }) => Ok(node),
+                Some(token) => Err(self.error_at(token.offset, &format!("expected ')' but found {}", token.kind))),
+                None => Err(self.error_at(open.offset, "unclosed '('")),
+            };
+        }
+
+        let left = self.parse_operand()?;
+
+        if self.keyword("changed") {
+            return match left {
+                Operand::Attribute(Side::Planned, path) => Ok(Node::Changed(path)),
+                _ => Err(self.error_at_previous("'changed' only follows attr(\"path\")")),
+            };
+        }
+
+        let operator = match self.peek() {
+            Some(Token { kind: TokenKind::Operator(operator), .. }) => *operator,
+            _ => return Ok(Node::Truthy(left)),
+        };
+        let operator_offset = self.next().expect("peeked token").offset;
+
+        if operator == "~" || operator == "!~" {
+            return match self.next() {
+                Some(Token { kind: TokenKind::Str(pattern), offset }) => {
+                    let regex = Regex::new(&pattern).map_err(|e| {
+                        self.error_at(offset, &format!("invalid regular expression \"{}\": {}", pattern, regex_error_reason(&e)))
+                    })?;
+                    Ok(Node::Matches(left, regex, operator == "~"))
+                }
+                Some(token) => Err(self.error_at(token.offset, &format!("expected a quoted pattern after '{}' but found {}", operator, token.kind))),
+                None => Err(self.error_at(operator_offset, &format!("expected a quoted pattern after '{}'", operator))),
+            };
+        }
+
+        if self.peek().is_none() {
+            return Err(self.error_at(operator_offset, &format!("expected a value after '{}'", operator)));
+        }
+        let right = self.parse_operand()?;
+        let operator = match operator {
+            "==" => Operator::Equal,
+            "!=" => Operator::NotEqual,
+            "<" => Operator::Less,
+            "<=" => Operator::LessOrEqual,
+            ">" => Operator::Greater,
+            _ => Operator::GreaterOrEqual,
+        };
+        Ok(Node::Compare(left, operator, right))
+    }
+
+    /// Error pointing at the token just consumed.
+    fn error_at_previous(&self, message: &str) -> TfdiffError {
+        let offset = self.position.checked_sub(1).and_then(|index| self.tokens.get(index)).map_or(0, |token| token.offset);
+        self.error_at(offset, message)
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand> {
+        let Some(token) = self.next() else {
+            return Err(self.end_error("expected a field, attr(\"path\") or a value"));
+        };
+
+        match token.kind {
+            TokenKind::Str(text) => Ok(Operand::Literal(Value::from(text))),
+            TokenKind::Number(number) => Ok(Operand::Literal(Value::from(number))),
+            TokenKind::Identifier(name) => match name.as_str() {
+                "true" => Ok(Operand::Literal(Value::Bool(true))),
+                "false" => Ok(Operand::Literal(Value::Bool(false))),
+                "null" => Ok(Operand::Literal(Value::Null)),
+                function if FUNCTIONS.contains(&function) => {
+                    let side = match function {
+                        "before" => Side::Before,
+                        "after" => Side::After,
+                        _ => Side::Planned,
+                    };
+                    Ok(Operand::Attribute(side, self.parse_path_argument(function, token.offset)?))
+                }
+                field => match FIELDS.iter().find(|known| **known == field) {
+                    Some(known) => Ok(Operand::Field(known)),
+                    None => Err(self.error_at(token.offset, &format!(
+                        "unknown field '{}' (expected {} or {}(\"path\"))", field, FIELDS.join(", "), FUNCTIONS.join("/")
+                    ))),
+                },
+            },
+            other => Err(self.error_at(token.offset, &format!("expected a field, attr(\"path\") or a value but found {}", other))),
+        }
+    }
+
+    fn parse_path_argument(&mut self, function: &str, offset: usize) -> Result<String> {
+        let usage = format!("expected {}(\"path\")", function);
+        match (self.next(), self.next(), self.next()) {
+            (
+                Some(Token { kind: TokenKind::OpenParen, .. }),
+                Some(Token { kind: TokenKind::Str(path), .. }),
+                Some(Token { kind: TokenKind::CloseParen, .. }),
+            ) => Ok(path),
+            _ => Err(self.error_at(offset, &usage)),
+        }
+    }
+}
+
+/// The reason part of a regex syntax error, which the regex crate spreads over several lines.
+pub(crate) fn regex_error_reason(error: &regex::Error) -> String {
+    error.to_string().lines().last().unwrap_or_default().trim_start_matches("error: ").to_string()
+}
@@ -0,0 +1,130 @@
+pub mod expression;
+
+pub use expression::*;
+
+use crate::{ActionType, Resource, Summary, TerraformPlan};
+use wildmatch::WildMatch;
+
+/// How the criteria of a `ResourceQuery` combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// A resource must meet every criterion that is set
+    #[default]
+    All,
+    /// A resource must meet at least one criterion that is set
+    Any,
+}
+
+/// Selects resources from a plan.
+///
+/// Each criterion is a list of alternatives, any of which may match; patterns
+/// may use `*` and `?` wildcards. Criteria left empty are ignored, and resources
+/// matching `exclude` are dropped whatever else they match.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceQuery {
+    pub actions: Vec<ActionType>,
+    /// Resource types, e.g. `aws_s3_bucket` or `aws_iam_*`
+    pub resource_types: Vec<String>,
+    /// Full addresses, e.g. `module.network.*`
+    pub addresses: Vec<String>,
+    /// Module paths, with or without the `module.` prefix; `root` is the root module.
+    /// Resources in nested modules match their parents.
+    pub modules: Vec<String>,
+    /// Provider names; `aws` also matches aliased configurations such as `aws.west`
+    pub providers: Vec<String>,
+    /// Attribute paths the resource changes, e.g. `tags.Environment` or `tags`
+    pub changed_attributes: Vec<String>,
+    /// Addresses or resource types to leave out
+    pub exclude: Vec<String>,
+    /// A `--where` expression the resource must satisfy
+    pub expression: Option<Expression>,
+    pub match_mode: MatchMode,
+}
+
+impl ResourceQuery {
+    /// Whether the query selects every resource.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+            && self.resource_types.is_empty()
+            && self.addresses.is_empty()
+            && self.modules.is_empty()
+            && self.providers.is_empty()
+            && self.changed_attributes.is_empty()
+            && self.exclude.is_empty()
+            && self.expression.is_none()
+    }
+
+    pub fn matches(&self, resource: &Resource) -> bool {
+        let excluded = self.exclude.iter()
+            .any(|pattern| glob(pattern, &resource.id) || glob(pattern, &resource.type_name));
+        if excluded {
+            return false;
+        }
+
+        let module = resource.module().unwrap_or_else(|| "root".to_string());
+        let criteria = [
+            criterion(&self.actions, |action| *action == resource.action),
+            criterion(&self.resource_types, |pattern| glob(pattern, &resource.type_name)),
+            criterion(&self.addresses, |pattern| glob(pattern, &resource.id)),
+            criterion(&self.modules, |pattern| module_matches(pattern, &module)),
+            criterion(&self.providers, |pattern| provider_matches(pattern, &resource.provider)),
+            criterion(&self.changed_attributes, |pattern| changed_paths(resource).iter().any(|path| path_matches(pattern, path))),
+            self.expression.as_ref().map(|expression| expression.matches(resource)),
+        ];
+        let mut set = criteria.into_iter().flatten().peekable();
+
+        match self.match_mode {
+            MatchMode::All => set.all(|matched| matched),
+            MatchMode::Any => set.peek().is_none() || set.any(|matched| matched),
+        }
+    }
+
+    /// Keep only the matching resources and recount the summary from what is left.
+    pub fn filter_plan(&self, plan: &mut TerraformPlan) {
+        if self.is_empty() {
+            return;
+        }
+
+        plan.resources.retain(|resource| self.matches(resource));
+
+        // Data sources are not filtered, so their count stays as reported
+        let read = plan.summary.read;
+        plan.summary = Summary { read, ..Summary::from_resources(&plan.resources) };
+        plan.parsed_summary = Summary::from_resources(&plan.resources);
+    }
+}
+
+/// `None` for a criterion that is not set, otherwise whether any alternative matches.
+fn criterion<T>(alternatives: &[T], matches: impl Fn(&T) -> bool) -> Option<bool> {
+    (!alternatives.is_empty()).then(|| alternatives.iter().any(matches))
+}
+
+pub(crate) fn glob(pattern: &str, text: &str) -> bool {
+    WildMatch::new(pattern).matches(text)
+}
+
+/// A dotted path matches a pattern naming it or any of its parents.
+pub(crate) fn path_matches(pattern: &str, path: &str) -> bool {
+    glob(pattern, path) || glob(&format!("{}.*", pattern), path)
+}
+
+fn module_matches(pattern: &str, module: &str) -> bool {
+    if pattern == "root" || pattern.starts_with("module.") {
+        path_matches(pattern, module)
+    } else {
+        path_matches(&format!("module.{}", pattern), module)
+    }
+}
+
+fn provider_matches(pattern: &str, provider: &str) -> bool {
+    let name = provider.split('.').next().unwrap_or(provider);
+    glob(pattern, provider) || glob(pattern, name)
+}
+
+/// Dotted paths of the attributes a resource changes.
+fn changed_paths(resource: &Resource) -> Vec<String> {
+    resource.changes.iter()
+        .map(|change| change.path.join("."))
+        .chain(resource.attributes.keys().cloned())
+        .collect()
+}
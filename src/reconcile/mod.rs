@@ -0,0 +1,130 @@
+use crate::{ActionType, PlanMode, Resource, Result, Summary, TerraformPlan, TfdiffError, WarningLevel};
+use serde::{Deserialize, Serialize};
+
+/// A planned resource the apply changed with a different action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionMismatch {
+    pub address: String,
+    pub planned: ActionType,
+    pub applied: ActionType,
+}
+
+/// A planned resource Terraform reported errors for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedResource {
+    pub address: String,
+    pub action: ActionType,
+    pub errors: Vec<String>,
+}
+
+/// How an apply log lines up with the plan that was reviewed before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reconciliation {
+    /// Counts of the resources the plan changes
+    pub planned_summary: Summary,
+    /// Counts of the resources the apply completed
+    pub applied_summary: Summary,
+    /// Planned resources applied with the planned action
+    pub matched: Vec<String>,
+    pub action_mismatches: Vec<ActionMismatch>,
+    pub failed: Vec<FailedResource>,
+    /// Planned resources the apply never completed, without an error naming them
+    pub not_applied: Vec<Resource>,
+    /// Resources the apply set out to change that the reviewed plan did not
+    pub unplanned: Vec<Resource>,
+    /// Errors that do not name a planned resource
+    pub errors: Vec<String>,
+}
+
+impl Reconciliation {
+    /// Whether the apply did exactly what was planned.
+    pub fn is_consistent(&self) -> bool {
+        self.action_mismatches.is_empty()
+            && self.failed.is_empty()
+            && self.not_applied.is_empty()
+            && self.unplanned.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+/// Check that `apply` (a parsed apply log) applied every resource in `plan` with
+/// the planned action and nothing else.
+pub fn reconcile(plan: &TerraformPlan, apply: &TerraformPlan) -> Result<Reconciliation> {
+    if apply.mode != PlanMode::Apply {
+        return Err(TfdiffError::ParseError(
+            "the apply log has no apply progress or \"Apply complete!\" line".to_string(),
+        ));
+    }
+
+    let completed: Vec<Resource> = apply.resources.iter().filter(|resource| resource.applied).cloned().collect();
+    let mut reconciliation = Reconciliation {
+        planned_summary: Summary::from_resources(&plan.resources),
+        // A failed apply never prints "Apply complete!", so count what finished
+        applied_summary: Summary::from_resources(&completed),
+        matched: Vec::new(),
+        action_mismatches: Vec::new(),
+        failed: Vec::new(),
+        not_applied: Vec::new(),
+        unplanned: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let errors_for = |address: &str| -> Vec<String> {
+        apply.warnings.iter()
+            .filter(|warning| warning.level == WarningLevel::Error && warning.address.as_deref() == Some(address))
+            .map(|warning| warning.message.clone())
+            .collect()
+    };
+
+    for planned in plan.resources.iter().filter(|resource| changes_infrastructure(resource)) {
+        let applied = apply.resources.iter().find(|resource| resource.id == planned.id);
+
+        match applied {
+            Some(applied) if applied.applied => {
+                if applied.action == planned.action {
+                    reconciliation.matched.push(planned.id.clone());
+                } else {
+                    reconciliation.action_mismatches.push(ActionMismatch {
+                        address: planned.id.clone(),
+                        planned: planned.action.clone(),
+                        applied: applied.action.clone(),
+                    });
+                }
+            }
+            _ => {
+                let errors = errors_for(&planned.id);
+                if errors.is_empty() {
+                    reconciliation.not_applied.push(planned.clone());
+                } else {
+                    reconciliation.failed.push(FailedResource {
+                        address: planned.id.clone(),
+                        action: planned.action.clone(),
+                        errors,
+                    });
+                }
+            }
+        }
+    }
+
+    reconciliation.unplanned = apply.resources.iter()
+        .filter(|resource| changes_infrastructure(resource))
+        .filter(|resource| !plan.resources.iter().any(|planned| planned.id == resource.id))
+        .cloned()
+        .collect();
+
+    let is_planned = |address: &str| plan.resources.iter().any(|planned| planned.id == address);
+    reconciliation.errors = apply.warnings.iter()
+        .filter(|warning| warning.level == WarningLevel::Error)
+        .filter_map(|warning| match warning.address.as_deref() {
+            Some(address) if is_planned(address) => None,
+            Some(address) => Some(format!("{}: {}", address, warning.message)),
+            None => Some(warning.message.clone()),
+        })
+        .collect();
+
+    Ok(reconciliation)
+}
+
+fn changes_infrastructure(resource: &Resource) -> bool {
+    !matches!(resource.action, ActionType::Read | ActionType::NoOp)
+}
@@ -0,0 +1,99 @@
+use crate::config::compile_pattern;
+use crate::query::path_matches;
+use crate::{Result, TerraformPlan};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// What replaces a redacted value.
+pub const REDACTED: &str = "(redacted)";
+
+/// Values to mask before a plan is shown or shared.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedactionRules {
+    /// Attribute paths whose values are replaced, e.g. `*password*` or `tags.Owner`
+    pub attributes: Vec<String>,
+    /// Regular expressions for secrets inside any string value, e.g. `AKIA[0-9A-Z]{16}`
+    pub values: Vec<String>,
+}
+
+impl RedactionRules {
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.values.is_empty()
+    }
+
+    /// Check that every value pattern is a valid regular expression.
+    pub fn validate(&self) -> Result<()> {
+        self.value_patterns().map(|_| ())
+    }
+
+    fn value_patterns(&self) -> Result<Vec<Regex>> {
+        self.values.iter()
+            .map(|pattern| compile_pattern("redaction pattern", pattern))
+            .collect()
+    }
+
+    /// Replace the values of matching attributes, and matching text in any value.
+    pub fn apply(&self, plan: &mut TerraformPlan) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let patterns = self.value_patterns()?;
+        let redacts_path = |path: &str| self.attributes.iter().any(|pattern| path_matches(pattern, path));
+
+        for resource in &mut plan.resources {
+            for change in &mut resource.changes {
+                let path_redacted = redacts_path(&change.path.join("."));
+                for value in [&mut change.before, &mut change.after].into_iter().flatten() {
+                    if path_redacted {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        redact_text(value, &patterns);
+                    }
+                }
+            }
+            redact_attributes(&mut resource.attributes, &redacts_path, &patterns);
+        }
+        for data_source in &mut plan.data_sources {
+            redact_attributes(&mut data_source.attributes, &redacts_path, &patterns);
+        }
+        for warning in &mut plan.warnings {
+            for pattern in &patterns {
+                warning.message = pattern.replace_all(&warning.message, REDACTED).into_owned();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn redact_attributes(attributes: &mut HashMap<String, Value>, redacts_path: &impl Fn(&str) -> bool, patterns: &[Regex]) {
+    for (key, value) in attributes.iter_mut() {
+        if !redacts_path(key) {
+            redact_text(value, patterns);
+            continue;
+        }
+        // Text plans keep the change marker in front of the value, e.g. `+ "secret"`
+        let marker = value.as_str()
+            .and_then(|text| ["+ ", "- ", "~ "].into_iter().find(|marker| text.starts_with(marker)))
+            .unwrap_or_default();
+        *value = Value::String(format!("{}{}", marker, REDACTED));
+    }
+}
+
+fn redact_text(value: &mut Value, patterns: &[Regex]) {
+    match value {
+        Value::String(text) => {
+            for pattern in patterns {
+                if pattern.is_match(text) {
+                    *text = pattern.replace_all(text, REDACTED).into_owned();
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_text(item, patterns)),
+        Value::Object(fields) => fields.values_mut().for_each(|field| redact_text(field, patterns)),
+        _ => {}
+    }
+}
@@ -0,0 +1,198 @@
+use crate::query::glob;
+use crate::{ActionType, Resource, ResourceAnnotation, ResourceClass, RiskLevel, TerraformPlan};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Kinds of resources whose changes deserve a closer look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskCategory {
+    Database,
+    Iam,
+    Networking,
+    Dns,
+    Kms,
+}
+
+impl std::fmt::Display for RiskCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            RiskCategory::Database => "database",
+            RiskCategory::Iam => "IAM",
+            RiskCategory::Networking => "networking",
+            RiskCategory::Dns => "DNS",
+            RiskCategory::Kms => "KMS",
+        };
+        f.write_str(label)
+    }
+}
+
+// What kind of resource a type is. The resource catalog instead classes types
+// by what changing them may cause, so the two overlap without matching: a
+// bucket is stateful but not a database, and a DNS record is network-critical
+// but scored as DNS. Types outside these categories are scored by their
+// catalog notes, which also picks up types added under `[catalog.*]`.
+//
+// Checked in order, so `aws_route53_*` is DNS before `aws_route*` is networking
+const CATEGORIES: &[(RiskCategory, &[&str])] = &[
+    (RiskCategory::Dns, &["aws_route53_*", "google_dns_*", "azurerm_dns_*", "azurerm_private_dns_*", "cloudflare_record"]),
+    (RiskCategory::Kms, &["aws_kms_*", "google_kms_*", "azurerm_key_vault*"]),
+    (RiskCategory::Iam, &[
+        "aws_iam_*", "google_*_iam_*", "google_service_account*", "azurerm_role_*", "azuread_*",
+        "kubernetes_role*", "kubernetes_cluster_role*", "kubernetes_service_account*",
+    ]),
+    (RiskCategory::Database, &[
+        "aws_db_*", "aws_rds_*", "aws_dynamodb_table", "aws_elasticache_*", "aws_redshift_*", "aws_docdb_*", "aws_neptune_*",
+        "google_sql_*", "google_spanner_*", "google_bigtable_*", "google_firestore_*",
+        "azurerm_*sql*", "azurerm_cosmosdb_*", "azurerm_redis_*",
+    ]),
+    (RiskCategory::Networking, &[
+        "aws_vpc*", "aws_subnet", "aws_route*", "aws_security_group*", "aws_network_acl*", "aws_nat_gateway",
+        "aws_internet_gateway", "aws_lb*", "aws_alb*", "aws_ec2_transit_gateway*",
+        "google_compute_network", "google_compute_subnetwork", "google_compute_firewall", "google_compute_router*",
+        "azurerm_virtual_network*", "azurerm_subnet*", "azurerm_network_security_*", "azurerm_lb*",
+        "kubernetes_network_policy*", "kubernetes_ingress*",
+    ]),
+];
+
+/// How risky one resource change is, on a scale of 0 to 100.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceRisk {
+    pub address: String,
+    pub score: u32,
+    pub level: RiskLevel,
+    /// What contributed to the score, e.g. `destroy`, `database` or `2 attributes force replacement`
+    pub factors: Vec<String>,
+}
+
+/// The risk of a whole plan: its riskiest change, raised a little for every other high-risk change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanRisk {
+    pub score: u32,
+    pub level: RiskLevel,
+    /// One entry per resource, in plan order
+    pub resources: Vec<ResourceRisk>,
+}
+
+impl PlanRisk {
+    pub fn resource(&self, address: &str) -> Option<&ResourceRisk> {
+        self.resources.iter().find(|risk| risk.address == address)
+    }
+}
+
+/// The category of a resource type, if it is one of the risky kinds.
+pub fn risk_category(type_name: &str) -> Option<RiskCategory> {
+    CATEGORIES.iter()
+        .find(|(_, patterns)| patterns.iter().any(|pattern| glob(pattern, type_name)))
+        .map(|(category, _)| *category)
+}
+
+/// Map a score to a level: 60 and above is high, 30 and above medium.
+pub fn risk_level_for_score(score: u32) -> RiskLevel {
+    match score {
+        0 => RiskLevel::None,
+        1..=29 => RiskLevel::Low,
+        30..=59 => RiskLevel::Medium,
+        _ => RiskLevel::High,
+    }
+}
+
+/// Score one resource; `module_changes` is how many resources change in its module, itself included.
+pub fn assess_resource(resource: &Resource, module_changes: usize) -> ResourceRisk {
+    assess_resource_with_annotations(resource, &[], module_changes)
+}
+
+/// Score one resource, weighing its catalog notes when its type is in no risk category.
+pub fn assess_resource_with_annotations(resource: &Resource, annotations: &[&ResourceAnnotation], module_changes: usize) -> ResourceRisk {
+    let mut factors = Vec::new();
+    let mut score = match resource.action {
+        ActionType::Update if resource.replace => 60,
+        ActionType::Destroy => 60,
+        ActionType::Update => 30,
+        ActionType::Create => 10,
+        ActionType::Read | ActionType::NoOp => 0,
+    };
+    if score == 0 {
+        return ResourceRisk { address: resource.id.clone(), score, level: RiskLevel::None, factors };
+    }
+    factors.push(match resource.action {
+        ActionType::Update if resource.replace => "replace",
+        ActionType::Update => "update",
+        ActionType::Destroy => "destroy",
+        _ => "create",
+    }.to_string());
+
+    let kind = match risk_category(&resource.type_name) {
+        Some(category) => Some((category_weight(category), category.to_string())),
+        None => annotations.iter()
+            .filter_map(|annotation| Some((class_weight(annotation.class)?, annotation.class.to_string())))
+            .max_by_key(|(weight, _)| *weight),
+    };
+    if let Some((weight, label)) = kind {
+        score += weight;
+        factors.push(label);
+    }
+
+    let forcing = resource.changes.iter().filter(|change| change.forces_replacement).count();
+    if forcing > 0 {
+        score += (5 * forcing as u32).min(15);
+        factors.push(format!("{} attribute{} force{} replacement",
+            forcing, if forcing == 1 { "" } else { "s" }, if forcing == 1 { "s" } else { "" }));
+    }
+
+    let others = module_changes.saturating_sub(1);
+    if others > 0 {
+        score += (2 * others as u32).min(10);
+        factors.push(format!("{} other change{} in {}",
+            others, if others == 1 { "" } else { "s" }, resource.module().unwrap_or_else(|| "the root module".to_string())));
+    }
+
+    let score = score.min(100);
+    ResourceRisk { address: resource.id.clone(), score, level: risk_level_for_score(score), factors }
+}
+
+fn category_weight(category: RiskCategory) -> u32 {
+    match category {
+        RiskCategory::Database | RiskCategory::Kms => 25,
+        RiskCategory::Iam => 20,
+        RiskCategory::Networking | RiskCategory::Dns => 15,
+    }
+}
+
+/// The weight of a catalog note, matching the categories it is closest to.
+fn class_weight(class: ResourceClass) -> Option<u32> {
+    match class {
+        ResourceClass::Stateful => Some(25),
+        ResourceClass::SecuritySensitive => Some(20),
+        ResourceClass::NetworkCritical => Some(15),
+        ResourceClass::CheapToRecreate => None,
+    }
+}
+
+/// Score every resource in the plan and the plan as a whole.
+pub fn assess_plan(plan: &TerraformPlan) -> PlanRisk {
+    let mut module_changes: HashMap<Option<String>, usize> = HashMap::new();
+    for resource in plan.resources.iter().filter(|resource| is_change(resource)) {
+        *module_changes.entry(resource.module()).or_default() += 1;
+    }
+
+    let resources: Vec<ResourceRisk> = plan.resources.iter()
+        .map(|resource| {
+            let changes = module_changes.get(&resource.module()).copied().unwrap_or(0);
+            let annotations: Vec<&ResourceAnnotation> = plan.annotations.iter()
+                .filter(|annotation| annotation.address == resource.id)
+                .collect();
+            assess_resource_with_annotations(resource, &annotations, changes)
+        })
+        .collect();
+
+    let highest = resources.iter().map(|risk| risk.score).max().unwrap_or(0);
+    let other_high = resources.iter().filter(|risk| risk.level == RiskLevel::High).count().saturating_sub(1);
+    let score = (highest + (2 * other_high as u32).min(10)).min(100);
+
+    PlanRisk { score, level: risk_level_for_score(score), resources }
+}
+
+fn is_change(resource: &Resource) -> bool {
+    matches!(resource.action, ActionType::Create | ActionType::Update | ActionType::Destroy)
+}
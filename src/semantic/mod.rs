@@ -0,0 +1,111 @@
+use crate::parser::diff::parse_terraform_value;
+use crate::Change;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Whether a change alters what Terraform will configure, or only how the value is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeClass {
+    Semantic,
+    Cosmetic(CosmeticReason),
+}
+
+impl ChangeClass {
+    pub fn is_cosmetic(&self) -> bool {
+        matches!(self, ChangeClass::Cosmetic(_))
+    }
+}
+
+/// Why a change is only cosmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CosmeticReason {
+    /// A JSON document (e.g. an IAM policy) with reordered keys or different formatting
+    EquivalentJson,
+    /// The same value written as a different type, e.g. `"1"` and `1`
+    EquivalentValue,
+    /// A resource's own list of plain values holding the same elements in a different order
+    ReorderedElements,
+}
+
+impl std::fmt::Display for CosmeticReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            CosmeticReason::EquivalentJson => "equivalent JSON",
+            CosmeticReason::EquivalentValue => "equivalent value",
+            CosmeticReason::ReorderedElements => "reordered elements",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Classify a change by comparing its values after decoding JSON strings and
+/// numbers or booleans written as strings, then after sorting lists.
+///
+/// Only a top-level attribute holding a list of plain values, where sets are
+/// common, counts as reordered. Lists in nested blocks, lists of blocks and
+/// lists in JSON documents are often evaluated in order (container `command`,
+/// IAM statements), so reordering them is semantic.
+///
+/// Additions, removals, and unknown or sensitive values are always semantic.
+pub fn classify_change(change: &Change) -> ChangeClass {
+    let (Some(before), Some(after)) = (&change.before, &change.after) else {
+        return ChangeClass::Semantic;
+    };
+    if change.computed || change.sensitive || before == after {
+        return ChangeClass::Semantic;
+    }
+
+    let (before_decoded, after_decoded) = (decode(before), decode(after));
+    if before_decoded == after_decoded {
+        let reason = if is_document(before) || is_document(after) {
+            CosmeticReason::EquivalentJson
+        } else {
+            CosmeticReason::EquivalentValue
+        };
+        return ChangeClass::Cosmetic(reason);
+    }
+    let top_level = change.path.len() == 1 && !is_document(before) && !is_document(after);
+    if top_level && is_reordered(&before_decoded, &after_decoded) {
+        return ChangeClass::Cosmetic(CosmeticReason::ReorderedElements);
+    }
+
+    ChangeClass::Semantic
+}
+
+/// Decode strings holding JSON, numbers, booleans or null, at any depth.
+fn decode(value: &Value) -> Value {
+    match value {
+        Value::String(text) => match parse_terraform_value(text) {
+            // Keep the original text; the parser would strip quotes and whitespace
+            Value::String(_) => value.clone(),
+            decoded => decode(&decoded),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(decode).collect()),
+        Value::Object(fields) => Value::Object(fields.iter().map(|(key, value)| (key.clone(), decode(value))).collect()),
+        other => other.clone(),
+    }
+}
+
+/// A string holding a JSON object or array.
+fn is_document(value: &Value) -> bool {
+    matches!(value, Value::String(text) if matches!(parse_terraform_value(text), Value::Object(_) | Value::Array(_)))
+}
+
+/// Two lists of plain values with the same elements in a different order.
+fn is_reordered(before: &Value, after: &Value) -> bool {
+    let (Value::Array(before), Value::Array(after)) = (before, after) else {
+        return false;
+    };
+    let plain = |items: &[Value]| items.iter().all(|item| !matches!(item, Value::Array(_) | Value::Object(_)));
+    if !plain(before) || !plain(after) {
+        return false;
+    }
+    let sorted = |items: &[Value]| {
+        let mut elements: Vec<String> = items.iter().map(Value::to_string).collect();
+        elements.sort();
+        elements
+    };
+    sorted(before) == sorted(after)
+}
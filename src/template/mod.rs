@@ -0,0 +1,134 @@
+use crate::risk::assess_plan;
+use crate::{Result, TerraformPlan, TfdiffError};
+use handlebars::Handlebars;
+use serde_json::Value;
+use std::path::Path;
+
+/// Name the template is registered under.
+const TEMPLATE_NAME: &str = "report";
+
+/// A user-supplied report layout in Handlebars syntax, rendered against the
+/// serialized plan:
+///
+/// ```handlebars
+/// Change ticket: {{summary.add}} to add, {{summary.change}} to change
+/// {{#each resources}}
+/// {{action_icon this}} {{id}} will be {{action this}}
+/// {{diff this}}
+/// {{/each}}
+/// ```
+///
+/// Besides the fields of `TerraformPlan`, the template sees `risk`, the
+/// plan's risk assessment. Helpers:
+///
+/// - `action_icon resource` — an icon for the resource's action, e.g. `➕`
+/// - `action resource` — what happens to it, e.g. `replaced`
+/// - `value value max=N` — a value on one line, cut to `N` characters if set
+/// - `diff resource` — its changed attributes as `+`, `-` and `~` lines
+/// - `money amount currency="USD"` — an amount such as `$1,234.50`
+/// - `json value pretty=true` — any value as JSON
+/// - `join list ", "` — list items joined by a separator
+pub struct PlanTemplate {
+    registry: Handlebars<'static>,
+}
+
+impl PlanTemplate {
+    /// Compile a template; `html` escapes the values it inserts for HTML output.
+    pub fn new(source: &str, html: bool) -> Result<Self> {
+        let mut registry = Handlebars::new();
+        if !html {
+            registry.register_escape_fn(handlebars::no_escape);
+        }
+        helpers::register(&mut registry);
+        registry.register_template_string(TEMPLATE_NAME, source)
+            .map_err(|e| TfdiffError::TemplateError(e.to_string()))?;
+        Ok(PlanTemplate { registry })
+    }
+
+    /// Load a template file. Values are HTML-escaped in `.html`, `.htm` and `.xml` files.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        let html = path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| ["html", "htm", "xml"].contains(&extension.to_ascii_lowercase().as_str()));
+        Self::new(&source, html).map_err(|e| match e {
+            TfdiffError::TemplateError(message) => TfdiffError::TemplateError(format!("{}: {}", path.display(), message)),
+            other => other,
+        })
+    }
+
+    pub fn render(&self, plan: &TerraformPlan) -> Result<String> {
+        self.registry.render(TEMPLATE_NAME, &template_context(plan)?)
+            .map_err(|e| TfdiffError::TemplateError(e.to_string()))
+    }
+}
+
+/// The data a template is rendered against: the plan, plus its risk assessment as `risk`.
+pub fn template_context(plan: &TerraformPlan) -> Result<Value> {
+    let mut context = serde_json::to_value(plan)?;
+    context["risk"] = serde_json::to_value(assess_plan(plan))?;
+    Ok(context)
+}
+
+mod helpers {
+    use crate::formatter::chat::action_emoji;
+    use crate::formatter::format_money;
+    use crate::formatter::pr_comment::diff_lines;
+    use crate::{ActionType, Resource};
+    use handlebars::{handlebars_helper, Handlebars};
+    use serde_json::Value;
+
+    pub(super) fn register(registry: &mut Handlebars) {
+        registry.register_helper("action_icon", Box::new(ActionIconHelper));
+        registry.register_helper("action", Box::new(ActionHelper));
+        registry.register_helper("value", Box::new(FormatValueHelper));
+        registry.register_helper("diff", Box::new(DiffHelper));
+        registry.register_helper("money", Box::new(MoneyHelper));
+        registry.register_helper("json", Box::new(JsonHelper));
+        registry.register_helper("join", Box::new(JoinHelper));
+    }
+
+    /// A resource, or a resource with just an action such as `"Create"` or `"no-op"`.
+    fn resource(value: &Value) -> Option<Resource> {
+        if let Some(name) = value.as_str() {
+            let action = serde_json::from_value::<ActionType>(value.clone()).ok()
+                .or_else(|| name.parse().ok())?;
+            return Some(Resource {
+                id: String::new(),
+                name: String::new(),
+                type_name: String::new(),
+                provider: String::new(),
+                action,
+                changes: Vec::new(),
+                attributes: Default::default(),
+                applied: false,
+                replace: false,
+                span: None,
+            });
+        }
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    fn display(value: &Value, max: u64) -> String {
+        let text = match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        match text.char_indices().nth(max as usize).filter(|_| max > 0) {
+            Some((cut, _)) => format!("{}…", &text[..cut]),
+            None => text,
+        }
+    }
+
+    handlebars_helper!(ActionIconHelper: |value: Json| resource(value).map(|resource| action_emoji(&resource)).unwrap_or_default());
+    handlebars_helper!(ActionHelper: |value: Json| resource(value).map(|resource| resource.action_description()).unwrap_or_default());
+    handlebars_helper!(FormatValueHelper: |value: Json, { max: u64 = 0 }| display(value, max));
+    handlebars_helper!(DiffHelper: |value: Json| resource(value).map(|resource| diff_lines(&resource).join("\n")).unwrap_or_default());
+    handlebars_helper!(MoneyHelper: |amount: f64, { currency: str = "USD" }| format_money(amount, currency));
+    handlebars_helper!(JsonHelper: |value: Json, { pretty: bool = false }| {
+        if pretty { serde_json::to_string_pretty(value) } else { serde_json::to_string(value) }.unwrap_or_default()
+    });
+    handlebars_helper!(JoinHelper: |list: array, separator: str| {
+        list.iter().map(|item| display(item, 0)).collect::<Vec<_>>().join(separator)
+    });
+}
@@ -0,0 +1,136 @@
+// Interactive behaviour for the tfdiff web UI: search, action filters,
+// collapsible resources, plan uploads and live reload.
+(function () {
+    const sections = Array.from(document.querySelectorAll('.resource-section'));
+    const actions = ['create', 'update', 'destroy', 'read'];
+
+    function actionOf(section) {
+        const badge = section.querySelector('.action-badge');
+        const text = badge ? badge.textContent.trim().toLowerCase() : '';
+        return actions.find((action) => text.startsWith(action)) || text;
+    }
+
+    function buildToolbar() {
+        const toolbar = document.createElement('div');
+        toolbar.className = 'toolbar';
+
+        const search = document.createElement('input');
+        search.type = 'search';
+        search.placeholder = 'Filter resources by address...';
+        toolbar.appendChild(search);
+
+        const toggles = {};
+        actions.forEach((action) => {
+            const label = document.createElement('label');
+            const checkbox = document.createElement('input');
+            checkbox.type = 'checkbox';
+            checkbox.checked = true;
+            label.appendChild(checkbox);
+            label.appendChild(document.createTextNode(' ' + action));
+            toolbar.appendChild(label);
+            toggles[action] = checkbox;
+        });
+
+        const count = document.createElement('span');
+        count.className = 'visible-count';
+        toolbar.appendChild(count);
+
+        return { toolbar, search, toggles, count };
+    }
+
+    function applyFilters(controls) {
+        const query = controls.search.value.trim().toLowerCase();
+        let visible = 0;
+
+        sections.forEach((section) => {
+            const name = section.querySelector('.resource-name');
+            const address = name ? name.textContent.toLowerCase() : '';
+            const toggle = controls.toggles[actionOf(section)];
+            const show = address.includes(query) && (!toggle || toggle.checked);
+            section.classList.toggle('hidden', !show);
+            if (show) {
+                visible += 1;
+            }
+        });
+
+        // Hide group headers whose resources are all filtered out
+        document.querySelectorAll('.group-header').forEach((header) => {
+            let next = header.nextElementSibling;
+            let anyVisible = false;
+            while (next && next.classList.contains('resource-section')) {
+                anyVisible = anyVisible || !next.classList.contains('hidden');
+                next = next.nextElementSibling;
+            }
+            header.classList.toggle('hidden', !anyVisible);
+        });
+
+        controls.count.textContent = visible + ' of ' + sections.length + ' resources';
+    }
+
+    function setupReport(container) {
+        const summary = document.querySelector('.summary-card');
+        const controls = buildToolbar();
+        container.insertBefore(controls.toolbar, summary ? summary.nextSibling : container.firstChild);
+
+        controls.search.addEventListener('input', () => applyFilters(controls));
+        Object.values(controls.toggles).forEach((checkbox) => {
+            checkbox.addEventListener('change', () => applyFilters(controls));
+        });
+
+        sections.forEach((section) => {
+            const header = section.querySelector('.resource-header');
+            if (header) {
+                header.addEventListener('click', () => section.classList.toggle('collapsed'));
+            }
+        });
+
+        // Keep the search across live reloads
+        controls.search.value = sessionStorage.getItem('tfdiff-search') || '';
+        controls.search.addEventListener('input', () => sessionStorage.setItem('tfdiff-search', controls.search.value));
+
+        applyFilters(controls);
+    }
+
+    function setupUpload(form) {
+        const status = document.getElementById('upload-status');
+        form.addEventListener('submit', (event) => {
+            event.preventDefault();
+            const name = form.elements.name.value.trim();
+            const url = '/api/plans' + (name ? '?name=' + encodeURIComponent(name) : '');
+            status.textContent = 'Uploading...';
+
+            fetch(url, { method: 'POST', body: form.elements.plan.value })
+                .then((response) => response.json().then((body) => ({ ok: response.ok, body })))
+                .then(({ ok, body }) => {
+                    if (ok) {
+                        window.location.reload();
+                    } else {
+                        status.textContent = body.error || 'Upload failed';
+                    }
+                })
+                .catch((error) => {
+                    status.textContent = 'Upload failed: ' + error;
+                });
+        });
+    }
+
+    document.addEventListener('DOMContentLoaded', function () {
+        const container = document.querySelector('.container');
+        if (container) {
+            setupReport(container);
+        }
+
+        const form = document.getElementById('upload-form');
+        if (form) {
+            setupUpload(form);
+        }
+
+        // Pages that follow server-side changes name the event they reload on:
+        // `plan` for the watched report, `plans` for the dashboard
+        const live = document.body.dataset.live;
+        if (live && window.EventSource) {
+            const events = new EventSource('/api/events');
+            events.addEventListener(live, () => window.location.reload());
+        }
+    });
+})();
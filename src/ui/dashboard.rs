@@ -0,0 +1,84 @@
+use crate::formatter::html::html_escape;
+use crate::ui::store::PlanListing;
+
+/// Render the dashboard listing uploaded plans, newest first.
+pub fn render_dashboard(listings: &[PlanListing], has_current_plan: bool) -> String {
+    let mut html = String::new();
+
+    html.push_str(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Terraform Plans - Dashboard</title>
+    <link rel="stylesheet" href="/assets/app.css">
+    <script src="/assets/app.js" defer></script>
+</head>
+<body class="dashboard-page" data-live="plans">
+    <div class="dashboard">
+        <h1>📋 Terraform Plans</h1>"#);
+
+    if has_current_plan {
+        html.push_str(r#"
+        <p class="panel"><a href="/">View the plan this server was started with →</a></p>"#);
+    }
+
+    html.push_str(r#"
+        <div class="panel">"#);
+
+    if listings.is_empty() {
+        html.push_str(r#"
+            <div class="empty-state">No plans uploaded yet. Paste one below or <code>curl --data-binary @plan.txt http://localhost:PORT/api/plans</code>.</div>"#);
+    } else {
+        html.push_str(r#"
+            <table class="plan-table">
+                <thead>
+                    <tr><th>#</th><th>Name</th><th>Uploaded</th><th>Add</th><th>Change</th><th>Destroy</th><th>Risk</th><th>Terraform</th></tr>
+                </thead>
+                <tbody>"#);
+
+        for listing in listings {
+            html.push_str(&format!(r#"
+                    <tr>
+                        <td>{id}</td>
+                        <td><a href="/plans/{id}">{name}</a></td>
+                        <td>{uploaded}</td>
+                        <td class="count-add">{add}</td>
+                        <td class="count-change">{change}</td>
+                        <td class="count-destroy">{destroy}</td>
+                        <td><span class="risk risk-{risk}">{risk} ({score})</span></td>
+                        <td>{version}</td>
+                    </tr>"#,
+                id = listing.id,
+                name = html_escape(&listing.name),
+                uploaded = listing.uploaded_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                add = listing.summary.add,
+                change = listing.summary.change,
+                destroy = listing.summary.destroy,
+                risk = listing.risk,
+                score = listing.risk_score,
+                version = listing.terraform_version.as_deref().map(html_escape).unwrap_or_else(|| "-".to_string()),
+            ));
+        }
+
+        html.push_str(r#"
+                </tbody>
+            </table>"#);
+    }
+
+    html.push_str(r#"
+        </div>
+        <form id="upload-form" class="panel">
+            <textarea name="plan" placeholder="Paste terraform plan output, a JSON plan or a -json log..."></textarea>
+            <div class="upload-row">
+                <input type="text" name="name" placeholder="Name (optional)">
+                <button type="submit">Upload plan</button>
+                <span id="upload-status"></span>
+            </div>
+        </form>
+    </div>
+</body>
+</html>"#);
+
+    html
+}
@@ -1,5 +1,11 @@
 pub mod terminal;
 pub mod web;
+pub mod watch;
+pub mod store;
+pub mod dashboard;
 
 pub use terminal::*;
-pub use web::*;
\ No newline at end of file
+pub use web::*;
+pub use watch::*;
+pub use store::*;
+pub use dashboard::*;
\ No newline at end of file
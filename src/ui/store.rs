@@ -0,0 +1,114 @@
+use crate::risk::assess_plan;
+use crate::{RiskLevel, Result, Summary, TerraformPlan};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A plan uploaded to the web server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPlan {
+    pub id: u64,
+    pub name: String,
+    pub uploaded_at: DateTime<Utc>,
+    pub plan: TerraformPlan,
+}
+
+/// What the dashboard shows for each stored plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanListing {
+    pub id: u64,
+    pub name: String,
+    pub uploaded_at: DateTime<Utc>,
+    pub summary: Summary,
+    /// The plan's risk, as scored for the terminal and HTML reports
+    pub risk: RiskLevel,
+    pub risk_score: u32,
+    pub terraform_version: Option<String>,
+}
+
+impl From<&StoredPlan> for PlanListing {
+    fn from(stored: &StoredPlan) -> Self {
+        let risk = assess_plan(&stored.plan);
+        Self {
+            id: stored.id,
+            name: stored.name.clone(),
+            uploaded_at: stored.uploaded_at,
+            summary: stored.plan.summary.clone(),
+            risk: risk.level,
+            risk_score: risk.score,
+            terraform_version: stored.plan.metadata.terraform_version.clone(),
+        }
+    }
+}
+
+/// Uploaded plans, kept in memory and optionally persisted as one JSON file per plan.
+#[derive(Debug, Default)]
+pub struct PlanStore {
+    plans: Vec<StoredPlan>,
+    directory: Option<PathBuf>,
+}
+
+impl PlanStore {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open a store backed by `directory`, loading any plans saved there earlier.
+    pub fn on_disk(directory: &Path) -> Result<Self> {
+        fs::create_dir_all(directory)?;
+
+        let mut plans = Vec::new();
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            let is_plan_file = path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("plan-") && name.ends_with(".json"));
+            if is_plan_file {
+                plans.push(serde_json::from_str::<StoredPlan>(&fs::read_to_string(&path)?)?);
+            }
+        }
+        plans.sort_by_key(|stored| stored.id);
+
+        Ok(Self {
+            plans,
+            directory: Some(directory.to_path_buf()),
+        })
+    }
+
+    /// Add a plan, naming it after its id when no name is given.
+    pub fn insert(&mut self, name: Option<String>, plan: TerraformPlan) -> Result<&StoredPlan> {
+        let id = self.plans.last().map_or(1, |last| last.id + 1);
+        let stored = StoredPlan {
+            id,
+            name: name.filter(|name| !name.trim().is_empty()).unwrap_or_else(|| format!("Plan {}", id)),
+            uploaded_at: Utc::now(),
+            plan,
+        };
+
+        if let Some(directory) = &self.directory {
+            let path = directory.join(format!("plan-{}.json", id));
+            fs::write(path, serde_json::to_string(&stored)?)?;
+        }
+
+        self.plans.push(stored);
+        Ok(self.plans.last().expect("plan was just inserted"))
+    }
+
+    pub fn get(&self, id: u64) -> Option<&StoredPlan> {
+        self.plans.iter().find(|stored| stored.id == id)
+    }
+
+    /// Listings for every stored plan, newest first.
+    pub fn list(&self) -> Vec<PlanListing> {
+        self.plans.iter().rev().map(PlanListing::from).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.plans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+}
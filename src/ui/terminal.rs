@@ -1,8 +1,8 @@
-use crate::formatter::format_terminal_output;
+use crate::formatter::{format_terminal_output_with_width, terminal_width, GroupBy};
 use crate::TerraformPlan;
 
 pub fn display_terminal_ui(plan: &TerraformPlan) {
-    let output = format_terminal_output(plan);
+    let output = format_terminal_output_with_width(plan, GroupBy::None, terminal_width());
     println!("{}", output);
 }
 
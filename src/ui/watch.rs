@@ -0,0 +1,84 @@
+use crate::{Result, TfdiffError};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+// Writers like `tee` flush in bursts; wait for this much quiet before re-reading
+const SETTLE_TIME: Duration = Duration::from_millis(150);
+
+/// Watches an input file and yields its contents whenever they change.
+///
+/// The parent directory is watched rather than the file itself so that editors
+/// which replace the file on save keep working.
+pub struct InputWatcher {
+    path: PathBuf,
+    last_contents: String,
+    events: Receiver<notify::Result<Event>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl InputWatcher {
+    pub fn new(path: &Path) -> Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| TfdiffError::WatchError(e.to_string()))?;
+
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher.watch(directory, RecursiveMode::NonRecursive)
+            .map_err(|e| TfdiffError::WatchError(format!("failed to watch {}: {}", path.display(), e)))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            last_contents: fs::read_to_string(path).unwrap_or_default(),
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Block until the file's contents change and return the new contents.
+    pub fn next_change(&mut self) -> Result<String> {
+        loop {
+            self.wait_for_event()?;
+
+            // Drain the rest of the burst before reading
+            loop {
+                match self.events.recv_timeout(SETTLE_TIME) {
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+                }
+            }
+
+            // The file may briefly disappear while it is being replaced
+            let Ok(contents) = fs::read_to_string(&self.path) else { continue };
+            if contents != self.last_contents {
+                self.last_contents = contents.clone();
+                return Ok(contents);
+            }
+        }
+    }
+
+    fn wait_for_event(&self) -> Result<()> {
+        loop {
+            match self.events.recv() {
+                Ok(Ok(event)) if self.concerns_input(&event) => return Ok(()),
+                Ok(Ok(_)) => continue,
+                Ok(Err(e)) => return Err(TfdiffError::WatchError(e.to_string())),
+                Err(_) => return Err(disconnected()),
+            }
+        }
+    }
+
+    fn concerns_input(&self, event: &Event) -> bool {
+        event.paths.iter().any(|path| path.file_name() == self.path.file_name())
+    }
+}
+
+fn disconnected() -> TfdiffError {
+    TfdiffError::WatchError("file watcher stopped unexpectedly".to_string())
+}
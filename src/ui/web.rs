@@ -1,15 +1,257 @@
-use crate::{TerraformPlan, Result};
+use crate::formatter::{format_html_output_grouped, GroupBy};
+use crate::parser::parse_terraform_output;
+use crate::ui::dashboard::render_dashboard;
+use crate::ui::store::{PlanListing, PlanStore, StoredPlan};
+use crate::{Expression, Resource, ResourceQuery, Result, TerraformPlan, TfdiffError};
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::RwLock;
 
-pub async fn start_web_server(_plan: TerraformPlan, port: u16) -> Result<()> {
-    println!("🌐 Starting web server on port {}", port);
-    
-    // TODO: Implement full web server with axum
-    // This is a placeholder implementation
-    
-    Ok(())
+/// Static assets compiled into the binary, served under `/assets/`.
+const WEB_ASSETS: &[(&str, &str, &str)] = &[
+    ("app.js", "text/javascript; charset=utf-8", include_str!("assets/app.js")),
+    ("app.css", "text/css; charset=utf-8", include_str!("assets/app.css")),
+];
+
+// Large plans run to tens of megabytes; axum's default limit is 2 MB
+const UPLOAD_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Changes pushed to browsers over `/api/events`.
+#[derive(Debug, Clone, Copy)]
+enum WebEvent {
+    /// The plan the server was started with was re-read, carrying a revision number
+    PlanUpdated(u64),
+    /// A plan was uploaded, carrying its id
+    PlanUploaded(u64),
+}
+
+/// Shared state behind the web UI handlers.
+#[derive(Clone)]
+pub struct WebState {
+    /// The plan given on the command line, if any
+    pub plan: Arc<RwLock<Option<TerraformPlan>>>,
+    pub store: Arc<RwLock<PlanStore>>,
+    pub group_by: GroupBy,
+    revision: Arc<AtomicU64>,
+    events: broadcast::Sender<WebEvent>,
+}
+
+impl WebState {
+    pub fn new(plan: Option<TerraformPlan>, group_by: GroupBy, store: PlanStore) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            plan: Arc::new(RwLock::new(plan)),
+            store: Arc::new(RwLock::new(store)),
+            group_by,
+            revision: Arc::new(AtomicU64::new(0)),
+            events,
+        }
+    }
+
+    /// Replace the served plan and notify connected browsers.
+    pub async fn update_plan(&self, plan: TerraformPlan) {
+        *self.plan.write().await = Some(plan);
+        let revision = self.revision.fetch_add(1, Ordering::SeqCst) + 1;
+        // Nobody listening is fine; the next page load picks up the new plan
+        let _ = self.events.send(WebEvent::PlanUpdated(revision));
+    }
+
+    /// Parse and store an uploaded plan, notifying dashboards.
+    pub async fn upload_plan(&self, name: Option<String>, input: &str) -> Result<PlanListing> {
+        let plan = parse_terraform_output(input)?;
+        let listing = PlanListing::from(self.store.write().await.insert(name, plan)?);
+        let _ = self.events.send(WebEvent::PlanUploaded(listing.id));
+        Ok(listing)
+    }
+}
+
+/// Build the web UI router: the interactive report at `/`, the plan dashboard, the JSON API and embedded assets.
+pub fn web_router(state: WebState) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/dashboard", get(dashboard))
+        .route("/plans/:id", get(stored_plan_page))
+        .route("/api/plan", get(api_plan))
+        .route("/api/resources/:address", get(api_resource))
+        .route("/api/plans", get(api_list_plans).post(api_upload_plan))
+        .route("/api/plans/:id", get(api_stored_plan))
+        .route("/api/events", get(api_events))
+        .route("/assets/:name", get(asset))
+        .layer(DefaultBodyLimit::max(UPLOAD_LIMIT))
+        .with_state(state)
+}
+
+/// Serve the web UI on localhost until the process is stopped.
+pub async fn start_web_server(plan: TerraformPlan, port: u16) -> Result<()> {
+    start_web_server_with_state(WebState::new(Some(plan), GroupBy::None, PlanStore::in_memory()), port).await
+}
+
+/// Serve the web UI on localhost with state shared with the caller, e.g. a file watcher.
+pub async fn start_web_server_with_state(state: WebState, port: u16) -> Result<()> {
+    let address = SocketAddr::from(([127, 0, 0, 1], port));
+    let listener = TcpListener::bind(address).await
+        .map_err(|e| TfdiffError::WebError(format!("failed to bind {}: {}", address, e)))?;
+
+    println!("🌐 Serving Terraform diff at http://{}", address);
+    serve(listener, state).await
+}
+
+/// Serve the web UI on an already bound listener.
+pub async fn serve(listener: TcpListener, state: WebState) -> Result<()> {
+    axum::serve(listener, web_router(state)).await
+        .map_err(|e| TfdiffError::WebError(e.to_string()))
+}
+
+/// Look up an embedded asset by file name, returning its content type and body.
+pub fn web_asset(name: &str) -> Option<(&'static str, &'static str)> {
+    WEB_ASSETS.iter()
+        .find(|(asset_name, _, _)| *asset_name == name)
+        .map(|(_, content_type, body)| (*content_type, *body))
+}
+
+/// The HTML report with the web UI stylesheet and script linked in.
+///
+/// `live_event` names the `/api/events` event the page reloads on, if any.
+pub fn render_web_page(plan: &TerraformPlan, group_by: GroupBy, live_event: Option<&str>) -> String {
+    let report = format_html_output_grouped(plan, group_by);
+    let page = report.replacen("</head>", r#"    <link rel="stylesheet" href="/assets/app.css">
+    <script src="/assets/app.js" defer></script>
+</head>"#, 1);
+
+    match live_event {
+        Some(event) => page.replacen("<body>", &format!(r#"<body data-live="{}">"#, event), 1),
+        None => page,
+    }
+}
+
+async fn index(State(state): State<WebState>) -> Response {
+    match state.plan.read().await.as_ref() {
+        Some(plan) => Html(render_web_page(plan, state.group_by, Some("plan"))).into_response(),
+        // Started without a plan: the server is only a shared upload target
+        None => Redirect::to("/dashboard").into_response(),
+    }
+}
+
+async fn dashboard(State(state): State<WebState>) -> Html<String> {
+    let has_current_plan = state.plan.read().await.is_some();
+    Html(render_dashboard(&state.store.read().await.list(), has_current_plan))
+}
+
+async fn stored_plan_page(State(state): State<WebState>, Path(id): Path<u64>) -> Response {
+    match state.store.read().await.get(id) {
+        Some(stored) => Html(render_web_page(&stored.plan, state.group_by, None)).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, &format!("no plan with id {}", id)),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlanParams {
+    /// A `--where` expression selecting resources
+    #[serde(rename = "where")]
+    where_expression: Option<String>,
+}
+
+/// The plan narrowed to the resources matching `?where=`.
+fn select_resources(plan: &TerraformPlan, params: &PlanParams) -> Result<TerraformPlan> {
+    let mut plan = plan.clone();
+    if let Some(source) = &params.where_expression {
+        let expression = Expression::parse(source)?;
+        ResourceQuery { expression: Some(expression), ..Default::default() }.filter_plan(&mut plan);
+    }
+    Ok(plan)
+}
+
+async fn api_plan(State(state): State<WebState>, Query(params): Query<PlanParams>) -> Response {
+    match state.plan.read().await.as_ref() {
+        Some(plan) => match select_resources(plan, &params) {
+            Ok(plan) => Json(plan).into_response(),
+            Err(e) => error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+        },
+        None => error_response(StatusCode::NOT_FOUND, "no plan was given on the command line"),
+    }
+}
+
+async fn api_resource(State(state): State<WebState>, Path(address): Path<String>) -> std::result::Result<Json<Resource>, Response> {
+    let plan = state.plan.read().await;
+    plan.iter()
+        .flat_map(|plan| plan.resources.iter())
+        .find(|resource| resource.id == address)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, &format!("no resource with address {}", address)))
+}
+
+async fn api_list_plans(State(state): State<WebState>) -> Json<Vec<PlanListing>> {
+    Json(state.store.read().await.list())
 }
 
-pub fn generate_web_assets() -> String {
-    // TODO: Generate/serve static assets for web UI
-    "Web UI assets placeholder".to_string()
-}
\ No newline at end of file
+#[derive(Debug, Deserialize)]
+struct UploadParams {
+    name: Option<String>,
+}
+
+/// Accept a plan as text output, a JSON plan or a `-json` log in the request body.
+async fn api_upload_plan(State(state): State<WebState>, Query(params): Query<UploadParams>, body: String) -> Response {
+    if body.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "request body is empty");
+    }
+
+    match state.upload_plan(params.name, &body).await {
+        Ok(listing) => (StatusCode::CREATED, Json(listing)).into_response(),
+        Err(e @ TfdiffError::IoError(_)) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    }
+}
+
+async fn api_stored_plan(State(state): State<WebState>, Path(id): Path<u64>, Query(params): Query<PlanParams>) -> std::result::Result<Json<StoredPlan>, Response> {
+    let store = state.store.read().await;
+    let stored = store.get(id)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, &format!("no plan with id {}", id)))?;
+    Ok(Json(StoredPlan {
+        id: stored.id,
+        name: stored.name.clone(),
+        uploaded_at: stored.uploaded_at,
+        plan: select_resources(&stored.plan, &params)
+            .map_err(|e| error_response(StatusCode::BAD_REQUEST, &e.to_string()))?,
+    }))
+}
+
+/// Server-Sent Events stream: `plan` when the watched plan changes, `plans` when a plan is uploaded.
+async fn api_events(State(state): State<WebState>) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    let events = stream::unfold(state.events.subscribe(), |mut events| async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(WebEvent::PlanUpdated(revision)) => Event::default().event("plan").data(revision.to_string()),
+                Ok(WebEvent::PlanUploaded(id)) => Event::default().event("plans").data(id.to_string()),
+                // Only the latest state matters, so skipped events are fine
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), events));
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+async fn asset(Path(name): Path<String>) -> Response {
+    match web_asset(&name) {
+        Some((content_type, body)) => ([(header::CONTENT_TYPE, content_type)], body).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, &format!("no asset named {}", name)),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
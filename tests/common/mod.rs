@@ -72,7 +72,22 @@ pub mod assertions {
 
 pub mod test_data {
     use tfdiff::models::*;
+    use tfdiff::parser::parse_terraform_output;
+    use tfdiff::policy::PolicySet;
     use std::collections::HashMap;
+    use super::fixtures::*;
+    
+    /// The policy fixture plan, before any policies are evaluated.
+    pub fn guarded_plan() -> TerraformPlan {
+        parse_terraform_output(&load_fixture("policy/guarded_plan.txt")).unwrap()
+    }
+    
+    /// The policy fixture plan with `policy/policies.toml` evaluated under `labels`.
+    pub fn guarded_plan_with_policies(labels: &[String]) -> TerraformPlan {
+        let mut plan = guarded_plan();
+        plan.policy_results = PolicySet::load(&fixture_path("policy/policies.toml")).unwrap().evaluate(&plan, labels).unwrap();
+        plan
+    }
     
     pub fn sample_terraform_plan() -> TerraformPlan {
         TerraformPlan {
@@ -83,6 +98,7 @@ pub mod test_data {
                 destroy: 0,
                 read: 0,
             },
+            parsed_summary: Summary::default(),
             resources: vec![
                 sample_create_resource(),
                 sample_update_resource(),
@@ -94,6 +110,10 @@ pub mod test_data {
                 timestamp: Some("2024-01-01T12:00:00Z".to_string()),
                 duration: Some("5s".to_string()),
             },
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         }
     }
     
@@ -111,6 +131,8 @@ pub mod test_data {
             changes: Vec::new(),
             attributes,
             applied: false,
+            replace: false,
+            span: None,
         }
     }
     
@@ -131,10 +153,14 @@ pub mod test_data {
                     after: Some(serde_json::Value::String("t3.small".to_string())),
                     sensitive: false,
                     computed: false,
+                    forces_replacement: false,
+                    span: None,
                 }
             ],
             attributes,
             applied: false,
+            replace: false,
+            span: None,
         }
     }
     
@@ -151,6 +177,8 @@ pub mod test_data {
             changes: Vec::new(),
             attributes,
             applied: false,
+            replace: false,
+            span: None,
         }
     }
 }
\ No newline at end of file
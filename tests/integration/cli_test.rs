@@ -105,14 +105,18 @@ Plan: 1 to add, 0 to change, 0 to destroy.
         let plan_content = "Plan: 1 to add, 1 to change, 1 to destroy.";
         let file = create_temp_file_with_content(plan_content);
         
+        // Occupy the port so the server reports the bind failure instead of serving forever
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        
         let mut cmd = Command::cargo_bin("tfdiff").unwrap();
         cmd.arg(file.path())
             .arg("--web")
             .arg("--port")
-            .arg("9999")
+            .arg(port.to_string())
             .assert()
-            .success()
-            .stderr(predicate::str::contains("Web UI mode on port 9999"));
+            .failure()
+            .stderr(predicate::str::contains(format!("failed to bind 127.0.0.1:{}", port)));
     }
     
     #[test]
@@ -169,11 +173,16 @@ Plan: 1 to add, 0 to change, 0 to destroy.
         let plan_content = "Plan: 1 to add, 0 to change, 0 to destroy.";
         let file = create_temp_file_with_content(plan_content);
         
+        // Watch mode keeps running after the first report until it is stopped
         let mut cmd = Command::cargo_bin("tfdiff").unwrap();
         cmd.arg(file.path())
             .arg("--watch")
+            .arg("--summary")
+            .timeout(std::time::Duration::from_secs(2))
             .assert()
-            .success();
+            .interrupted()
+            .stdout(predicate::str::contains("Summary: 1 to add, 0 to change, 0 to destroy"))
+            .stderr(predicate::str::contains("Watching"));
     }
     
     #[test]
@@ -242,11 +251,20 @@ Plan: 1 to add, 0 to change, 0 to destroy.
         let file = create_temp_file_with_content(plan_content);
         
         let mut cmd = Command::cargo_bin("tfdiff").unwrap();
-        cmd.arg(file.path())
+        let output = cmd.arg(file.path())
             .arg("--browser")
             .assert()
             .success()
-            .stdout(predicate::str::contains("🌐 Opened Terraform diff in browser"));
+            .get_output()
+            .clone();
+        
+        // Without a browser opener (e.g. no xdg-open on CI) the page is still written out
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stdout.contains("🌐 Opened Terraform diff in browser") || stderr.contains("📄 HTML file saved to: "),
+            "stdout: {}\nstderr: {}", stdout, stderr
+        );
     }
     
     #[test]
@@ -258,4 +276,691 @@ Plan: 1 to add, 0 to change, 0 to destroy.
             .stdout(predicate::str::contains("--browser"))
             .stdout(predicate::str::contains("Generate HTML and open in browser"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_cli_explain_mode() {
+        let plan_content = r#"
+# aws_s3_bucket.test will be created
++ resource "aws_s3_bucket" "test" {
+    + bucket = "test-bucket"
+  }
+
+Plan: 1 to add, 0 to change, 0 to destroy.
+        "#;
+        let file = create_temp_file_with_content(plan_content);
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg(file.path())
+            .arg("--explain")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("lines 2-5"))
+            .stdout(predicate::str::contains("action: create"));
+    }
+    
+    #[test]
+    fn test_cli_group_by_module() {
+        let plan_content = r#"
+# module.vpc.aws_vpc.main will be created
++ resource "aws_vpc" "main" {
+    + cidr_block = "10.0.0.0/16"
+  }
+
+# aws_s3_bucket.logs will be created
++ resource "aws_s3_bucket" "logs" {
+    + bucket = "logs"
+  }
+
+Plan: 2 to add, 0 to change, 0 to destroy.
+        "#;
+        let file = create_temp_file_with_content(plan_content);
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg(file.path())
+            .arg("--format")
+            .arg("markdown")
+            .arg("--group-by")
+            .arg("module")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("### 📦 module.vpc (1)"))
+            .stdout(predicate::str::contains("### 📦 root module (1)"));
+    }
+    
+    #[test]
+    fn test_cli_json_includes_spans() {
+        let plan_content = r#"# aws_s3_bucket.test will be created
++ resource "aws_s3_bucket" "test" {
+    + bucket = "test-bucket"
+  }"#;
+        let file = create_temp_file_with_content(plan_content);
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg(file.path())
+            .arg("--format")
+            .arg("json")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("\"start_line\": 1"))
+            .stdout(predicate::str::contains("\"end_line\": 4"));
+    }
+    
+    #[test]
+    fn test_cli_strict_mode_rejects_truncated_plan() {
+        let plan_content = r#"
+# aws_s3_bucket.test will be created
++ resource "aws_s3_bucket" "test" {
+    + bucket = "test-bucket"
+"#;
+        let file = create_temp_file_with_content(plan_content);
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg(file.path())
+            .arg("--strict")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("resource block is missing 1 closing brace"))
+            .stderr(predicate::str::contains("may be truncated"));
+    }
+    
+    #[test]
+    fn test_cli_compare_mode() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/compare/previous_plan.txt")
+            .arg("tests/fixtures/compare/current_plan.txt")
+            .arg("--compare")
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("# Terraform Plan Comparison"))
+            .stdout(predicate::str::contains("- CREATE `aws_sqs_queue.jobs`"))
+            .stdout(predicate::str::contains("- DESTROY `aws_iam_role.old`"));
+    }
+    
+    #[test]
+    fn test_cli_compare_width() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/compare/previous_plan.txt")
+            .arg("tests/fixtures/compare/current_plan.txt")
+            .arg("--compare")
+            .arg("--width").arg("100")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("╔{}╗\n", "═".repeat(98))))
+            .stdout(predicate::str::contains(format!("\n{}\n", "─".repeat(100))));
+    }
+    
+    #[test]
+    fn test_cli_compare_requires_two_files() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/compare/previous_plan.txt")
+            .arg("--compare")
+            .assert()
+            .failure();
+    }
+    
+    #[test]
+    fn test_cli_reconcile_matching_apply() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/reconcile/reviewed_plan.txt")
+            .arg("--reconcile")
+            .arg("tests/fixtures/reconcile/apply_success.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Apply matched the plan"));
+    }
+    
+    #[test]
+    fn test_cli_reconcile_ignores_ignore_rules_and_filters() {
+        // Dropping the web server's only change, or the role, from the plan would make them look unplanned
+        let dir = project_with_config("[filter]\nexclude = [\"aws_iam_role.*\"]\n[ignore]\n[[ignore.rule]]\nattribute = \"instance_type\"\n");
+        let fixture = |name: &str| std::fs::canonicalize(format!("tests/fixtures/reconcile/{}", name)).unwrap();
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path())
+            .arg(fixture("reviewed_plan.txt"))
+            .arg("--reconcile")
+            .arg(fixture("apply_success.txt"))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Apply matched the plan (4 resources)"));
+    }
+    
+    #[test]
+    fn test_cli_reconcile_diverged_apply_fails() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/reconcile/reviewed_plan.txt")
+            .arg("--reconcile")
+            .arg("tests/fixtures/reconcile/apply_diverged.txt")
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .code(2)
+            .stdout(predicate::str::contains("## 💥 Failed"));
+    }
+    
+    #[test]
+    fn test_cli_resource_filters() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/module_plan.txt")
+            .arg("--module")
+            .arg("vpc")
+            .arg("--exclude")
+            .arg("module.vpc.module.subnets.*")
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("- ✅ **1** resources to add"))
+            .stdout(predicate::str::contains("module.vpc.aws_vpc.main"))
+            .stdout(predicate::str::contains("aws_subnet").not())
+            .stdout(predicate::str::contains("aws_db_instance").not());
+    }
+    
+    #[test]
+    fn test_cli_invalid_action_filter() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--filter")
+            .arg("create,remove")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown action 'remove'"));
+    }
+    
+    #[test]
+    fn test_cli_where_expression() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/plan.json")
+            .arg("--where")
+            .arg(r#"action == "update" and attr("tags.Environment") changed"#)
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("aws_instance.web_server"))
+            .stdout(predicate::str::contains("aws_s3_bucket.app_bucket").not());
+    }
+    
+    #[test]
+    fn test_cli_where_expression_error() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/plan.json")
+            .arg("--where")
+            .arg(r#"action = "update""#)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unexpected '=' (use '==' to compare) at column 8"));
+    }
+    
+    #[test]
+    fn test_cli_ignore_file() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/ignore/noisy_plan.txt")
+            .arg("--ignore-file")
+            .arg("tests/fixtures/ignore/rules.toml")
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("🔄 **1** resources to change"))
+            .stdout(predicate::str::contains("4 changes suppressed by ignore rules (1 × known after apply only, 2 × ignore rule: provider default tags, 1 × whitespace only)"))
+            .stdout(predicate::str::contains("aws_s3_bucket.logs").not());
+    }
+    
+    #[test]
+    fn test_cli_verbosity_in_markdown() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/ignore/noisy_plan.txt")
+            .arg("--ignore-file")
+            .arg("tests/fixtures/ignore/rules.toml")
+            .arg("--format")
+            .arg("markdown")
+            .arg("--verbose")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("- `aws_s3_bucket.logs` `"))
+            .stdout(predicate::str::contains("## Resource Changes"));
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/ignore/noisy_plan.txt")
+            .arg("--format")
+            .arg("markdown")
+            .arg("--quiet")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("## Summary"))
+            .stdout(predicate::str::contains("## Resource Changes").not());
+    }
+    
+    #[test]
+    fn test_cli_ignore_file_errors() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/ignore/noisy_plan.txt")
+            .arg("--ignore-file")
+            .arg("tests/fixtures/ignore/missing.toml")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Invalid configuration: tests/fixtures/ignore/missing.toml"));
+    }
+    
+    #[test]
+    fn test_cli_hide_cosmetic() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/semantic/cosmetic_plan.json")
+            .arg("--hide-cosmetic")
+            .arg("--format")
+            .arg("json")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(r#""kind": "cosmetic""#))
+            .stdout(predicate::str::contains(r#""reason": "reordered_elements""#))
+            .stdout(predicate::str::contains(r#""change": 1,"#));
+    }
+    
+    /// A project directory with a `.tfdiff.toml` and the noisy plan fixture.
+    fn project_with_config(config: &str) -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".tfdiff.toml"), config).unwrap();
+        std::fs::create_dir(dir.path().join("live")).unwrap();
+        std::fs::copy("tests/fixtures/ignore/noisy_plan.txt", dir.path().join("live").join("plan.txt")).unwrap();
+        dir
+    }
+    
+    #[test]
+    fn test_cli_discovers_config_file() {
+        let dir = project_with_config("format = \"markdown\"\n[filter]\nexclude = [\"aws_s3_bucket.*\"]\n[ignore]\nwhitespace_only = true\n");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("# Terraform Plan Report"))
+            .stdout(predicate::str::contains("1 change suppressed by ignore rules (1 × whitespace only)"))
+            .stdout(predicate::str::contains("aws_s3_bucket.logs").not());
+    }
+    
+    #[test]
+    fn test_cli_config_precedence() {
+        let dir = project_with_config("format = \"markdown\"\n[filter]\nexclude = [\"aws_s3_bucket.*\"]\n");
+        
+        // Command line options beat environment variables, which beat the file
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .env("TFDIFF_FORMAT", "json")
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("{"));
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .env("TFDIFF_FORMAT", "json")
+            .arg("plan.txt")
+            .arg("--format")
+            .arg("markdown")
+            .arg("--exclude")
+            .arg("aws_iam_*")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("# Terraform Plan Report"))
+            .stdout(predicate::str::contains("aws_s3_bucket.logs"))
+            .stdout(predicate::str::contains("aws_iam_policy.deploy").not());
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .arg("--no-config")
+            .arg("--summary")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Summary: 0 to add, 3 to change, 0 to destroy"));
+    }
+    
+    #[test]
+    fn test_cli_config_flags_can_be_turned_off() {
+        let dir = project_with_config("strict = true\nhide_cosmetic = true\n");
+        std::fs::write(dir.path().join("live").join("truncated.txt"),
+            "# aws_s3_bucket.test will be created\n+ resource \"aws_s3_bucket\" \"test\" {\n    + bucket = \"test-bucket\"\n").unwrap();
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("truncated.txt")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("may be truncated"));
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("--strict=false")
+            .arg("--hide-cosmetic=false")
+            .arg("truncated.txt")
+            .assert()
+            .success();
+        
+        // A bare flag still turns the setting on, without taking the input file as its value
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("--no-config")
+            .arg("--strict")
+            .arg("truncated.txt")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("may be truncated"));
+    }
+    
+    #[test]
+    fn test_cli_config_width() {
+        let dir = project_with_config("width = 100\n");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("╔{}╗\n", "═".repeat(98))));
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .env("TFDIFF_WIDTH", "60")
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("╔{}╗\n", "═".repeat(58))));
+    }
+    
+    #[test]
+    fn test_cli_invalid_config() {
+        let dir = project_with_config("format = \"pdf\"\n");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown format 'pdf' (expected terminal, json, html, markdown, junit, sarif, pr-comment, slack, teams)"));
+    }
+    
+    #[test]
+    fn test_cli_failed_policy_exits_with_status_4() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/policy/guarded_plan.txt")
+            .arg("--policy-file")
+            .arg("tests/fixtures/policy/policies.toml")
+            .arg("--format")
+            .arg("markdown")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::contains("## Policy Checks"))
+            .stdout(predicate::str::contains("3 failed, 1 warning"))
+            .stdout(predicate::str::contains("aws_db_instance.main will be replaced"));
+    }
+    
+    #[test]
+    fn test_cli_ignore_rules_do_not_hide_policy_failures() {
+        let policies = create_temp_file_with_content(
+            "[[policy]]\nname = \"protect-databases\"\nactions = [\"replace\"]\nresource_types = [\"aws_db_instance\"]\n\n[[policy]]\nname = \"endpoints\"\nchanged_attributes = [\"endpoint\"]\n"
+        );
+        let rules = create_temp_file_with_content("unknown_only_updates = true\n\n[[rule]]\nattribute = \"endpoint\"\nreason = \"endpoints are looked up\"\n");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/ignore/replaced_database.txt")
+            .arg("--policy-file")
+            .arg(policies.path())
+            .arg("--ignore-file")
+            .arg(rules.path())
+            .arg("--summary")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::contains("Policy checks: 2 failed"));
+    }
+    
+    #[test]
+    fn test_cli_policy_labels_and_warnings() {
+        let policies = create_temp_file_with_content(
+            "[[policy]]\nname = \"iam-review\"\nresource_types = [\"aws_iam_*\"]\nunless_label = \"iam-approved\"\n\n[[policy]]\nname = \"small-plans\"\nlevel = \"warn\"\nmax_changes = 1\n"
+        );
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/policy/guarded_plan.txt")
+            .arg("--policy-file")
+            .arg(policies.path())
+            .arg("--summary")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::contains("Policy checks: 1 failed, 1 warning"));
+        
+        // Warnings alone do not fail the run
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.env("TFDIFF_LABELS", "needs-review,iam-approved")
+            .arg("tests/fixtures/policy/guarded_plan.txt")
+            .arg("--policy-file")
+            .arg(policies.path())
+            .arg("--format")
+            .arg("json")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(r#""waived_by": "iam-approved""#))
+            .stdout(predicate::str::contains(r#""outcome": "warn""#));
+    }
+    
+    #[test]
+    fn test_cli_policies_from_config_file() {
+        let dir = project_with_config("[[policy]]\nname = \"no-iam\"\nresource_types = [\"aws_iam_*\"]\n");
+        
+        // Policies check the whole plan, whatever the report is filtered to
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .arg("--exclude")
+            .arg("aws_iam_*")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::contains("✗ FAIL no-iam"))
+            .stdout(predicate::str::contains("aws_iam_policy.deploy will be updated in-place"));
+    }
+    
+    #[test]
+    fn test_cli_catalog_annotations() {
+        let dir = project_with_config("format = \"markdown\"\n[catalog.aws]\nstateful = [\"aws_instance\"]\n");
+        std::fs::copy("tests/fixtures/policy/guarded_plan.txt", dir.path().join("live").join("plan.txt")).unwrap();
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("> 💾 stateful resource will be replaced — data loss possible"))
+            .stdout(predicate::str::contains("> 💾 stateful resource will be destroyed — data loss possible"));
+    }
+    
+    #[test]
+    fn test_cli_cost_estimate() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/cost/cost_plan.txt")
+            .arg("--no-config")
+            .arg("--summary")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Monthly cost: $47.56 → $76.32 (+$28.76), 1 change of unknown cost"));
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/cost/cost_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("json")
+            .arg("--price-file").arg("tests/fixtures/cost/prices.toml")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("\"currency\": \"EUR\""));
+    }
+    
+    #[test]
+    fn test_cli_invalid_price_file() {
+        let price_file = create_temp_file_with_content("[[price]]\nresource_types = [\"aws_eip\"]\nmonthly = \"cheap\"\n");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/cost/cost_plan.txt")
+            .arg("--no-config")
+            .arg("--price-file").arg(price_file.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("line 3: invalid type"));
+    }
+    
+    #[test]
+    fn test_cli_junit_output() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/policy/guarded_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("junit")
+            .arg("--policy-file").arg("tests/fixtures/policy/policies.toml")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::starts_with("<?xml"))
+            .stdout(predicate::str::contains("<testsuite name=\"terraform plan\" tests=\"4\" failures=\"3\""))
+            .stdout(predicate::str::contains("<failure type=\"policy\" message=\"policy protect-databases: aws_db_instance.main will be replaced\">"));
+    }
+    
+    #[test]
+    fn test_cli_sarif_output() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/policy/guarded_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("sarif")
+            .arg("--policy-file").arg("tests/fixtures/policy/policies.toml")
+            .assert()
+            .code(4)
+            .stdout(predicate::str::contains("\"version\": \"2.1.0\""))
+            .stdout(predicate::str::contains("\"ruleId\": \"policy/protect-databases\""))
+            .stdout(predicate::str::contains("\"uri\": \"tests/fixtures/policy/guarded_plan.txt\""))
+            .stdout(predicate::str::contains("\"uriBaseId\": \"%SRCROOT%\""));
+    }
+    
+    #[test]
+    fn test_cli_sarif_does_not_compare() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("--compare")
+            .arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("sarif")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--format sarif does not apply to plan comparisons"));
+    }
+    
+    #[test]
+    fn test_cli_pr_comment_output() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("pr-comment")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("<!-- tfdiff comment 1/1 -->\n## Terraform plan:"))
+            .stdout(predicate::str::contains("```diff"));
+    }
+    
+    #[test]
+    fn test_cli_slack_output() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("slack")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("\"blocks\": ["))
+            .stdout(predicate::str::contains("\"type\": \"header\""));
+    }
+    
+    #[test]
+    fn test_cli_teams_output() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--no-config")
+            .arg("--format").arg("teams")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("\"contentType\": \"application/vnd.microsoft.card.adaptive\""));
+    }
+    
+    #[test]
+    fn test_cli_template() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/cost/cost_plan.txt")
+            .arg("--no-config")
+            .arg("--template").arg("tests/fixtures/template/change_ticket.hbs")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("CHANGE REQUEST\nSummary: 2 to add, 2 to change, 1 to destroy\n"))
+            .stdout(predicate::str::contains("➕ aws_instance.web will be created"));
+    }
+    
+    #[test]
+    fn test_cli_template_from_config() {
+        let dir = project_with_config("template = \"ticket.hbs\"\n");
+        std::fs::write(dir.path().join("ticket.hbs"), "{{#each resources}}{{id}}\n{{/each}}").unwrap();
+        
+        // The template path is relative to the configuration file
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("aws_instance.web\naws_s3_bucket.logs\n"));
+        
+        // A format on the command line wins over the file's template
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.current_dir(dir.path().join("live"))
+            .arg("plan.txt")
+            .arg("--format").arg("json")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("{"));
+    }
+    
+    #[test]
+    fn test_cli_template_error() {
+        let template = create_temp_file_with_content("{{#each resources}");
+        
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--no-config")
+            .arg("--template").arg(template.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("TemplateError"));
+    }
+    
+    #[test]
+    fn test_cli_list_formats() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("--list-formats")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("terminal     Colored report for the terminal (default)\n"))
+            .stdout(predicate::str::contains("pr-comment   Markdown comments for a GitHub or GitLab pull request\n"));
+    }
+    
+    #[test]
+    fn test_cli_unknown_format() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--format").arg("pdf")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown format 'pdf' (expected terminal, json,"));
+    }
+    
+    #[test]
+    fn test_cli_width() {
+        let mut cmd = Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("tests/fixtures/terraform/simple_plan.txt")
+            .arg("--width").arg("100")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("╔{}╗\n", "═".repeat(98))));
+    }
+}
@@ -119,6 +119,27 @@ mod file_processing_integration_tests {
         assert!(matches!(result.mode, PlanMode::Plan));
     }
     
+    #[test]
+    fn test_parsed_summary_matches_consistent_fixture() {
+        let content = load_terraform_fixture("complex_update.txt");
+        let result = parse_terraform_output(&content).unwrap();
+        
+        assert_summary_totals(&result.parsed_summary, 1, 1, 0);
+        assert!(result.summary.matches_counts(&result.parsed_summary));
+        assert!(result.warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_parsed_summary_flags_inconsistent_fixture() {
+        // The fixture's "Plan:" line claims more changes than it contains
+        let content = load_terraform_fixture("kubernetes_microservices.txt");
+        let result = parse_terraform_output(&content).unwrap();
+        
+        assert_summary_totals(&result.summary, 2, 4, 3);
+        assert_summary_totals(&result.parsed_summary, 2, 3, 2);
+        assert!(result.warnings.iter().any(|w| w.kind == Some(tfdiff::models::DiagnosticKind::SummaryMismatch)));
+    }
+    
     #[test]
     fn test_end_to_end_terminal_formatting() {
         let content = load_terraform_fixture("simple_plan.txt");
@@ -1,3 +1,4 @@
 mod cli_test;
 mod file_processing_test;
-mod property_tests;
\ No newline at end of file
+mod property_tests;mod web_test;
+mod watch_test;
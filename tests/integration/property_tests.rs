@@ -71,10 +71,15 @@ mod proptest_integration {
             let plan = TerraformPlan {
                 mode: PlanMode::Plan,
                 summary: Summary { add, change, destroy, read: 0 },
+                parsed_summary: Summary::default(),
                 resources: Vec::new(),
                 data_sources: Vec::new(),
                 warnings: Vec::new(),
                 metadata: Metadata::default(),
+                suppressed: Vec::new(),
+                policy_results: Vec::new(),
+                annotations: Vec::new(),
+                cost: None,
             };
             
             if let Ok(json_output) = format_json_output(&plan) {
@@ -94,10 +99,15 @@ mod proptest_integration {
             let plan = TerraformPlan {
                 mode: PlanMode::Plan,
                 summary: Summary { add, change, destroy, read: 0 },
+                parsed_summary: Summary::default(),
                 resources: Vec::new(),
                 data_sources: Vec::new(),
                 warnings: Vec::new(),
                 metadata: Metadata::default(),
+                suppressed: Vec::new(),
+                policy_results: Vec::new(),
+                annotations: Vec::new(),
+                cost: None,
             };
             
             let markdown_output = format_markdown_output(&plan);
@@ -123,10 +133,15 @@ mod proptest_integration {
             let plan = TerraformPlan {
                 mode: PlanMode::Plan,
                 summary: Summary { add, change, destroy: 0, read: 0 },
+                parsed_summary: Summary::default(),
                 resources: Vec::new(),
                 data_sources: Vec::new(),
                 warnings: Vec::new(),
                 metadata: Metadata::default(),
+                suppressed: Vec::new(),
+                policy_results: Vec::new(),
+                annotations: Vec::new(),
+                cost: None,
             };
             
             let html_output = format_html_output(&plan);
@@ -207,8 +222,8 @@ mod proptest_integration {
             if !resources.is_empty() {
                 prop_assert_eq!(&resources[0].type_name, &resource_type);
                 prop_assert_eq!(&resources[0].name, &resource_name);
-                // Provider is set to "unknown" by default in our parser
-                prop_assert_eq!(&resources[0].provider, "unknown");
+                // Provider is inferred from the resource type prefix
+                prop_assert_eq!(&resources[0].provider, &provider_from_type(&resource_type));
             }
         }
     }
@@ -0,0 +1,74 @@
+use assert_cmd::cargo::cargo_bin;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod watch_mode_tests {
+    use super::*;
+    
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    
+    #[test]
+    fn test_watch_redraws_on_change() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("plan.txt");
+        fs::write(&path, "Plan: 1 to add, 0 to change, 0 to destroy.\n").unwrap();
+        
+        let mut child = Command::new(cargo_bin("tfdiff"))
+            .arg(&path)
+            .arg("--watch")
+            .arg("--summary")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        
+        let (sender, lines) = channel();
+        let stdout = child.stdout.take().unwrap();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        
+        let first = lines.recv_timeout(TIMEOUT).unwrap();
+        assert!(first.contains("Summary: 1 to add"));
+        
+        // Simulate `terraform plan | tee plan.txt` finishing a new run
+        fs::write(&path, r#"# aws_s3_bucket.logs will be created
++ resource "aws_s3_bucket" "logs" {
+    + bucket = "logs"
+  }
+
+Plan: 2 to add, 0 to change, 1 to destroy.
+"#).unwrap();
+        
+        let redrawn = loop {
+            let line = lines.recv_timeout(TIMEOUT).expect("watch mode did not redraw");
+            if line.contains("Summary:") {
+                break line;
+            }
+        };
+        
+        let _ = child.kill();
+        let _ = child.wait();
+        assert!(redrawn.contains("Summary: 2 to add, 0 to change, 1 to destroy"));
+    }
+    
+    #[test]
+    fn test_watch_requires_input_file() {
+        let mut cmd = assert_cmd::Command::cargo_bin("tfdiff").unwrap();
+        cmd.arg("--watch")
+            .write_stdin("Plan: 1 to add, 0 to change, 0 to destroy.")
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("--watch requires an input file"));
+    }
+}
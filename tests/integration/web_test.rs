@@ -0,0 +1,273 @@
+use assert_cmd::cargo::cargo_bin;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod web_server_tests {
+    use super::*;
+    
+    /// A `tfdiff --web` process that is killed when dropped.
+    struct WebServer {
+        child: Child,
+        port: u16,
+    }
+    
+    impl Drop for WebServer {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+    
+    fn free_port() -> u16 {
+        TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+    
+    fn start_server(fixture: &str) -> WebServer {
+        start_server_with_args(&fixture_path(&format!("terraform/{}", fixture)), &[])
+    }
+    
+    fn start_server_with_args(input: &std::path::Path, args: &[&str]) -> WebServer {
+        let port = free_port();
+        let mut command = Command::new(cargo_bin("tfdiff"));
+        if !input.as_os_str().is_empty() {
+            command.arg(input);
+        }
+        let child = command
+            .args(args)
+            .arg("--web")
+            .arg("--port")
+            .arg(port.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let server = WebServer { child, port };
+        
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(Instant::now() < deadline, "web server did not start on port {}", port);
+            thread::sleep(Duration::from_millis(50));
+        }
+        server
+    }
+    
+    /// Minimal HTTP/1.1 GET returning the status code and body.
+    fn http_get(port: u16, path: &str) -> (u16, String) {
+        http_request(port, "GET", path, "")
+    }
+    
+    fn http_post(port: u16, path: &str, body: &str) -> (u16, String) {
+        http_request(port, "POST", path, body)
+    }
+    
+    fn http_request(port: u16, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+            method, path, body.len(), body).unwrap();
+        
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+    
+    #[test]
+    fn test_web_serves_interactive_report() {
+        let server = start_server("simple_plan.txt");
+        
+        let (status, body) = http_get(server.port, "/");
+        assert_eq!(status, 200);
+        assert!(body.contains("<!DOCTYPE html>"));
+        assert!(body.contains("/assets/app.js"));
+        assert!(body.contains("aws_s3_bucket.app_bucket"));
+    }
+    
+    #[test]
+    fn test_web_api_plan() {
+        let server = start_server("simple_plan.txt");
+        
+        let (status, body) = http_get(server.port, "/api/plan");
+        assert_eq!(status, 200);
+        let plan: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(plan["summary"]["add"], 1);
+        assert!(plan["resources"].as_array().is_some_and(|resources| !resources.is_empty()));
+    }
+    
+    #[test]
+    fn test_web_api_plan_where() {
+        let server = start_server("module_plan.txt");
+        
+        // module ~ "subnets"
+        let (status, body) = http_get(server.port, "/api/plan?where=module%20~%20%22subnets%22");
+        assert_eq!(status, 200);
+        let plan: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(plan["resources"].as_array().unwrap().len(), 2);
+        assert_eq!(plan["summary"]["add"], 2);
+        
+        // module ~
+        let (status, body) = http_get(server.port, "/api/plan?where=module%20~");
+        assert_eq!(status, 400);
+        assert!(body.contains("expected a quoted pattern after '~'"));
+    }
+    
+    #[test]
+    fn test_web_api_plan_where_nesting_limit() {
+        let server = start_server("module_plan.txt");
+        
+        // 3,000 parentheses around action == "create"
+        let path = format!("/api/plan?where={}action%20%3D%3D%20%22create%22{}", "%28".repeat(3_000), "%29".repeat(3_000));
+        let (status, body) = http_get(server.port, &path);
+        assert_eq!(status, 400);
+        assert!(body.contains("expression nests deeper than 64 levels"));
+        
+        // The server is still up
+        let (status, _) = http_get(server.port, "/api/plan");
+        assert_eq!(status, 200);
+    }
+    
+    #[test]
+    fn test_web_api_resource_by_address() {
+        let server = start_server("module_plan.txt");
+        
+        let (status, body) = http_get(server.port, "/api/resources/module.vpc.module.subnets.aws_subnet.public%5B0%5D");
+        assert_eq!(status, 200);
+        let resource: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(resource["type_name"], "aws_subnet");
+        assert_eq!(resource["name"], "public[0]");
+        
+        let (status, body) = http_get(server.port, "/api/resources/aws_s3_bucket.missing");
+        assert_eq!(status, 404);
+        assert!(body.contains("no resource with address"));
+    }
+    
+    #[test]
+    fn test_web_watch_pushes_updates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("plan.txt");
+        std::fs::write(&path, "Plan: 1 to add, 0 to change, 0 to destroy.\n").unwrap();
+        let server = start_server_with_args(&path, &["--watch"]);
+        
+        let mut events = TcpStream::connect(("127.0.0.1", server.port)).unwrap();
+        events.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        write!(events, "GET /api/events HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        
+        // Wait for the stream to be established before changing the file
+        let mut received = String::new();
+        let mut buffer = [0u8; 1024];
+        while !received.contains("text/event-stream") {
+            let read = events.read(&mut buffer).unwrap();
+            received.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        }
+        
+        std::fs::write(&path, "Plan: 3 to add, 0 to change, 0 to destroy.\n").unwrap();
+        
+        while !received.contains("event: plan") {
+            let read = events.read(&mut buffer).expect("no plan event received");
+            assert!(read > 0, "event stream closed");
+            received.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        }
+        
+        let (_, body) = http_get(server.port, "/api/plan");
+        let plan: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(plan["summary"]["add"], 3);
+    }
+    
+    #[test]
+    fn test_web_serves_embedded_assets() {
+        let server = start_server("simple_plan.txt");
+        
+        let (status, body) = http_get(server.port, "/assets/app.js");
+        assert_eq!(status, 200);
+        assert!(body.contains("resource-section"));
+        
+        let (status, _) = http_get(server.port, "/assets/missing.js");
+        assert_eq!(status, 404);
+    }
+    
+    /// A server started without input, used only for uploads.
+    fn start_dashboard_server(args: &[&str]) -> WebServer {
+        start_server_with_args(std::path::Path::new(""), args)
+    }
+    
+    #[test]
+    fn test_web_upload_plans_of_each_format() {
+        let server = start_dashboard_server(&[]);
+        
+        // Without a plan of its own the server opens on the dashboard
+        let (status, _) = http_get(server.port, "/");
+        assert_eq!(status, 303);
+        
+        let (status, body) = http_post(server.port, "/api/plans?name=release%2042", &load_terraform_fixture("simple_plan.txt"));
+        assert_eq!(status, 201, "{}", body);
+        let listing: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(listing["id"], 1);
+        assert_eq!(listing["name"], "release 42");
+        assert_eq!(listing["risk"], "low");
+        assert_eq!(listing["risk_score"], 10);
+        
+        let (status, _) = http_post(server.port, "/api/plans", &load_terraform_fixture("plan.json"));
+        assert_eq!(status, 201);
+        let (status, body) = http_post(server.port, "/api/plans", &load_terraform_fixture("plan_log.ndjson"));
+        assert_eq!(status, 201);
+        let listing: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(listing["risk"], "high");
+        
+        let (status, body) = http_get(server.port, "/api/plans");
+        assert_eq!(status, 200);
+        let listings: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap();
+        let ids: Vec<u64> = listings.iter().map(|listing| listing["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+        
+        let (status, body) = http_get(server.port, "/dashboard");
+        assert_eq!(status, 200);
+        assert!(body.contains("release 42"));
+        assert!(body.contains(r#"href="/plans/3""#));
+        
+        let (status, body) = http_get(server.port, "/plans/1");
+        assert_eq!(status, 200);
+        assert!(body.contains("aws_s3_bucket.app_bucket"));
+        
+        let (status, body) = http_get(server.port, "/api/plans/2");
+        assert_eq!(status, 200);
+        let stored: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(stored["plan"]["resources"].as_array().unwrap().len(), 2);
+    }
+    
+    #[test]
+    fn test_web_upload_rejects_bad_input() {
+        let server = start_dashboard_server(&[]);
+        
+        let (status, body) = http_post(server.port, "/api/plans", "   ");
+        assert_eq!(status, 400);
+        assert!(body.contains("request body is empty"));
+        
+        let (status, _) = http_get(server.port, "/api/plans/7");
+        assert_eq!(status, 404);
+        let (status, _) = http_get(server.port, "/api/plan");
+        assert_eq!(status, 404);
+    }
+    
+    #[test]
+    fn test_web_store_persists_uploads() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().to_str().unwrap();
+        
+        {
+            let server = start_dashboard_server(&["--store", store]);
+            let (status, _) = http_post(server.port, "/api/plans?name=persisted", &load_terraform_fixture("simple_plan.txt"));
+            assert_eq!(status, 201);
+        }
+        
+        let server = start_dashboard_server(&["--store", store]);
+        let (_, body) = http_get(server.port, "/api/plans");
+        assert!(body.contains("persisted"));
+    }
+}
@@ -0,0 +1,105 @@
+use tfdiff::catalog::*;
+use tfdiff::config::Config;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod catalog_tests {
+    use super::*;
+    
+    fn resource(type_name: &str, action: ActionType) -> Resource {
+        let mut resource = sample_create_resource();
+        resource.id = format!("{}.main", type_name);
+        resource.type_name = type_name.to_string();
+        resource.action = action;
+        resource
+    }
+    
+    fn messages(catalog: &ResourceCatalog, resource: &Resource) -> Vec<String> {
+        catalog.annotations(resource).into_iter().map(|annotation| annotation.message).collect()
+    }
+    
+    #[test]
+    fn test_builtin_classes() {
+        let catalog = ResourceCatalog::default();
+        
+        assert_eq!(catalog.classes("aws_db_instance"), vec![ResourceClass::Stateful]);
+        assert_eq!(catalog.classes("aws_kms_key"), vec![ResourceClass::Stateful, ResourceClass::SecuritySensitive]);
+        assert_eq!(catalog.classes("aws_iam_role"), vec![ResourceClass::SecuritySensitive]);
+        assert_eq!(catalog.classes("azurerm_virtual_network"), vec![ResourceClass::NetworkCritical]);
+        assert_eq!(catalog.classes("google_sql_database_instance"), vec![ResourceClass::Stateful]);
+        assert_eq!(catalog.classes("kubernetes_config_map_v1"), vec![ResourceClass::CheapToRecreate]);
+        assert!(catalog.classes("aws_instance").is_empty());
+    }
+    
+    #[test]
+    fn test_annotations_depend_on_action() {
+        let catalog = ResourceCatalog::default();
+        
+        assert_eq!(messages(&catalog, &resource("aws_db_instance", ActionType::Destroy)),
+            vec!["stateful resource will be destroyed — data loss possible"]);
+        assert!(messages(&catalog, &resource("aws_db_instance", ActionType::Create)).is_empty());
+        assert!(messages(&catalog, &resource("aws_db_instance", ActionType::Update)).is_empty());
+        
+        let mut replaced = resource("aws_db_instance", ActionType::Update);
+        replaced.replace = true;
+        assert_eq!(messages(&catalog, &replaced), vec!["stateful resource will be replaced — data loss possible"]);
+        
+        assert_eq!(messages(&catalog, &resource("aws_iam_role", ActionType::Create)),
+            vec!["security-sensitive resource will be created — check the access it grants"]);
+        assert_eq!(messages(&catalog, &resource("aws_vpc", ActionType::Update)),
+            vec!["network-critical resource will be updated in-place — connectivity may be interrupted"]);
+        assert!(messages(&catalog, &resource("aws_vpc", ActionType::Create)).is_empty());
+        assert_eq!(messages(&catalog, &resource("aws_lambda_function", ActionType::Destroy)),
+            vec!["cheap-to-recreate resource will be destroyed — little lasting impact"]);
+    }
+    
+    #[test]
+    fn test_cheap_to_recreate_needs_no_other_class() {
+        let mut catalog = ResourceCatalog::from_toml("[custom]\ncheap_to_recreate = [\"aws_sqs_queue\"]\nstateful = [\"aws_sqs_queue\"]\n").unwrap();
+        assert_eq!(messages(&catalog, &resource("aws_sqs_queue", ActionType::Destroy)),
+            vec!["stateful resource will be destroyed — data loss possible"]);
+        
+        catalog = ResourceCatalog::from_toml("[custom]\ncheap_to_recreate = [\"aws_sqs_queue\"]\n").unwrap();
+        assert_eq!(catalog.annotations(&resource("aws_sqs_queue", ActionType::Destroy))[0].class, ResourceClass::CheapToRecreate);
+    }
+    
+    #[test]
+    fn test_extend_from_config() {
+        let config = Config::from_toml("[catalog.aws]\nstateful = [\"aws_memorydb_*\"]\n\n[catalog.internal]\nnetwork_critical = [\"acme_gateway\"]\n").unwrap();
+        let mut catalog = ResourceCatalog::default();
+        catalog.extend(&config.catalog);
+        
+        assert_eq!(catalog.classes("aws_memorydb_cluster"), vec![ResourceClass::Stateful]);
+        assert_eq!(catalog.classes("acme_gateway"), vec![ResourceClass::NetworkCritical]);
+        // The built-in types are kept
+        assert_eq!(catalog.classes("aws_db_instance"), vec![ResourceClass::Stateful]);
+    }
+    
+    #[test]
+    fn test_annotate_plan() {
+        let mut plan = parse_terraform_output(&load_fixture("policy/guarded_plan.txt")).unwrap();
+        ResourceCatalog::default().annotate(&mut plan);
+        
+        assert_eq!(plan.annotations, vec![
+            ResourceAnnotation {
+                address: "aws_db_instance.main".to_string(),
+                class: ResourceClass::Stateful,
+                message: "stateful resource will be replaced — data loss possible".to_string(),
+            },
+            ResourceAnnotation {
+                address: "aws_iam_role_policy.deploy".to_string(),
+                class: ResourceClass::SecuritySensitive,
+                message: "security-sensitive resource will be updated in-place — check the access it grants".to_string(),
+            },
+        ]);
+    }
+    
+    #[test]
+    fn test_invalid_catalog() {
+        let error = ResourceCatalog::from_toml("[aws]\ndurable = [\"aws_s3_bucket\"]\n").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: line 2: unknown field `durable`"));
+    }
+}
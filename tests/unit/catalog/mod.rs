@@ -0,0 +1 @@
+mod catalog_test;
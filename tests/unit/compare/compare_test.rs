@@ -0,0 +1,79 @@
+use tfdiff::compare::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+use serde_json::json;
+
+#[cfg(test)]
+mod compare_tests {
+    use super::*;
+    
+    fn load_pair() -> (TerraformPlan, TerraformPlan) {
+        let previous = parse_terraform_output(&load_fixture("compare/previous_plan.txt")).unwrap();
+        let current = parse_terraform_output(&load_fixture("compare/current_plan.txt")).unwrap();
+        (previous, current)
+    }
+    
+    #[test]
+    fn test_compare_identical_plans() {
+        let plan = sample_terraform_plan();
+        let comparison = compare_plans(&plan, &plan);
+        
+        assert!(comparison.is_identical());
+        assert_eq!(comparison.unchanged, plan.resources.len());
+    }
+    
+    #[test]
+    fn test_compare_added_and_removed_resources() {
+        let (previous, current) = load_pair();
+        let comparison = compare_plans(&previous, &current);
+        
+        let added: Vec<&str> = comparison.added.iter().map(|r| r.id.as_str()).collect();
+        let removed: Vec<&str> = comparison.removed.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(added, vec!["aws_sqs_queue.jobs"]);
+        assert_eq!(removed, vec!["aws_iam_role.old"]);
+        assert_eq!(comparison.previous_summary.destroy, 1);
+        assert_eq!(comparison.current_summary.add, 2);
+    }
+    
+    #[test]
+    fn test_compare_attribute_differences() {
+        let (previous, current) = load_pair();
+        let comparison = compare_plans(&previous, &current);
+        
+        assert_eq!(comparison.changed.len(), 2);
+        
+        let bucket = comparison.changed.iter().find(|d| d.address == "aws_s3_bucket.logs").unwrap();
+        assert!(!bucket.action_changed());
+        assert_eq!(bucket.attributes.len(), 1);
+        assert_eq!(bucket.attributes[0].path, "bucket");
+        assert_eq!(bucket.attributes[0].previous, Some(ProposedValue { before: None, after: Some(json!("logs-v1")) }));
+        assert_eq!(bucket.attributes[0].current, Some(ProposedValue { before: None, after: Some(json!("logs-v2")) }));
+        
+        let instance = comparison.changed.iter().find(|d| d.address == "aws_instance.web").unwrap();
+        assert_eq!(instance.attributes[0].path, "instance_type");
+        assert_eq!(instance.attributes[0].current.as_ref().unwrap().after, Some(json!("t3.large")));
+    }
+    
+    #[test]
+    fn test_compare_action_changes() {
+        let previous = sample_terraform_plan();
+        let mut current = previous.clone();
+        current.resources[0].action = ActionType::Destroy;
+        
+        let comparison = compare_plans(&previous, &current);
+        assert_eq!(comparison.changed.len(), 1);
+        let difference = &comparison.changed[0];
+        assert!(difference.action_changed());
+        assert_eq!(difference.previous_action, previous.resources[0].action);
+        assert_eq!(difference.current_action, ActionType::Destroy);
+    }
+    
+    #[test]
+    fn test_proposed_values_from_update_changes() {
+        let values = proposed_values(&sample_update_resource());
+        assert!(!values.is_empty());
+        assert!(values.values().all(|value| value.before.is_some() || value.after.is_some()));
+    }
+}
@@ -0,0 +1 @@
+mod compare_test;
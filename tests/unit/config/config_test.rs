@@ -0,0 +1,90 @@
+use tfdiff::config::*;
+use tfdiff::ignore::IgnoreRule;
+use crate::common::fixtures::*;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    
+    #[test]
+    fn test_load_config() {
+        let config = Config::load(&fixture_path("config/tfdiff.toml")).unwrap();
+        
+        assert_eq!(config.format.as_deref(), Some("markdown"));
+        assert_eq!(config.group_by.as_deref(), Some("module"));
+        assert_eq!(config.theme.as_deref(), Some("plain"));
+        assert_eq!(config.width, Some(120));
+        assert_eq!(config.hide_cosmetic, Some(true));
+        assert_eq!(config.strict, None);
+        assert_eq!(config.filter.exclude, vec!["aws_s3_bucket.*"]);
+        assert_eq!(config.filter.match_mode.as_deref(), Some("any"));
+        
+        let ignore = config.ignore.as_ref().unwrap();
+        assert!(ignore.whitespace_only);
+        assert_eq!(ignore.rules, vec![IgnoreRule { attribute: Some("tags_all".to_string()), ..Default::default() }]);
+        
+        assert_eq!(config.redact.attributes, vec!["*password*"]);
+        assert_eq!(config.prefix_regexes().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_empty_config() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config, Config::default());
+        assert!(config.ignore.is_none());
+    }
+    
+    #[test]
+    fn test_discover_searches_parent_directories() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("live").join("prod");
+        std::fs::create_dir_all(&nested).unwrap();
+        
+        assert_eq!(Config::discover(&nested), None);
+        
+        std::fs::write(dir.path().join(CONFIG_FILE_NAME), "format = \"json\"").unwrap();
+        assert_eq!(Config::discover(&nested), Some(dir.path().join(CONFIG_FILE_NAME)));
+        
+        // The closest file wins
+        std::fs::write(nested.join(CONFIG_FILE_NAME), "format = \"html\"").unwrap();
+        assert_eq!(Config::discover(&nested), Some(nested.join(CONFIG_FILE_NAME)));
+    }
+    
+    #[test]
+    fn test_invalid_config() {
+        let error = Config::from_toml("format = \"json\"\n[filter]\nmodule = [\"network\"]").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: line 3: unknown field `module`, expected one of `actions`"));
+        
+        let error = Config::from_toml(r#"prefix_patterns = ["[stack"]"#).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: invalid prefix pattern \"[stack\": unclosed character class"));
+        
+        let error = Config::from_toml("[redact]\nvalues = [\"(\"]").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: invalid redaction pattern \"(\""));
+        
+        let error = Config::from_toml("[[ignore.rule]]\nreason = \"noise\"").unwrap_err();
+        assert!(error.to_string().contains("rule 1 would ignore every change"));
+    }
+    
+    #[test]
+    fn test_load_names_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "strict = \"yes\"").unwrap();
+        
+        let error = Config::load(&path).unwrap_err().to_string();
+        assert!(error.starts_with(&format!("Invalid configuration: {}: line 1: invalid type", path.display())));
+    }
+    
+    #[test]
+    fn test_config_policies() {
+        let config = Config::from_toml("[[policy]]\nname = \"protect-databases\"\nactions = [\"destroy\", \"replace\"]\nresource_types = [\"aws_db_instance\"]\n").unwrap();
+        
+        assert_eq!(config.policies.len(), 1);
+        assert_eq!(config.policies[0].name, "protect-databases");
+        assert_eq!(config.policies[0].actions, vec!["destroy", "replace"]);
+        
+        let error = Config::from_toml("[[policy]]\nname = \"p\"\nwhere = \"action ==\"\n").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: policy 'p': "));
+    }
+}
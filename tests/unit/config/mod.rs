@@ -0,0 +1 @@
+mod config_test;
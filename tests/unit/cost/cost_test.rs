@@ -0,0 +1,164 @@
+use tfdiff::config::Config;
+use tfdiff::cost::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod cost_tests {
+    use super::*;
+    
+    fn cost_plan() -> TerraformPlan {
+        parse_terraform_output(&load_fixture("cost/cost_plan.txt")).unwrap()
+    }
+    
+    fn resource_cost(cost: &PlanCost, address: &str) -> ResourceCost {
+        cost.resources.iter().find(|resource_cost| resource_cost.address == address).cloned().unwrap()
+    }
+    
+    #[test]
+    fn test_builtin_table() {
+        let prices = PriceTable::builtin();
+        
+        assert_eq!(prices.currency(), "USD");
+        assert_eq!(prices.rule("aws_instance").unwrap().prices["t3.small"], 15.18);
+        assert_eq!(prices.rule("aws_nat_gateway").unwrap().monthly, 32.85);
+        assert!(prices.rule("aws_s3_bucket").is_none());
+    }
+    
+    #[test]
+    fn test_estimate_text_plan() {
+        let cost = PriceTable::builtin().estimate(&cost_plan()).unwrap();
+        
+        assert_eq!(cost.currency, "USD");
+        // Tag changes leave the price alone and are not listed
+        assert_eq!(cost.resources.len(), 4);
+        assert!(cost.resources.iter().all(|resource_cost| resource_cost.address != "aws_instance.tags"));
+        
+        assert_eq!(resource_cost(&cost, "aws_db_instance.main"), ResourceCost {
+            address: "aws_db_instance.main".to_string(),
+            before: Some(14.71),
+            after: Some(61.14),
+            delta: Some(46.43),
+            note: None,
+        });
+        assert_eq!(resource_cost(&cost, "aws_instance.web").delta, Some(15.18));
+        assert_eq!(resource_cost(&cost, "aws_nat_gateway.main").delta, Some(-32.85));
+        
+        let unpriced = resource_cost(&cost, "aws_instance.big");
+        assert_eq!(unpriced.delta, None);
+        assert_eq!(unpriced.note.as_deref(), Some("no price for instance_type \"x1e.32xlarge\""));
+        
+        assert_eq!(cost.unknown(), 1);
+        assert_eq!((cost.before, cost.after, cost.delta), (47.56, 76.32, 28.76));
+    }
+    
+    #[test]
+    fn test_estimate_json_plan() {
+        let plan = parse_terraform_output(&load_fixture("terraform/plan.json")).unwrap();
+        let cost = PriceTable::builtin().estimate(&plan).unwrap();
+        
+        let web = cost.resources.iter().find(|resource_cost| resource_cost.address.starts_with("aws_instance")).unwrap();
+        assert_eq!((web.before, web.after, web.delta), (Some(8.47), Some(15.18), Some(6.71)));
+    }
+    
+    #[test]
+    fn test_update_delta_from_changed_attributes() {
+        // Only the storage changes, so the instance class is not in the plan but cancels out
+        let plan = parse_terraform_output(r#"
+  # aws_db_instance.main will be updated in-place
+  ~ resource "aws_db_instance" "main" {
+      ~ allocated_storage = 100 -> 200
+    }
+
+Plan: 0 to add, 1 to change, 0 to destroy.
+"#).unwrap();
+        let cost = PriceTable::builtin().estimate(&plan).unwrap();
+        
+        assert_eq!(cost.resources[0], ResourceCost {
+            address: "aws_db_instance.main".to_string(),
+            before: None,
+            after: None,
+            delta: Some(11.5),
+            note: None,
+        });
+    }
+    
+    #[test]
+    fn test_count_multiplies_price() {
+        let prices = PriceTable::from_toml("[[price]]\nresource_types = [\"acme_pool\"]\nmonthly = 10.0\ncount = \"size\"\n").unwrap();
+        let plan = parse_terraform_output(r#"
+  # acme_pool.main will be created
+  + resource "acme_pool" "main" {
+      + size = 3
+    }
+
+  # acme_pool.other will be created
+  + resource "acme_pool" "other" {
+      + size = (known after apply)
+    }
+
+Plan: 2 to add, 0 to change, 0 to destroy.
+"#).unwrap();
+        let cost = prices.estimate(&plan).unwrap();
+        
+        assert_eq!(cost.resources[0].delta, Some(30.0));
+        assert_eq!(cost.resources[1].delta, None);
+        assert_eq!(cost.resources[1].note.as_deref(), Some("size is not known until apply"));
+    }
+    
+    #[test]
+    fn test_user_prices_take_precedence() {
+        let mut prices = PriceTable::builtin();
+        prices.extend(&PriceTable::from_toml("[[price]]\nresource_types = [\"aws_instance\"]\nattribute = \"instance_type\"\nprices = { \"x1e.32xlarge\" = 19500.0 }\n").unwrap());
+        let cost = prices.estimate(&cost_plan()).unwrap();
+        
+        assert_eq!(resource_cost(&cost, "aws_instance.big").delta, Some(19500.0));
+        // The user's rule replaces the built-in one for the types it selects
+        assert_eq!(resource_cost(&cost, "aws_instance.web").note.as_deref(), Some("no price for instance_type \"t3.small\""));
+        assert_eq!(resource_cost(&cost, "aws_nat_gateway.main").delta, Some(-32.85));
+    }
+    
+    #[test]
+    fn test_other_currency_replaces_builtin_prices() {
+        let mut prices = PriceTable::builtin();
+        prices.extend(&PriceTable::load(&fixture_path("cost/prices.toml")).unwrap());
+        let cost = prices.estimate(&cost_plan()).unwrap();
+        
+        assert_eq!(cost.currency, "EUR");
+        assert_eq!(cost.resources.len(), 3);
+        assert_eq!(resource_cost(&cost, "aws_db_instance.main").delta, Some(42.5));
+    }
+    
+    #[test]
+    fn test_price_from_config() {
+        let config = Config::from_toml("[cost]\ncurrency = \"GBP\"\n\n[[cost.price]]\nresource_types = [\"aws_nat_gateway\"]\nmonthly = 26.0\n").unwrap();
+        let mut plan = cost_plan();
+        let mut prices = PriceTable::builtin();
+        prices.extend(config.cost.as_ref().unwrap());
+        prices.price(&mut plan);
+        
+        let cost = plan.cost.unwrap();
+        assert_eq!(cost.currency, "GBP");
+        assert_eq!(cost.delta, -26.0);
+    }
+    
+    #[test]
+    fn test_no_priced_resources() {
+        let plan = parse_terraform_output(&load_fixture("terraform/simple_plan.txt")).unwrap();
+        
+        assert!(PriceTable::from_toml("").unwrap().estimate(&plan).is_none());
+    }
+    
+    #[test]
+    fn test_invalid_price_table() {
+        let error = PriceTable::from_toml("[[price]]\nresource_types = [\"aws_instance\"]\nattribute = \"instance_type\"\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid configuration: price for 'aws_instance': attribute needs prices to pick from");
+        
+        let error = PriceTable::from_toml("[[price]]\nresource_types = [\"aws_eip\"]\nmonthly = -1.0\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid configuration: price for 'aws_eip': prices cannot be negative");
+        
+        let error = PriceTable::from_toml("[[price]]\nmonthly = 1.0\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid configuration: every price needs resource_types");
+    }
+}
@@ -0,0 +1 @@
+mod cost_test;
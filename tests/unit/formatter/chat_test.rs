@@ -0,0 +1,140 @@
+use tfdiff::formatter::chat::*;
+use tfdiff::models::*;
+use crate::common::test_data::*;
+use serde_json::Value;
+
+#[cfg(test)]
+mod chat_formatter_tests {
+    use super::*;
+
+    fn slack(plan: &TerraformPlan) -> Value {
+        serde_json::from_str(&format_slack_output(plan).unwrap()).unwrap()
+    }
+
+    fn teams(plan: &TerraformPlan) -> Value {
+        serde_json::from_str(&format_teams_output(plan).unwrap()).unwrap()
+    }
+
+    /// Texts of the Slack sections, in order.
+    fn slack_texts(message: &Value) -> Vec<String> {
+        message["blocks"].as_array().unwrap().iter()
+            .filter_map(|block| block["text"]["text"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    fn teams_texts(message: &Value) -> Vec<String> {
+        message["attachments"][0]["content"]["body"].as_array().unwrap().iter()
+            .filter_map(|block| block["text"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    fn large_plan(count: usize) -> TerraformPlan {
+        let mut plan = TerraformPlan::default();
+        plan.resources = (0..count).map(|index| {
+            let mut resource = sample_create_resource();
+            resource.id = format!("aws_s3_bucket.bucket_{:03}", index);
+            resource
+        }).collect();
+        plan.summary = Summary::from_resources(&plan.resources);
+        plan
+    }
+
+    #[test]
+    fn test_format_slack_output() {
+        let message = slack(&sample_terraform_plan());
+
+        assert_eq!(message["text"], "Terraform plan: 2 to add, 1 to change");
+        assert_eq!(message["blocks"][0]["type"], "header");
+        assert_eq!(message["blocks"][1]["fields"][0]["text"], "*➕ Add*\n2");
+        assert!(message["blocks"][1]["fields"][3]["text"].as_str().unwrap().starts_with("*⚠️ Risk*\nmedium risk ("));
+        let texts = slack_texts(&message);
+        assert!(texts.iter().any(|text| text.starts_with("*Riskiest changes*\n🟠 `aws_instance.web` — ")));
+        assert!(texts.contains(&"➕ `aws_s3_bucket.test` will be created\n🔄 `aws_instance.web` will be updated in-place".to_string()));
+        assert!(!texts.iter().any(|text| text.contains("Policy checks")));
+    }
+
+    #[test]
+    fn test_format_slack_output_policies() {
+        let message = slack(&guarded_plan_with_policies(&[]));
+        let texts = slack_texts(&message);
+        let policies = texts.iter().find(|text| text.starts_with("*Policy checks:* 3 failed, 1 warning")).unwrap();
+
+        assert!(policies.contains("🛑 failed `protect-databases` — Databases are never destroyed or replaced: `aws_db_instance.main`"));
+        assert!(policies.contains("⚠️ warning `small-plans`"));
+    }
+
+    #[test]
+    fn test_format_slack_output_truncates_resources() {
+        let message = slack(&large_plan(40));
+        let resources = slack_texts(&message).into_iter().find(|text| text.contains("bucket_000")).unwrap();
+
+        assert_eq!(resources.lines().count(), 26);
+        assert!(resources.contains("bucket_024"));
+        assert!(!resources.contains("bucket_025"));
+        assert!(resources.ends_with("…and 15 more"));
+    }
+
+    #[test]
+    fn test_format_slack_output_stays_within_section_limit() {
+        let mut plan = large_plan(25);
+        for resource in &mut plan.resources {
+            resource.id = format!("module.{}.{}", "nested".repeat(30), resource.id);
+        }
+        let message = slack(&plan);
+
+        for text in slack_texts(&message) {
+            assert!(text.chars().count() <= 3_000, "section is {} characters", text.chars().count());
+        }
+        let resources = slack_texts(&message).into_iter().find(|text| text.contains("bucket_000")).unwrap();
+        assert!(resources.contains("…and "));
+    }
+
+    #[test]
+    fn test_format_slack_output_escapes_text() {
+        let mut plan = sample_terraform_plan();
+        plan.resources[0].id = "aws_s3_bucket.test[\"<b>&\"]".to_string();
+        let texts = slack_texts(&slack(&plan));
+
+        assert!(texts.iter().any(|text| text.contains("`aws_s3_bucket.test[\"&lt;b&gt;&amp;\"]`")));
+    }
+
+    #[test]
+    fn test_format_teams_output() {
+        let message = teams(&guarded_plan_with_policies(&[]));
+
+        assert_eq!(message["type"], "message");
+        let attachment = &message["attachments"][0];
+        assert_eq!(attachment["contentType"], "application/vnd.microsoft.card.adaptive");
+        assert_eq!(attachment["content"]["type"], "AdaptiveCard");
+
+        let facts = attachment["content"]["body"][1]["facts"].as_array().unwrap();
+        assert_eq!(facts[0]["title"], "➕ Add");
+        assert_eq!(facts[0]["value"], "2");
+        assert!(facts.iter().any(|fact| fact["title"] == "📋 Policies" && fact["value"] == "3 failed, 1 warning"));
+
+        let texts = teams_texts(&message);
+        assert_eq!(texts[0], "Terraform plan: 2 to add, 1 to change, 2 to destroy");
+        assert!(texts.iter().any(|text| text.contains("🛑 failed protect-databases — Databases are never destroyed or replaced: aws_db_instance.main")));
+        assert!(texts.iter().any(|text| text.contains("♻️ aws_db_instance.main will be replaced")));
+        assert!(!texts.iter().any(|text| text.contains('`')));
+    }
+
+    #[test]
+    fn test_format_teams_output_truncates_resources() {
+        let texts = teams_texts(&teams(&large_plan(30)));
+        let resources = texts.iter().find(|text| text.contains("bucket_000")).unwrap();
+
+        assert!(resources.contains("bucket_024"));
+        assert!(!resources.contains("bucket_025"));
+        assert!(resources.ends_with("…and 5 more"));
+    }
+
+    #[test]
+    fn test_format_chat_output_without_changes() {
+        let plan = TerraformPlan::default();
+
+        assert_eq!(slack(&plan)["text"], "Terraform plan: no changes");
+        assert_eq!(slack(&plan)["blocks"][1]["fields"].as_array().unwrap().len(), 3);
+        assert!(!teams_texts(&teams(&plan)).iter().any(|text| text == "Changes"));
+    }
+}
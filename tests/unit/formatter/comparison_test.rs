@@ -0,0 +1,82 @@
+use tfdiff::compare::*;
+use tfdiff::formatter::*;
+use tfdiff::parser::cleaner::clean_ansi_codes;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod comparison_formatter_tests {
+    use super::*;
+    
+    fn sample_comparison() -> PlanComparison {
+        let previous = parse_terraform_output(&load_fixture("compare/previous_plan.txt")).unwrap();
+        let current = parse_terraform_output(&load_fixture("compare/current_plan.txt")).unwrap();
+        compare_plans(&previous, &current)
+    }
+    
+    #[test]
+    fn test_comparison_terminal_output() {
+        let output = clean_ansi_codes(&format_comparison_terminal_output(&sample_comparison()));
+        
+        assert!(output.contains("TERRAFORM PLAN COMPARISON"));
+        assert!(output.contains("ADDED TO PLAN"));
+        assert!(output.contains("+ CREATE aws_sqs_queue.jobs"));
+        assert!(output.contains("- DESTROY aws_iam_role.old"));
+        assert!(output.contains(r#"previous: "t3.micro" → "t3.small""#));
+        assert!(output.contains(r#"current:  "t3.micro" → "t3.large""#));
+    }
+    
+    #[test]
+    fn test_comparison_terminal_output_with_width() {
+        let output = clean_ansi_codes(&format_comparison_terminal_output_with_width(&sample_comparison(), 100));
+        let lines: Vec<&str> = output.lines().collect();
+        
+        assert_eq!(display_width(lines[0]), 100);
+        assert_eq!(display_width(lines[1]), 100);
+        assert!(lines[1].contains("TERRAFORM PLAN COMPARISON"));
+        assert!(output.contains(&format!("\n{}\n", "─".repeat(100))));
+        
+        let narrow = clean_ansi_codes(&format_comparison_terminal_output_with_width(&sample_comparison(), 10));
+        assert_eq!(display_width(narrow.lines().next().unwrap()), MIN_WIDTH);
+    }
+    
+    #[test]
+    fn test_comparison_terminal_output_identical() {
+        let plan = sample_terraform_plan();
+        let output = clean_ansi_codes(&format_comparison_terminal_output(&compare_plans(&plan, &plan)));
+        assert!(output.contains("Both plans propose the same changes"));
+    }
+    
+    #[test]
+    fn test_comparison_json_output() {
+        let output = format_comparison_json_output(&sample_comparison()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        
+        assert_eq!(json["added"][0]["id"], "aws_sqs_queue.jobs");
+        assert_eq!(json["removed"][0]["id"], "aws_iam_role.old");
+        assert_eq!(json["changed"].as_array().unwrap().len(), 2);
+        assert_eq!(json["unchanged"], 0);
+    }
+    
+    #[test]
+    fn test_comparison_markdown_output() {
+        let output = format_comparison_markdown_output(&sample_comparison());
+        
+        assert!(output.contains("# Terraform Plan Comparison"));
+        assert!(output.contains("| Previous | 1 | 1 | 1 |"));
+        assert!(output.contains("- CREATE `aws_sqs_queue.jobs`"));
+        assert!(output.contains("### `aws_instance.web`"));
+        assert!(output.contains(r#"| `bucket` | `+ "logs-v1"` | `+ "logs-v2"` |"#));
+    }
+    
+    #[test]
+    fn test_comparison_html_output() {
+        let output = format_comparison_html_output(&sample_comparison());
+        
+        assert!(output.contains("<!DOCTYPE html>"));
+        assert!(output.contains("Terraform Plan Comparison"));
+        assert!(output.contains("aws_sqs_queue.jobs"));
+        assert!(output.contains("&quot;t3.large&quot;"));
+    }
+}
@@ -0,0 +1,48 @@
+use tfdiff::formatter::explain::*;
+use tfdiff::parser::parse_terraform_output;
+use tfdiff::parser::cleaner::clean_ansi_codes;
+
+#[cfg(test)]
+mod explain_formatter_tests {
+    use super::*;
+    
+    const INPUT: &str = r#"Terraform will perform the following actions:
+
+  # aws_s3_bucket.logs will be created
+  + resource "aws_s3_bucket" "logs" {
+      + bucket = "logs-bucket"
+    }
+
+Plan: 1 to add, 0 to change, 0 to destroy."#;
+    
+    #[test]
+    fn test_explain_shows_span_and_raw_lines() {
+        let plan = parse_terraform_output(INPUT).unwrap();
+        let output = clean_ansi_codes(&format_explain_output(&plan, INPUT));
+        
+        assert!(output.contains("aws_s3_bucket.logs (lines 3-6)"));
+        assert!(output.contains("    3 │   # aws_s3_bucket.logs will be created"));
+        assert!(output.contains("    5 │       + bucket = \"logs-bucket\""));
+        assert!(!output.contains("Plan: 1 to add"));
+    }
+    
+    #[test]
+    fn test_explain_shows_parsed_representation() {
+        let plan = parse_terraform_output(INPUT).unwrap();
+        let output = clean_ansi_codes(&format_explain_output(&plan, INPUT));
+        
+        assert!(output.contains("action: create"));
+        assert!(output.contains("type: aws_s3_bucket"));
+        assert!(output.contains("bucket = \"+ \\\"logs-bucket\\\"\""));
+    }
+    
+    #[test]
+    fn test_explain_without_span() {
+        let mut plan = parse_terraform_output(INPUT).unwrap();
+        plan.resources[0].span = None;
+        let output = clean_ansi_codes(&format_explain_output(&plan, INPUT));
+        
+        assert!(output.contains("aws_s3_bucket.logs (no source span)"));
+        assert!(output.contains("action: create"));
+    }
+}
@@ -0,0 +1,60 @@
+use tfdiff::formatter::*;
+use tfdiff::models::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod grouping_tests {
+    use super::*;
+    
+    fn module_resource(id: &str) -> Resource {
+        let mut resource = sample_create_resource();
+        resource.id = id.to_string();
+        resource
+    }
+    
+    #[test]
+    fn test_group_resources_none() {
+        let resources = vec![sample_create_resource(), sample_update_resource()];
+        let groups = group_resources(&resources, GroupBy::None);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].1.len(), 2);
+    }
+    
+    #[test]
+    fn test_group_resources_by_action_keeps_plan_order() {
+        let resources = vec![sample_update_resource(), sample_create_resource(), sample_update_resource()];
+        let groups = group_resources(&resources, GroupBy::Action);
+        let labels: Vec<&str> = groups.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["update", "create"]);
+        assert_eq!(groups[0].1.len(), 2);
+    }
+    
+    #[test]
+    fn test_group_resources_by_module() {
+        let resources = vec![
+            module_resource("module.vpc.aws_vpc.main"),
+            module_resource("aws_s3_bucket.logs"),
+            module_resource("module.vpc.aws_subnet.public[0]"),
+        ];
+        let groups = group_resources(&resources, GroupBy::Module);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "module.vpc");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "root module");
+    }
+    
+    #[test]
+    fn test_grouped_formatters_show_headers() {
+        let plan = sample_terraform_plan();
+        
+        let terminal = format_terminal_output_grouped(&plan, GroupBy::Action);
+        assert!(terminal.contains("▸ create"));
+        
+        let markdown = format_markdown_output_grouped(&plan, GroupBy::Action);
+        assert!(markdown.contains("### 📦 create"));
+        
+        let html = format_html_output_grouped(&plan, GroupBy::Action);
+        assert!(html.contains("group-header"));
+        assert!(!format_html_output(&plan).contains("class=\"group-header\""));
+    }
+}
@@ -35,10 +35,15 @@ mod html_formatter_tests {
         let plan = TerraformPlan {
             mode: PlanMode::Plan,
             summary: Summary::default(),
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_html_output(&plan);
@@ -60,6 +65,7 @@ mod html_formatter_tests {
                 destroy: 2,
                 read: 0,
             },
+            parsed_summary: Summary::default(),
             resources: vec![
                 sample_create_resource(),
                 sample_destroy_resource(),
@@ -67,6 +73,10 @@ mod html_formatter_tests {
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_html_output(&plan);
@@ -88,10 +98,15 @@ mod html_formatter_tests {
                 destroy: 0,
                 read: 3,
             },
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_html_output(&plan);
@@ -163,4 +178,80 @@ mod html_formatter_tests {
         assert!(output.contains("bucket"));
         assert!(output.contains("test-bucket"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_format_html_policy_checks() {
+        let mut plan = sample_terraform_plan();
+        assert!(!format_html_output(&plan).contains(r#"<div class="policy-section">"#));
+        
+        plan.policy_results = vec![PolicyResult {
+            policy: "protect-databases".to_string(),
+            description: Some("Databases are never destroyed".to_string()),
+            outcome: PolicyOutcome::Fail,
+            violations: vec!["aws_db_instance.main will be destroyed".to_string()],
+            waived_by: None,
+            addresses: Vec::new(),
+        }];
+        let output = format_html_output(&plan);
+        
+        assert!(output.contains(r#"<div class="policy-result policy-fail">"#));
+        assert!(output.contains(r#"<span class="policy-outcome">FAIL</span>"#));
+        assert!(output.contains("<li>aws_db_instance.main will be destroyed</li>"));
+    }
+    
+    #[test]
+    fn test_format_html_risk() {
+        let plan = sample_terraform_plan();
+        let output = format_html_output(&plan);
+        
+        assert!(output.contains(r#"<span class="risk-badge risk-medium">medium · 32</span>"#));
+        assert!(output.contains(r#"data-risk-score="12" data-order="0""#));
+        assert!(output.contains(r#"data-risk-score="32" data-order="1""#));
+        assert!(output.contains(r#"<select class="resource-sort">"#));
+    }
+    
+    #[test]
+    fn test_format_html_annotations() {
+        let mut plan = sample_terraform_plan();
+        plan.annotations = vec![ResourceAnnotation {
+            address: "aws_instance.web".to_string(),
+            class: ResourceClass::NetworkCritical,
+            message: "network-critical resource will be updated in-place — connectivity may be interrupted".to_string(),
+        }];
+        let output = format_html_output(&plan);
+        
+        assert!(output.contains(r#"<div class="resource-annotation annotation-network-critical">🌐 network-critical resource will be updated in-place — connectivity may be interrupted</div>"#));
+    }
+    
+    #[test]
+    fn test_format_html_cost() {
+        let mut plan = sample_terraform_plan();
+        plan.cost = Some(PlanCost {
+            currency: "USD".to_string(),
+            before: 1200.0,
+            after: 1260.5,
+            delta: 60.5,
+            resources: vec![
+                ResourceCost {
+                    address: "aws_instance.web".to_string(),
+                    before: Some(1200.0),
+                    after: Some(1260.5),
+                    delta: Some(60.5),
+                    note: None,
+                },
+                ResourceCost {
+                    address: "aws_instance.big".to_string(),
+                    before: Some(0.0),
+                    after: None,
+                    delta: None,
+                    note: Some("no price for instance_type \"x1e.32xlarge\"".to_string()),
+                },
+            ],
+        });
+        let output = format_html_output(&plan);
+        
+        assert!(output.contains("💰 Monthly cost <strong>$1,200.00 → $1,260.50 (+$60.50), 1 change of unknown cost</strong>"));
+        assert!(output.contains(r#"<tr class="cost-up"><td>aws_instance.web</td><td>$1,200.00 → $1,260.50 (+$60.50)</td></tr>"#));
+        assert!(output.contains(r#"<tr class="cost-unknown"><td>aws_instance.big</td><td>unknown cost: no price for instance_type &quot;x1e.32xlarge&quot;</td></tr>"#));
+    }
+}
@@ -114,4 +114,21 @@ mod json_formatter_tests {
         assert_eq!(original_plan.resources.len(), parsed_plan.resources.len());
         assert_eq!(original_plan.resources[0].id, parsed_plan.resources[0].id);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_format_json_output_exposes_both_summaries() {
+        let input = r#"  # aws_s3_bucket.logs will be created
+  + resource "aws_s3_bucket" "logs" {
+      + bucket = "logs-bucket"
+    }
+
+Plan: 2 to add, 0 to change, 0 to destroy."#;
+        let plan = tfdiff::parser::parse_terraform_output(input).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&format_json_output(&plan).unwrap()).unwrap();
+        
+        assert_eq!(parsed["summary"]["add"], 2);
+        assert_eq!(parsed["parsed_summary"]["add"], 1);
+        assert_eq!(parsed["warnings"][0]["kind"], "SummaryMismatch");
+        assert_eq!(parsed["warnings"][0]["level"], "Warning");
+    }
+}
@@ -0,0 +1,114 @@
+use tfdiff::formatter::junit::*;
+use tfdiff::ignore::IgnoreRules;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod junit_formatter_tests {
+    use super::*;
+    
+    fn stateful_destroy(plan: &mut TerraformPlan) {
+        plan.resources.push(sample_destroy_resource());
+        plan.annotations.push(ResourceAnnotation {
+            address: "aws_instance.legacy".to_string(),
+            class: ResourceClass::Stateful,
+            message: "stateful resource will be destroyed — data loss possible".to_string(),
+        });
+    }
+    
+    #[test]
+    fn test_format_junit_output_basic() {
+        let plan = sample_terraform_plan();
+        let output = format_junit_output(&plan);
+        
+        assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"tfdiff\" tests=\"2\" failures=\"0\" skipped=\"0\""));
+        assert!(output.contains("<testsuite name=\"terraform plan\" tests=\"2\" failures=\"0\" skipped=\"0\" errors=\"0\" time=\"0\" timestamp=\"2024-01-01T12:00:00Z\">"));
+        assert!(output.contains("<testcase name=\"aws_s3_bucket.test\" classname=\"aws_s3_bucket\" time=\"0\">"));
+        assert!(output.contains("<system-out>aws_instance.web will be updated in-place\n  instance_type</system-out>"));
+        assert!(!output.contains("<failure"));
+        assert!(!output.contains("policy checks"));
+        assert!(output.ends_with("</testsuites>\n"));
+    }
+    
+    #[test]
+    fn test_format_junit_protected_destroy() {
+        let mut plan = sample_terraform_plan();
+        stateful_destroy(&mut plan);
+        let output = format_junit_output(&plan);
+        
+        assert!(output.contains("<testsuites name=\"tfdiff\" tests=\"3\" failures=\"1\""));
+        assert!(output.contains("<failure type=\"protected-resource\" message=\"stateful resource will be destroyed — data loss possible\">"));
+    }
+    
+    #[test]
+    fn test_format_junit_policy_failures() {
+        let mut plan = sample_terraform_plan();
+        stateful_destroy(&mut plan);
+        plan.policy_results = vec![
+            PolicyResult {
+                policy: "protect-legacy".to_string(),
+                description: Some("Legacy servers stay up".to_string()),
+                outcome: PolicyOutcome::Fail,
+                violations: vec!["aws_instance.legacy will be destroyed".to_string()],
+                waived_by: None,
+                addresses: vec!["aws_instance.legacy".to_string()],
+            },
+            PolicyResult {
+                policy: "small-plans".to_string(),
+                description: None,
+                outcome: PolicyOutcome::Warn,
+                violations: vec!["3 resources change, more than the limit of 2".to_string()],
+                waived_by: None,
+                addresses: Vec::new(),
+            },
+        ];
+        let output = format_junit_output(&plan);
+        
+        // The policy failure comes first; the protected resource is listed with it
+        assert!(output.contains("<failure type=\"policy\" message=\"policy protect-legacy: aws_instance.legacy will be destroyed\">policy protect-legacy: aws_instance.legacy will be destroyed\nstateful resource will be destroyed — data loss possible</failure>"));
+        assert!(output.contains("<testsuites name=\"tfdiff\" tests=\"5\" failures=\"2\""));
+        assert!(output.contains("<testsuite name=\"policy checks\" tests=\"2\" failures=\"1\""));
+        assert!(output.contains("<failure type=\"policy\" message=\"Legacy servers stay up\">aws_instance.legacy will be destroyed</failure>"));
+        assert!(output.contains("<testcase name=\"small-plans\" classname=\"policy\" time=\"0\">\n      <system-out>3 resources change, more than the limit of 2</system-out>"));
+    }
+    
+    #[test]
+    fn test_format_junit_skips_no_ops() {
+        let mut plan = sample_terraform_plan();
+        let mut unchanged = sample_update_resource();
+        unchanged.id = "aws_instance.idle".to_string();
+        unchanged.action = ActionType::NoOp;
+        plan.resources.push(unchanged);
+        let output = format_junit_output(&plan);
+        
+        assert!(output.contains("skipped=\"1\""));
+        assert!(output.contains("<testcase name=\"aws_instance.idle\" classname=\"aws_instance\" time=\"0\">\n      <skipped message=\"no changes\"/>"));
+    }
+    
+    #[test]
+    fn test_format_junit_skips_suppressed_resources() {
+        let mut plan = parse_terraform_output(&load_fixture("ignore/noisy_plan.txt")).unwrap();
+        let tests = plan.resources.len();
+        IgnoreRules::load(&fixture_path("ignore/rules.toml")).unwrap().apply(&mut plan);
+        assert!(plan.resources.iter().all(|resource| resource.id != "aws_s3_bucket.logs"));
+        let output = format_junit_output(&plan);
+        
+        // The updates the rules emptied are still reported, as skipped
+        assert!(output.contains(&format!("<testsuite name=\"terraform plan\" tests=\"{}\" failures=\"0\" skipped=\"2\"", tests)));
+        assert!(output.contains("<testcase name=\"aws_s3_bucket.logs\" classname=\"aws_s3_bucket\" time=\"0\">\n      <skipped message=\"changes suppressed by ignore rules\"/>\n      <system-out>aws_s3_bucket.logs will be updated in-place\n  tags_all.CostCenter (ignore rule: provider default tags)</system-out>"));
+        assert!(output.contains("<system-out>aws_iam_policy.deploy will be updated in-place\n  description (whitespace only)</system-out>"));
+        // Suppressed changes of a resource that still changes do not make it skipped
+        assert!(output.contains("<testcase name=\"aws_instance.web\" classname=\"aws_instance\" time=\"0\">\n      <system-out>"));
+    }
+    
+    #[test]
+    fn test_format_junit_escapes_xml() {
+        let mut plan = sample_terraform_plan();
+        plan.resources[0].id = "aws_s3_bucket.test[\"a&b\"]".to_string();
+        let output = format_junit_output(&plan);
+        
+        assert!(output.contains("<testcase name=\"aws_s3_bucket.test[&quot;a&amp;b&quot;]\""));
+    }
+}
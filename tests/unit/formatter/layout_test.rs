@@ -0,0 +1,47 @@
+use tfdiff::formatter::layout::*;
+use colored::*;
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("t3.small"), 8);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width(&"t3.small".bright_green().bold().to_string()), 8);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("t3.small", 8), "t3.small");
+        assert_eq!(truncate_to_width("héllo wörld", 6), "héllo…");
+        assert_eq!(truncate_to_width("日本語テキスト", 6), "日本…");
+        assert_eq!(truncate_to_width("日本語テキスト", 5), "日本…");
+        assert_eq!(truncate_to_width("anything", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_to_width_keeps_grapheme_clusters() {
+        // e + combining acute accent, three times
+        assert_eq!(truncate_to_width("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+        // family emoji joined with zero-width joiners
+        assert_eq!(truncate_to_width("👨\u{200d}👩\u{200d}👧 family", 3), "👨\u{200d}👩\u{200d}👧…");
+        assert_eq!(truncate_to_width("👨\u{200d}👩\u{200d}👧 family", 2), "…");
+    }
+
+    #[test]
+    fn test_pad_to_width() {
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+        assert_eq!(pad_to_width("too long", 3), "too long");
+        assert_eq!(display_width(&pad_to_width(&"ab".red().to_string(), 5)), 5);
+    }
+
+    #[test]
+    fn test_wrap_to_width() {
+        assert_eq!(wrap_to_width("the quick brown fox", 10), vec!["the quick", "brown fox"]);
+        assert_eq!(wrap_to_width("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_to_width("日本語 テキスト", 5), vec!["日本", "語", "テキ", "スト"]);
+        assert_eq!(wrap_to_width("", 10), vec![""]);
+    }
+}
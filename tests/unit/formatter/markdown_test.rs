@@ -48,10 +48,15 @@ mod markdown_formatter_tests {
                 destroy: 0,
                 read: 2,
             },
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_markdown_output(&plan);
@@ -68,10 +73,15 @@ mod markdown_formatter_tests {
                 destroy: 1,
                 read: 0,
             },
+            parsed_summary: Summary::default(),
             resources: vec![sample_destroy_resource()],
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_markdown_output(&plan);
@@ -86,14 +96,23 @@ mod markdown_formatter_tests {
             Warning {
                 message: "This is a test warning".to_string(),
                 level: WarningLevel::Warning,
+                span: None,
+                kind: None,
+                address: None,
             },
             Warning {
                 message: "This is an error".to_string(),
                 level: WarningLevel::Error,
+                span: None,
+                kind: None,
+                address: None,
             },
             Warning {
                 message: "This is info".to_string(),
                 level: WarningLevel::Info,
+                span: None,
+                kind: None,
+                address: None,
             },
         ];
         
@@ -133,6 +152,7 @@ mod markdown_formatter_tests {
         let plan = TerraformPlan {
             mode: PlanMode::Plan,
             summary: Summary::default(),
+            parsed_summary: Summary::default(),
             resources: vec![
                 Resource {
                     id: "test.create".to_string(),
@@ -143,6 +163,8 @@ mod markdown_formatter_tests {
                     changes: Vec::new(),
                     attributes: std::collections::HashMap::new(),
                     applied: false,
+                    replace: false,
+                    span: None,
                 },
                 Resource {
                     id: "test.update".to_string(),
@@ -153,6 +175,8 @@ mod markdown_formatter_tests {
                     changes: Vec::new(),
                     attributes: std::collections::HashMap::new(),
                     applied: false,
+                    replace: false,
+                    span: None,
                 },
                 Resource {
                     id: "test.destroy".to_string(),
@@ -163,6 +187,8 @@ mod markdown_formatter_tests {
                     changes: Vec::new(),
                     attributes: std::collections::HashMap::new(),
                     applied: false,
+                    replace: false,
+                    span: None,
                 },
                 Resource {
                     id: "test.read".to_string(),
@@ -173,6 +199,8 @@ mod markdown_formatter_tests {
                     changes: Vec::new(),
                     attributes: std::collections::HashMap::new(),
                     applied: false,
+                    replace: false,
+                    span: None,
                 },
                 Resource {
                     id: "test.noop".to_string(),
@@ -183,11 +211,17 @@ mod markdown_formatter_tests {
                     changes: Vec::new(),
                     attributes: std::collections::HashMap::new(),
                     applied: false,
+                    replace: false,
+                    span: None,
                 },
             ],
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_markdown_output(&plan);
@@ -206,4 +240,93 @@ mod markdown_formatter_tests {
         assert!(output.contains("---"));
         assert!(output.contains("*Generated by tfdiff*"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_format_markdown_policy_checks() {
+        let mut plan = sample_terraform_plan();
+        plan.policy_results = vec![
+            PolicyResult {
+            policy: "protect-databases".to_string(),
+            description: Some("Databases are never destroyed".to_string()),
+            outcome: PolicyOutcome::Fail,
+            violations: vec!["aws_db_instance.main will be destroyed".to_string()],
+            waived_by: None,
+            addresses: Vec::new(),
+        },
+            PolicyResult {
+                policy: "small-plans".to_string(),
+                description: None,
+                outcome: PolicyOutcome::Pass,
+                violations: Vec::new(),
+                waived_by: None,
+                addresses: Vec::new(),
+            },
+        ];
+        let output = format_markdown_output(&plan);
+        
+        assert!(output.contains("## Policy Checks\n\n1 failed, 1 passed\n"));
+        assert!(output.contains("| ❌ fail | `protect-databases` | Databases are never destroyed<br>aws_db_instance.main will be destroyed |"));
+        assert!(output.contains("| ✅ pass | `small-plans` |  |"));
+    }
+    
+    #[test]
+    fn test_format_markdown_risk_table() {
+        let plan = sample_terraform_plan();
+        let output = format_markdown_output(&plan);
+        
+        assert!(output.contains("## Risk\n\nOverall risk: **medium** (32/100)\n"));
+        assert!(output.contains("| Resource | Action | Risk | Factors |"));
+        // Riskiest first
+        let update = output.find("| `aws_instance.web` | update | 🟠 medium (32) |").unwrap();
+        let create = output.find("| `aws_s3_bucket.test` | create | 🟢 low (12) |").unwrap();
+        assert!(update < create);
+    }
+    
+    #[test]
+    fn test_format_markdown_annotations() {
+        let mut plan = sample_terraform_plan();
+        plan.annotations = vec![ResourceAnnotation {
+            address: "aws_instance.web".to_string(),
+            class: ResourceClass::NetworkCritical,
+            message: "network-critical resource will be updated in-place — connectivity may be interrupted".to_string(),
+        }];
+        let output = format_markdown_output(&plan);
+        
+        assert!(output.contains("### 🔄 UPDATE `aws_instance.web`\n\n> 🌐 network-critical resource will be updated in-place — connectivity may be interrupted\n\n"));
+    }
+    
+    #[test]
+    fn test_format_markdown_cost() {
+        let mut plan = sample_terraform_plan();
+        plan.cost = Some(PlanCost {
+            currency: "USD".to_string(),
+            before: 1200.0,
+            after: 1260.5,
+            delta: 60.5,
+            resources: vec![
+                ResourceCost {
+                    address: "aws_instance.web".to_string(),
+                    before: Some(1200.0),
+                    after: Some(1260.5),
+                    delta: Some(60.5),
+                    note: None,
+                },
+                ResourceCost {
+                    address: "aws_instance.big".to_string(),
+                    before: Some(0.0),
+                    after: None,
+                    delta: None,
+                    note: Some("no price for instance_type \"x1e.32xlarge\"".to_string()),
+                },
+            ],
+        });
+        let output = format_markdown_output(&plan);
+        
+        assert!(output.contains("### Monthly Cost\n\n💰 $1,200.00 → $1,260.50 (+$60.50), 1 change of unknown cost\n"));
+        assert!(output.contains("| `aws_instance.web` | $1,200.00 | $1,260.50 | +$60.50 |\n"));
+        assert!(output.contains("| `aws_instance.big` | $0.00 | ? | unknown (no price for instance_type \"x1e.32xlarge\") |\n"));
+        
+        plan.cost = None;
+        assert!(!format_markdown_output(&plan).contains("Monthly Cost"));
+    }
+}
@@ -1,4 +1,13 @@
 mod terminal_test;
 mod json_test;
 mod html_test;
-mod markdown_test;
\ No newline at end of file
+mod markdown_test;
+mod junit_test;
+mod sarif_test;
+mod pr_comment_test;
+mod chat_test;
+mod registry_test;
+mod explain_test;mod grouping_test;
+mod layout_test;
+mod comparison_test;
+mod reconciliation_test;
@@ -0,0 +1,163 @@
+use tfdiff::formatter::grouping::GroupBy;
+use tfdiff::formatter::pr_comment::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use tfdiff::policy::PolicySet;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod pr_comment_formatter_tests {
+    use super::*;
+
+    fn many_attributes(count: usize) -> Resource {
+        let mut resource = sample_create_resource();
+        resource.attributes = (0..count)
+            .map(|index| (format!("tag_{:03}", index), serde_json::Value::String("x".repeat(40))))
+            .collect();
+        resource
+    }
+
+    #[test]
+    fn test_format_pr_comment_basic() {
+        let comments = format_pr_comments(&sample_terraform_plan(), GroupBy::None);
+
+        assert_eq!(comments.len(), 1);
+        let comment = &comments[0];
+        assert!(comment.starts_with("<!-- tfdiff comment 1/1 -->\n## Terraform plan: 2 to add, 1 to change\n"));
+        assert!(comment.contains("| `root module` | 1 | 1 | 0 |"));
+        assert!(comment.contains("| **Total** | **2** | **1** | **0** |"));
+        assert!(comment.contains("<summary>📦 <b>root module</b> · 1 to add, 1 to change</summary>"));
+        assert!(comment.contains("<summary>🔄 <code>aws_instance.web</code> will be updated in-place"));
+        assert!(comment.contains("```diff\n~ instance_type = \"t2.micro\" -> \"t3.small\"\n```"));
+        assert!(comment.contains("+ bucket = \"test-bucket\""));
+        assert_eq!(comment.matches("<details>").count(), comment.matches("</details>").count());
+        assert!(comment.ends_with("*Generated by tfdiff*\n"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_is_not_json() {
+        let comment = format_pr_comments(&sample_terraform_plan(), GroupBy::None).concat();
+
+        assert!(!comment.contains("```json"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_forces_replacement() {
+        let plan = parse_terraform_output(&load_fixture("terraform/complex_update.txt")).unwrap();
+        let comment = format_pr_comments(&plan, GroupBy::None).concat();
+
+        assert!(comment.contains("~ user_data = \"c765373c563b260626d113c4a56a46e8a8248872\" -> \"a123b456c789d012e345f678g901h234i567j890\" # forces replacement"));
+        assert!(comment.contains("<code>aws_instance.web_server</code>"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_banner() {
+        let mut plan = parse_terraform_output(&load_fixture("policy/guarded_plan.txt")).unwrap();
+        plan.policy_results = PolicySet::load(&fixture_path("policy/policies.toml")).unwrap().evaluate(&plan, &[]).unwrap();
+        let comment = format_pr_comments(&plan, GroupBy::None).concat();
+
+        assert!(comment.contains("> 🛑 **Policy checks failed:** `protect-databases`"));
+        assert!(comment.contains(" risk** ("));
+    }
+
+    #[test]
+    fn test_format_pr_comment_without_changes() {
+        let plan = TerraformPlan::default();
+        let comments = format_pr_comments(&plan, GroupBy::None);
+
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0].contains("## Terraform plan: no changes"));
+        assert!(!comments[0].contains("| Module |"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_splits_long_plans() {
+        let plan = parse_terraform_output(&load_fixture("edge_cases/large_plan.txt")).unwrap();
+        let limit = 2_000;
+        let comments = format_pr_comments_with_limit(&plan, GroupBy::None, limit);
+
+        assert!(comments.len() > 1);
+        for (index, comment) in comments.iter().enumerate() {
+            assert!(comment.len() <= limit, "comment {} is {} bytes", index + 1, comment.len());
+            assert!(comment.starts_with(&format!("<!-- tfdiff comment {}/{} -->\n", index + 1, comments.len())));
+            assert_eq!(comment.matches("<details>").count(), comment.matches("</details>").count());
+            if index > 0 {
+                assert!(comment.contains("## Terraform plan (continued)"));
+            }
+        }
+        assert!(comments.last().unwrap().ends_with("*Generated by tfdiff*\n"));
+        assert_eq!(comments.iter().filter(|comment| comment.contains("*Generated by tfdiff*")).count(), 1);
+
+        // Every resource is in one of the comments
+        let all = comments.concat();
+        for resource in &plan.resources {
+            assert!(all.contains(&format!("<code>{}</code>", resource.id)), "{} is missing", resource.id);
+        }
+    }
+
+    #[test]
+    fn test_format_pr_comment_cuts_oversized_resources() {
+        let mut plan = TerraformPlan::default();
+        plan.resources.push(many_attributes(200));
+        plan.summary = Summary::from_resources(&plan.resources);
+        let limit = 4_000;
+        let comments = format_pr_comments_with_limit(&plan, GroupBy::None, limit);
+
+        assert!(comments.iter().all(|comment| comment.len() <= limit));
+        let all = comments.concat();
+        assert!(all.contains("+ tag_000 = "));
+        assert!(all.contains("more changes not shown\n```"));
+        assert!(!all.contains("+ tag_199 = "));
+    }
+
+    #[test]
+    fn test_format_pr_comment_splits_warnings_and_modules() {
+        let mut plan = TerraformPlan::default();
+        plan.resources = (0..500).map(|index| {
+            let mut resource = sample_create_resource();
+            resource.id = format!("module.service_{:03}.aws_s3_bucket.test", index);
+            resource
+        }).collect();
+        plan.summary = Summary::from_resources(&plan.resources);
+        plan.warnings = (0..3_000).map(|index| Warning {
+            message: format!("Warning {}: Argument is deprecated. Use the aws_s3_bucket_versioning resource instead of the versioning block.", index),
+            level: WarningLevel::Warning,
+            span: None,
+            kind: None,
+            address: None,
+        }).collect();
+
+        for limit in [8_000, PR_COMMENT_LIMIT] {
+            let comments = format_pr_comments_with_limit(&plan, GroupBy::None, limit);
+            for (index, comment) in comments.iter().enumerate() {
+                assert!(comment.len() <= limit, "comment {} is {} bytes", index + 1, comment.len());
+                assert_eq!(comment.matches("<details>").count(), comment.matches("</details>").count());
+            }
+            assert!(comments[0].contains(" more modules | "));
+            assert!(comments[0].contains("| **Total** | **500** | **0** | **0** |"));
+
+            // Every warning is in one of the comments
+            let all = comments.concat();
+            assert_eq!(all.matches("\n- Warning ").count(), 3_000);
+            assert!(all.contains("<summary>⚠️ 3000 warnings</summary>"));
+            assert!(comments.last().unwrap().ends_with("*Generated by tfdiff*\n"));
+        }
+    }
+
+    #[test]
+    fn test_format_pr_comment_default_limit() {
+        let mut plan = TerraformPlan::default();
+        plan.resources = (0..40).map(|index| {
+            let mut resource = many_attributes(50);
+            resource.id = format!("aws_s3_bucket.bucket_{}", index);
+            resource
+        }).collect();
+        plan.summary = Summary::from_resources(&plan.resources);
+        let comments = format_pr_comments(&plan, GroupBy::Type);
+
+        assert!(comments.len() > 1);
+        assert!(comments.iter().all(|comment| comment.chars().count() <= PR_COMMENT_LIMIT));
+        assert!(comments[1].contains("<summary>📦 <b>aws_s3_bucket</b>"));
+    }
+}
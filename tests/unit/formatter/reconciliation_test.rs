@@ -0,0 +1,76 @@
+use tfdiff::reconcile::*;
+use tfdiff::formatter::*;
+use tfdiff::parser::cleaner::clean_ansi_codes;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod reconciliation_formatter_tests {
+    use super::*;
+    
+    fn sample_reconciliation(apply: &str) -> Reconciliation {
+        let plan = parse_terraform_output(&load_fixture("reconcile/reviewed_plan.txt")).unwrap();
+        let apply = parse_terraform_output(&load_fixture(apply)).unwrap();
+        reconcile(&plan, &apply).unwrap()
+    }
+    
+    #[test]
+    fn test_reconciliation_terminal_output() {
+        let output = clean_ansi_codes(&format_reconciliation_terminal_output(&sample_reconciliation("reconcile/apply_diverged.txt")));
+        
+        assert!(output.contains("TERRAFORM APPLY RECONCILIATION"));
+        assert!(output.contains("Apply diverged from the plan"));
+        assert!(output.contains("✘ CREATE aws_iam_role.app"));
+        assert!(output.contains("EntityAlreadyExists"));
+        assert!(output.contains("planned → applied: DESTROY → UPDATE"));
+        assert!(output.contains("! CREATE aws_sqs_queue.jobs"));
+    }
+    
+    #[test]
+    fn test_reconciliation_terminal_output_with_width() {
+        let reconciliation = sample_reconciliation("reconcile/apply_diverged.txt");
+        let output = clean_ansi_codes(&format_reconciliation_terminal_output_with_width(&reconciliation, 60));
+        let lines: Vec<&str> = output.lines().collect();
+        
+        assert_eq!(display_width(lines[0]), 60);
+        assert!(lines[1].contains("TERRAFORM APPLY RECONCILIATION"));
+        assert!(output.contains(&format!("💥 FAILED\n{}\n", "─".repeat(60))));
+        assert!(!output.contains(&"─".repeat(61)));
+    }
+    
+    #[test]
+    fn test_reconciliation_terminal_output_consistent() {
+        let output = clean_ansi_codes(&format_reconciliation_terminal_output(&sample_reconciliation("reconcile/apply_success.txt")));
+        assert!(output.contains("Apply matched the plan (4 resources)"));
+    }
+    
+    #[test]
+    fn test_reconciliation_json_output() {
+        let output = format_reconciliation_json_output(&sample_reconciliation("reconcile/apply_diverged.txt")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        
+        assert_eq!(json["failed"][0]["address"], "aws_iam_role.app");
+        assert_eq!(json["action_mismatches"][0]["planned"], "Destroy");
+        assert_eq!(json["unplanned"][0]["id"], "aws_sqs_queue.jobs");
+    }
+    
+    #[test]
+    fn test_reconciliation_markdown_output() {
+        let output = format_reconciliation_markdown_output(&sample_reconciliation("reconcile/apply_diverged.txt"));
+        
+        assert!(output.contains("# Terraform Apply Reconciliation"));
+        assert!(output.contains("| Planned | 2 | 1 | 1 |"));
+        assert!(output.contains("- CREATE `aws_iam_role.app`"));
+        assert!(output.contains("| `aws_security_group.legacy` | DESTROY | UPDATE |"));
+        assert!(output.contains("## ⚠️ Not in the Reviewed Plan"));
+    }
+    
+    #[test]
+    fn test_reconciliation_html_output() {
+        let output = format_reconciliation_html_output(&sample_reconciliation("reconcile/apply_diverged.txt"));
+        
+        assert!(output.contains("<!DOCTYPE html>"));
+        assert!(output.contains("Apply diverged from the plan"));
+        assert!(output.contains("aws_iam_role.app"));
+    }
+}
@@ -0,0 +1,171 @@
+use tfdiff::formatter::grouping::GroupBy;
+use tfdiff::formatter::registry::*;
+use tfdiff::ignore::IgnoreRules;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use tfdiff::{Result, TfdiffError};
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+    
+    /// One line per resource, as a library user might add.
+    struct AddressList;
+    
+    impl Formatter for AddressList {
+        fn name(&self) -> &str {
+            "addresses"
+        }
+        
+        fn description(&self) -> &str {
+            "Resource addresses, one per line"
+        }
+        
+        fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+            Ok(plan.resources.iter().map(|resource| resource.id.as_str()).collect::<Vec<_>>().join("\n"))
+        }
+    }
+    
+    /// A replacement for a built-in format.
+    struct ShortJson;
+    
+    impl Formatter for ShortJson {
+        fn name(&self) -> &str {
+            "json"
+        }
+        
+        fn description(&self) -> &str {
+            "Summary counts as JSON"
+        }
+        
+        fn format(&self, plan: &TerraformPlan, _options: &FormatOptions) -> Result<String> {
+            Ok(serde_json::to_string(&plan.summary)?)
+        }
+    }
+    
+    #[test]
+    fn test_builtin_formats() {
+        let registry = FormatterRegistry::builtin();
+        
+        assert_eq!(registry.names(), vec!["terminal", "json", "html", "markdown", "junit", "sarif", "pr-comment", "slack", "teams"]);
+        assert!(registry.formatters().all(|formatter| !formatter.description().is_empty()));
+        assert!(FormatterRegistry::empty().names().is_empty());
+    }
+    
+    #[test]
+    fn test_builtin_formats_match_functions() {
+        let registry = FormatterRegistry::builtin();
+        let plan = sample_terraform_plan();
+        let options = FormatOptions { group_by: GroupBy::Module, ..Default::default() };
+        
+        assert_eq!(registry.format("json", &plan, &options).unwrap(), tfdiff::formatter::format_json_output(&plan).unwrap());
+        assert_eq!(registry.format("markdown", &plan, &options).unwrap(), tfdiff::formatter::format_markdown_output_grouped(&plan, GroupBy::Module));
+        assert_eq!(registry.format("junit", &plan, &options).unwrap(), tfdiff::formatter::format_junit_output(&plan));
+    }
+    
+    #[test]
+    fn test_register_custom_format() {
+        let mut registry = FormatterRegistry::builtin();
+        registry.register(AddressList);
+        
+        assert_eq!(registry.names().last(), Some(&"addresses"));
+        let output = registry.format("addresses", &sample_terraform_plan(), &FormatOptions::default()).unwrap();
+        assert_eq!(output, "aws_s3_bucket.test\naws_instance.web");
+    }
+    
+    #[test]
+    fn test_register_replaces_format() {
+        let mut registry = FormatterRegistry::builtin();
+        registry.register(ShortJson);
+        
+        assert_eq!(registry.names().len(), 9);
+        assert_eq!(registry.names()[1], "json");
+        assert_eq!(registry.get("json").unwrap().description(), "Summary counts as JSON");
+    }
+    
+    #[test]
+    fn test_unknown_format() {
+        let mut registry = FormatterRegistry::empty();
+        registry.register(AddressList);
+        
+        let Err(TfdiffError::ConfigError(message)) = registry.format("pdf", &sample_terraform_plan(), &FormatOptions::default()) else {
+            panic!("expected an unknown format error");
+        };
+        assert_eq!(message, "unknown format 'pdf' (expected addresses)");
+    }
+    
+    #[test]
+    fn test_terminal_options() {
+        let registry = FormatterRegistry::builtin();
+        let plan = sample_terraform_plan();
+        
+        let quiet = FormatOptions { verbosity: Verbosity::Quiet, ..Default::default() };
+        assert_eq!(registry.format("terminal", &plan, &quiet).unwrap(), "Summary: 2 to add, 1 to change, 0 to destroy");
+        
+        let plain = registry.format("terminal", &plan, &FormatOptions { color: false, ..Default::default() }).unwrap();
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.contains("aws_instance.web"));
+    }
+    
+    /// The noisy plan with the tags_all and known-after-apply changes suppressed.
+    fn suppressed_plan() -> TerraformPlan {
+        let mut plan = parse_terraform_output(&load_fixture("ignore/noisy_plan.txt")).unwrap();
+        IgnoreRules::load(&fixture_path("ignore/rules.toml")).unwrap().apply(&mut plan);
+        assert!(!plan.suppressed.is_empty());
+        plan
+    }
+    
+    #[test]
+    fn test_quiet_markdown_and_html() {
+        let registry = FormatterRegistry::builtin();
+        let plan = sample_terraform_plan();
+        let quiet = FormatOptions { verbosity: Verbosity::Quiet, ..Default::default() };
+        
+        let markdown = registry.format("markdown", &plan, &quiet).unwrap();
+        assert!(markdown.contains("## Summary"));
+        assert!(markdown.contains("*Generated by tfdiff*"));
+        assert!(!markdown.contains("## Resource Changes"));
+        assert!(!markdown.contains("aws_instance.web"));
+        
+        let html = registry.format("html", &plan, &quiet).unwrap();
+        assert!(html.contains("Plan Summary"));
+        assert!(html.contains("Overall risk"));
+        assert!(!html.contains(r#"<div class="resource-section"#));
+        assert!(!html.contains(r#"<select class="resource-sort">"#));
+        assert!(html.ends_with("</html>"));
+    }
+    
+    #[test]
+    fn test_verbose_lists_suppressed_changes() {
+        let registry = FormatterRegistry::builtin();
+        let plan = suppressed_plan();
+        let verbose = FormatOptions { verbosity: Verbosity::Verbose, color: false, width: Some(120), ..Default::default() };
+        let entry = &plan.suppressed[0];
+        
+        let terminal = registry.format("terminal", &plan, &verbose).unwrap();
+        assert!(terminal.contains(&format!("· {} {} ({})", entry.address, entry.path, entry.reason)));
+        let markdown = registry.format("markdown", &plan, &verbose).unwrap();
+        assert!(markdown.contains(&format!("- `{}` `{}` ({})", entry.address, entry.path, entry.reason)));
+        let html = registry.format("html", &plan, &verbose).unwrap();
+        assert!(html.contains(r#"<ul class="suppressed-list">"#));
+        
+        // Normal reports only count them
+        let normal = FormatOptions { color: false, width: Some(120), ..Default::default() };
+        assert!(!registry.format("terminal", &plan, &normal).unwrap().contains(&format!("· {}", entry.address)));
+        assert!(!registry.format("markdown", &plan, &normal).unwrap().contains(&format!("- `{}`", entry.address)));
+        assert!(!registry.format("html", &plan, &normal).unwrap().contains("<ul class=\"suppressed-list\">"));
+    }
+    
+    #[test]
+    fn test_sarif_source_option() {
+        let registry = FormatterRegistry::builtin();
+        let mut plan = sample_terraform_plan();
+        plan.resources[1].span = Some(SourceSpan { start_line: 3, end_line: 9, prefix: None });
+        let options = FormatOptions { source: Some("plan.txt".into()), ..Default::default() };
+        
+        let output = registry.format("sarif", &plan, &options).unwrap();
+        assert!(output.contains("\"uri\": \"plan.txt\""));
+    }
+}
@@ -0,0 +1,108 @@
+use tfdiff::formatter::sarif::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+use serde_json::{json, Value};
+use std::path::Path;
+
+#[cfg(test)]
+mod sarif_formatter_tests {
+    use super::*;
+    
+    fn sarif(plan: &TerraformPlan, artifact: Option<&str>) -> Value {
+        serde_json::from_str(&format_sarif_output(plan, artifact.map(Path::new)).unwrap()).unwrap()
+    }
+    
+    fn results<'a>(log: &'a Value, rule_id: &str) -> Vec<&'a Value> {
+        log["runs"][0]["results"].as_array().unwrap().iter()
+            .filter(|result| result["ruleId"] == rule_id)
+            .collect()
+    }
+    
+    #[test]
+    fn test_format_sarif_log() {
+        let log = sarif(&sample_terraform_plan(), None);
+        
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "tfdiff");
+        // The instance update is medium risk; nothing else is reported
+        let medium = results(&log, "risk/medium");
+        assert_eq!(medium.len(), 1);
+        assert_eq!(medium[0]["level"], "note");
+        assert_eq!(medium[0]["locations"], json!([{
+            "logicalLocations": [{ "fullyQualifiedName": "aws_instance.web", "kind": "resource" }],
+        }]));
+        assert_eq!(log["runs"][0]["results"].as_array().unwrap().len(), 1);
+        assert_eq!(log["runs"][0]["tool"]["driver"]["rules"], json!([{
+            "id": "risk/medium",
+            "shortDescription": { "text": "Medium risk change" },
+            "defaultConfiguration": { "level": "note" },
+        }]));
+    }
+    
+    #[test]
+    fn test_format_sarif_policy_violations() {
+        let log = sarif(&guarded_plan_with_policies(&[]), Some("plan.txt"));
+        
+        let databases = results(&log, "policy/protect-databases");
+        assert_eq!(databases.len(), 1);
+        assert_eq!(databases[0]["level"], "error");
+        assert_eq!(databases[0]["message"]["text"], "protect-databases: aws_db_instance.main will be replaced");
+        assert_eq!(databases[0]["locations"][0]["physicalLocation"], json!({
+            "artifactLocation": { "uri": "plan.txt", "uriBaseId": "%SRCROOT%" },
+            "region": { "startLine": 9, "endLine": 14 },
+        }));
+        
+        // A change limit is not about one resource
+        let small = results(&log, "policy/small-plans");
+        assert_eq!(small[0]["level"], "warning");
+        assert_eq!(small[0]["locations"], json!([]));
+        
+        let high = results(&log, "risk/high");
+        assert!(high.iter().any(|result| result["message"]["text"].as_str().unwrap().starts_with("aws_db_instance.main will be replaced (risk ")));
+    }
+    
+    #[test]
+    fn test_format_sarif_artifact_uris() {
+        let plan = guarded_plan_with_policies(&[]);
+        let uri = |artifact: &str| {
+            let log = sarif(&plan, Some(artifact));
+            results(&log, "policy/protect-databases")[0]["locations"][0]["physicalLocation"]["artifactLocation"].clone()
+        };
+        
+        // Under the working directory, absolute or not, the path is relative to %SRCROOT%
+        let cwd = std::env::current_dir().unwrap();
+        let inside = cwd.join("plans").join("prod plan #1.txt");
+        assert_eq!(uri(inside.to_str().unwrap()), json!({ "uri": "plans/prod%20plan%20%231.txt", "uriBaseId": "%SRCROOT%" }));
+        assert_eq!(uri("./plans/../plan.txt"), json!({ "uri": "plan.txt", "uriBaseId": "%SRCROOT%" }));
+        
+        // Anywhere else it is a file:// URI
+        let outside = cwd.parent().unwrap().join("other plans").join("plan.txt");
+        let location = uri(outside.to_str().unwrap());
+        assert!(location.get("uriBaseId").is_none());
+        let outside_uri = location["uri"].as_str().unwrap();
+        assert!(outside_uri.starts_with("file:///"));
+        assert!(outside_uri.ends_with("/other%20plans/plan.txt"));
+    }
+    
+    #[test]
+    fn test_format_sarif_waived_policy() {
+        let log = sarif(&guarded_plan_with_policies(&["iam-approved".to_string()]), None);
+        
+        let iam = results(&log, "policy/iam-review");
+        assert_eq!(iam[0]["suppressions"], json!([{ "kind": "external", "justification": "allowed by label iam-approved" }]));
+    }
+    
+    #[test]
+    fn test_format_sarif_diagnostics() {
+        let plan = parse_terraform_output(&load_fixture("malformed/incomplete_plan.txt")).unwrap();
+        let log = sarif(&plan, Some("incomplete_plan.txt"));
+        
+        let braces = results(&log, "parser/unbalanced-braces");
+        assert_eq!(braces.len(), 1);
+        assert_eq!(braces[0]["locations"][0]["physicalLocation"]["region"], json!({ "startLine": 8, "endLine": 18 }));
+        let truncated = results(&log, "parser/truncated-input");
+        assert_eq!(truncated[0]["locations"][0]["physicalLocation"]["region"], json!({ "startLine": 18, "endLine": 18 }));
+    }
+}
@@ -1,4 +1,6 @@
 use tfdiff::formatter::terminal::*;
+use tfdiff::formatter::grouping::GroupBy;
+use tfdiff::formatter::layout::display_width;
 use tfdiff::models::*;
 use tfdiff::parser::cleaner::clean_ansi_codes;
 use crate::common::test_data::*;
@@ -33,10 +35,15 @@ mod terminal_formatter_tests {
         let plan = TerraformPlan {
             mode: PlanMode::Plan,
             summary: Summary::default(),
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_terminal_output(&plan);
@@ -55,6 +62,7 @@ mod terminal_formatter_tests {
                 destroy: 2,
                 read: 0,
             },
+            parsed_summary: Summary::default(),
             resources: vec![
                 sample_create_resource(),
                 sample_destroy_resource(),
@@ -62,6 +70,10 @@ mod terminal_formatter_tests {
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_terminal_output(&plan);
@@ -81,10 +93,15 @@ mod terminal_formatter_tests {
                 destroy: 0,
                 read: 3,
             },
+            parsed_summary: Summary::default(),
             resources: Vec::new(),
             data_sources: Vec::new(),
             warnings: Vec::new(),
             metadata: Metadata::default(),
+            suppressed: Vec::new(),
+            policy_results: Vec::new(),
+            annotations: Vec::new(),
+            cost: None,
         };
         
         let output = format_terminal_output(&plan);
@@ -173,4 +190,167 @@ mod terminal_formatter_tests {
         assert!(clean_ansi_codes(&formatted).contains("1 to destroy"));
         assert!(!clean_ansi_codes(&formatted).contains("to read"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_format_terminal_policy_checks() {
+        let mut plan = sample_terraform_plan();
+        plan.policy_results = vec![PolicyResult {
+            policy: "iam-review".to_string(),
+            description: None,
+            outcome: PolicyOutcome::Pass,
+            violations: vec!["aws_iam_role.deploy will be updated in-place".to_string()],
+            waived_by: Some("iam-approved".to_string()),
+            addresses: Vec::new(),
+        }];
+        let output = clean_ansi_codes(&format_terminal_output(&plan));
+        
+        assert!(output.contains("POLICY CHECKS"));
+        assert!(output.contains("✓ PASS iam-review (allowed by label iam-approved)"));
+        assert!(output.contains("• aws_iam_role.deploy will be updated in-place"));
+        assert_eq!(format_policy_counts(&plan.policy_results).as_deref(), Some("1 passed"));
+    }
+    
+    #[test]
+    fn test_format_terminal_risk_badge() {
+        let plan = sample_terraform_plan();
+        let output = clean_ansi_codes(&format_terminal_output(&plan));
+        
+        assert!(output.contains("RISK: MEDIUM (32/100)"));
+    }
+    
+    #[test]
+    fn test_format_terminal_annotations() {
+        let mut plan = sample_terraform_plan();
+        plan.annotations = vec![ResourceAnnotation {
+            address: "aws_instance.web".to_string(),
+            class: ResourceClass::NetworkCritical,
+            message: "network-critical resource will be updated in-place — connectivity may be interrupted".to_string(),
+        }];
+        let output = clean_ansi_codes(&format_terminal_output(&plan));
+        
+        assert!(output.contains("  Provider: aws\n  🌐 network-critical resource will be updated in-place — connectivity may be interrupted\n"));
+    }
+    
+    #[test]
+    fn test_format_money() {
+        assert_eq!(format_money(1234567.891, "USD"), "$1,234,567.89");
+        assert_eq!(format_money(-32.85, "USD"), "-$32.85");
+        assert_eq!(format_money(0.0, "EUR"), "€0.00");
+        assert_eq!(format_money(999.999, "CHF"), "1,000.00 CHF");
+        assert_eq!(format_money_change(15.18, "GBP"), "+£15.18");
+        assert_eq!(format_money_change(-0.4, "USD"), "-$0.40");
+    }
+    
+    #[test]
+    fn test_format_terminal_cost() {
+        let mut plan = sample_terraform_plan();
+        plan.cost = Some(PlanCost {
+            currency: "USD".to_string(),
+            before: 1200.0,
+            after: 1260.5,
+            delta: 60.5,
+            resources: vec![
+                ResourceCost {
+                    address: "aws_instance.web".to_string(),
+                    before: Some(1200.0),
+                    after: Some(1260.5),
+                    delta: Some(60.5),
+                    note: None,
+                },
+                ResourceCost {
+                    address: "aws_instance.big".to_string(),
+                    before: Some(0.0),
+                    after: None,
+                    delta: None,
+                    note: Some("no price for instance_type \"x1e.32xlarge\"".to_string()),
+                },
+            ],
+        });
+        let output = clean_ansi_codes(&format_terminal_output(&plan));
+        
+        assert!(output.contains("💰 Monthly cost: $1,200.00 → $1,260.50 (+$60.50), 1 change of unknown cost\n"));
+        assert!(output.contains("      aws_instance.web  $1,200.00 → $1,260.50 (+$60.50)\n"));
+        assert!(output.contains("      aws_instance.big  unknown cost: no price for instance_type \"x1e.32xlarge\"\n"));
+    }
+    
+    fn wide_value_plan() -> TerraformPlan {
+        let mut plan = sample_terraform_plan();
+        plan.resources[1].changes.push(Change {
+            path: vec!["tags".to_string(), "Description".to_string()],
+            before: Some(serde_json::json!("本番環境のウェブサーバー、東京リージョンで稼働しています")),
+            after: Some(serde_json::json!("ステージング環境のウェブサーバー — マルチバイト文字を含む長い説明文")),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        });
+        plan.warnings = vec![Warning {
+            message: "Argument is deprecated: use the aws_s3_bucket_versioning resource instead of the versioning block in aws_s3_bucket".to_string(),
+            level: WarningLevel::Warning,
+            span: None,
+            kind: None,
+            address: None,
+        }];
+        plan
+    }
+    
+    #[test]
+    fn test_format_terminal_output_fits_width() {
+        let plan = wide_value_plan();
+        for width in [40, 60, 80, 132] {
+            let output = clean_ansi_codes(&format_terminal_output_with_width(&plan, GroupBy::None, width));
+            
+            assert!(output.starts_with(&format!("╔{}╗\n", "═".repeat(width - 2))));
+            for line in output.lines() {
+                assert!(display_width(line) <= width, "{} columns at width {}: {}", display_width(line), width, line);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_format_terminal_output_centers_header() {
+        let output = clean_ansi_codes(&format_terminal_output_with_width(&sample_terraform_plan(), GroupBy::None, 100));
+        let title = output.lines().nth(1).unwrap();
+        
+        assert_eq!(display_width(title), 100);
+        assert!(title.starts_with(&format!("║{}TERRAFORM PLAN ANALYSIS", " ".repeat(37))));
+    }
+    
+    #[test]
+    fn test_format_terminal_output_minimum_width() {
+        let plan = sample_terraform_plan();
+        
+        assert_eq!(
+            format_terminal_output_with_width(&plan, GroupBy::None, 10),
+            format_terminal_output_with_width(&plan, GroupBy::None, 40)
+        );
+    }
+    
+    #[test]
+    fn test_format_resource_truncates_multibyte_values() {
+        let plan = wide_value_plan();
+        let narrow = clean_ansi_codes(&format_resource_with_width(&plan.resources[1], &[], 60));
+        let wide = clean_ansi_codes(&format_resource_with_width(&plan.resources[1], &[], 200));
+        
+        assert!(narrow.contains("    ─ \"本番環境のウェブサーバ… │ + \"ステージング環境のウェ…\n"));
+        assert!(wide.contains("\"本番環境のウェブサーバー、東京リージョンで稼働しています\""));
+        assert!(wide.contains("\"ステージング環境のウェブサーバー — マルチバイト文字を含む長い説明文\"\n"));
+    }
+    
+    #[test]
+    fn test_format_resource_splits_arrow_attributes_by_width() {
+        let mut resource = sample_update_resource();
+        resource.changes.clear();
+        resource.attributes.insert("description".to_string(), serde_json::json!("Ünïcödé description → ñew déscription with àccents everywhere"));
+        let formatted = clean_ansi_codes(&format_resource_with_width(&resource, &[], 50));
+        
+        assert!(formatted.contains("  ~ description\n    ─ Ünïcödé description │ + ñew déscription wi…\n"));
+    }
+    
+    #[test]
+    fn test_format_terminal_output_wraps_warnings() {
+        let output = clean_ansi_codes(&format_terminal_output_with_width(&wide_value_plan(), GroupBy::None, 60));
+        
+        assert!(output.contains("  • Argument is deprecated: use the aws_s3_bucket_versioning\n    resource instead of the versioning block in\n    aws_s3_bucket\n"));
+    }
+}
@@ -0,0 +1,186 @@
+use tfdiff::ignore::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+use serde_json::json;
+
+#[cfg(test)]
+mod ignore_tests {
+    use super::*;
+    
+    fn noisy_plan() -> TerraformPlan {
+        parse_terraform_output(&load_fixture("ignore/noisy_plan.txt")).unwrap()
+    }
+    
+    fn change(path: &str, before: serde_json::Value, after: serde_json::Value) -> Change {
+        Change {
+            path: path.split('.').map(str::to_string).collect(),
+            before: Some(before),
+            after: Some(after),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        }
+    }
+    
+    #[test]
+    fn test_load_rules_file() {
+        let rules = IgnoreRules::load(&fixture_path("ignore/rules.toml")).unwrap();
+        
+        assert!(rules.whitespace_only);
+        assert!(rules.unknown_only_updates);
+        assert!(!rules.order_only_lists);
+        assert_eq!(rules.rules, vec![IgnoreRule {
+            address: None,
+            attribute: Some("tags_all".to_string()),
+            reason: Some("provider default tags".to_string()),
+        }]);
+    }
+    
+    #[test]
+    fn test_apply_rules_file() {
+        let rules = IgnoreRules::load(&fixture_path("ignore/rules.toml")).unwrap();
+        let mut plan = noisy_plan();
+        rules.apply(&mut plan);
+        
+        // The bucket and policy only had noise, so they are gone
+        assert_eq!(plan.resources.len(), 1);
+        assert_eq!(plan.summary.change, 1);
+        
+        let web = &plan.resources[0];
+        let paths: Vec<String> = web.changes.iter().map(|change| change.path.join(".")).collect();
+        assert_eq!(paths, vec!["instance_type", "tags.Environment"]);
+        
+        let suppressed: Vec<(&str, &str, &SuppressionReason)> = plan.suppressed.iter()
+            .map(|s| (s.address.as_str(), s.path.as_str(), &s.reason))
+            .collect();
+        let tags = SuppressionReason::Rule { description: "provider default tags".to_string() };
+        assert_eq!(suppressed, vec![
+            ("aws_instance.web", "public_dns", &SuppressionReason::UnknownOnly),
+            ("aws_instance.web", "tags_all.Environment", &tags),
+            ("aws_s3_bucket.logs", "tags_all.CostCenter", &tags),
+            ("aws_iam_policy.deploy", "description", &SuppressionReason::WhitespaceOnly),
+        ]);
+    }
+    
+    #[test]
+    fn test_address_rules() {
+        let rules = IgnoreRules::from_toml(r#"
+            [[rule]]
+            address = "aws_instance.*"
+            attribute = "tags"
+        "#).unwrap();
+        let mut plan = noisy_plan();
+        rules.apply(&mut plan);
+        
+        // `tags` covers `tags.Environment` but not `tags_all`, and only on instances
+        let web = plan.resources.iter().find(|r| r.id == "aws_instance.web").unwrap();
+        assert!(web.changes.iter().all(|change| change.path[0] != "tags"));
+        assert!(web.changes.iter().any(|change| change.path[0] == "tags_all"));
+        assert_eq!(plan.suppressed.len(), 1);
+        assert_eq!(plan.suppressed[0].reason, SuppressionReason::Rule { description: "tags on aws_instance.*".to_string() });
+        assert_eq!(plan.resources.len(), 3);
+    }
+    
+    #[test]
+    fn test_rules_apply_to_create_attributes() {
+        let rules = IgnoreRules::from_toml("[[rule]]\nattribute = \"force_destroy\"").unwrap();
+        let mut plan = sample_terraform_plan();
+        rules.apply(&mut plan);
+        
+        let bucket = plan.resources.iter().find(|r| r.action == ActionType::Create).unwrap();
+        assert!(!bucket.attributes.contains_key("force_destroy"));
+        assert!(bucket.attributes.contains_key("bucket"));
+        assert_eq!(plan.suppressed.len(), 1);
+        // Creates are never collapsed away
+        assert_eq!(plan.resources.len(), 2);
+    }
+    
+    #[test]
+    fn test_order_only_lists() {
+        let rules = IgnoreRules { order_only_lists: true, ..Default::default() };
+        let reason = |change: &Change| rules.suppression_reason("aws_instance.web", "aws_instance", &ActionType::Update, change);
+        
+        assert_eq!(reason(&change("security_groups", json!(["sg-1", "sg-2"]), json!(["sg-2", "sg-1"]))), Some(SuppressionReason::OrderOnlyList));
+        assert_eq!(reason(&change("security_groups", json!(["sg-1", "sg-2"]), json!(["sg-1", "sg-3"]))), None);
+        assert_eq!(reason(&change("security_groups", json!(["sg-1", "sg-1"]), json!(["sg-1"]))), None);
+        assert_eq!(reason(&change("security_groups", json!(["sg-1"]), json!(["sg-1"]))), None);
+    }
+    
+    #[test]
+    fn test_order_only_lists_in_text_plans() {
+        let rules = IgnoreRules { order_only_lists: true, ..Default::default() };
+        let mut plan = parse_terraform_output(&load_fixture("ignore/reordered_list.txt")).unwrap();
+        rules.apply(&mut plan);
+        
+        // The reordered security groups were the instance's only change
+        let addresses: Vec<&str> = plan.resources.iter().map(|resource| resource.id.as_str()).collect();
+        assert_eq!(addresses, vec!["aws_security_group.app"]);
+        assert_eq!(plan.suppressed.len(), 1);
+        assert_eq!(plan.suppressed[0].path, "vpc_security_group_ids");
+        assert_eq!(plan.suppressed[0].reason, SuppressionReason::OrderOnlyList);
+        
+        let tags = plan.resources[0].changes.iter().find(|change| change.path == ["tags"]).unwrap();
+        assert_eq!(tags.after, Some(json!(["team-b"])));
+    }
+    
+    #[test]
+    fn test_whitespace_and_unknown_only() {
+        let rules = IgnoreRules { whitespace_only: true, unknown_only_updates: true, ..Default::default() };
+        let reason = |action: ActionType, change: &Change| rules.suppression_reason("aws_instance.web", "aws_instance", &action, change);
+        
+        assert_eq!(reason(ActionType::Update, &change("user_data", json!("echo hi\n"), json!("echo  hi"))), Some(SuppressionReason::WhitespaceOnly));
+        assert_eq!(reason(ActionType::Update, &change("user_data", json!("echo hi"), json!("echo ho"))), None);
+        assert_eq!(reason(ActionType::Update, &change("arn", json!("arn:1"), json!("(known after apply)"))), Some(SuppressionReason::UnknownOnly));
+        // Unknown values are what a create is made of
+        assert_eq!(reason(ActionType::Create, &change("arn", json!(null), json!("(known after apply)"))), None);
+    }
+    
+    #[test]
+    fn test_collapsing_keeps_summary_mismatch() {
+        // Terraform reported one more change than the plan shows
+        let input = load_fixture("ignore/noisy_plan.txt").replace("0 to add, 3 to change", "0 to add, 4 to change");
+        let mut plan = parse_terraform_output(&input).unwrap();
+        assert_eq!((plan.summary.change, plan.parsed_summary.change), (4, 3));
+        
+        IgnoreRules::load(&fixture_path("ignore/rules.toml")).unwrap().apply(&mut plan);
+        
+        // Both counts lose the two collapsed updates, so they still disagree
+        assert_eq!((plan.summary.change, plan.parsed_summary.change), (2, 1));
+        assert!(!plan.summary.matches_counts(&plan.parsed_summary));
+    }
+    
+    #[test]
+    fn test_replacements_are_never_collapsed() {
+        let rules = IgnoreRules { unknown_only_updates: true, ..Default::default() };
+        let mut plan = parse_terraform_output(&load_fixture("ignore/replaced_database.txt")).unwrap();
+        rules.apply(&mut plan);
+        
+        // Every diff is unknown, but the database is still destroyed and created
+        assert_eq!(plan.resources.len(), 1);
+        assert_eq!(plan.resources[0].id, "aws_db_instance.main");
+        assert!(plan.resources[0].replace);
+        assert_eq!((plan.summary.add, plan.summary.change, plan.summary.destroy), (1, 0, 1));
+        assert_eq!(plan.suppressed.len(), 3);
+    }
+    
+    #[test]
+    fn test_empty_rules_leave_plan_alone() {
+        let mut plan = noisy_plan();
+        IgnoreRules::default().apply(&mut plan);
+        
+        assert_eq!(plan.resources.len(), 3);
+        assert!(plan.suppressed.is_empty());
+    }
+    
+    #[test]
+    fn test_invalid_rules() {
+        let error = IgnoreRules::from_toml("[[rule]]\nreason = \"noise\"").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid configuration: rule 1 would ignore every change; give it an address or an attribute");
+        
+        let error = IgnoreRules::from_toml("order_only_lists = true\nwhitespace = true").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid configuration: line 2: unknown field `whitespace`"));
+    }
+}
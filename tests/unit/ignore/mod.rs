@@ -0,0 +1 @@
+mod ignore_test;
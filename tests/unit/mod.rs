@@ -1,3 +1,16 @@
 pub mod parser;
 pub mod formatter; 
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod compare;
+pub mod reconcile;
+pub mod query;
+pub mod ignore;
+pub mod semantic;
+pub mod redact;
+pub mod config;
+pub mod policy;
+pub mod risk;
+pub mod catalog;
+pub mod cost;
+pub mod template;
+pub mod ui;
\ No newline at end of file
@@ -101,6 +101,8 @@ mod models_serialization_tests {
             after: Some(serde_json::Value::String("t3.small".to_string())),
             sensitive: false,
             computed: false,
+            forces_replacement: false,
+            span: None,
         };
         
         let json = serde_json::to_string(&change).unwrap();
@@ -134,6 +136,9 @@ mod models_serialization_tests {
         let warning = Warning {
             message: "Test warning message".to_string(),
             level: WarningLevel::Warning,
+            span: None,
+            kind: None,
+            address: None,
         };
         
         let json = serde_json::to_string(&warning).unwrap();
@@ -179,4 +184,44 @@ mod models_serialization_tests {
         assert_eq!(plan.data_sources.len(), 0);
         assert_eq!(plan.warnings.len(), 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_source_span_serialization() {
+        let mut resource = sample_create_resource();
+        resource.span = Some(SourceSpan {
+            start_line: 4,
+            end_line: 9,
+            prefix: Some("base14-stack".to_string()),
+        });
+        
+        let json = serde_json::to_value(&resource).unwrap();
+        assert_eq!(json["span"]["start_line"], 4);
+        assert_eq!(json["span"]["end_line"], 9);
+        assert_eq!(json["span"]["prefix"], "base14-stack");
+        
+        let deserialized: Resource = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.span, resource.span);
+    }
+    
+    #[test]
+    fn test_source_span_defaults_when_missing() {
+        let json = r#"{"path":["bucket"],"before":null,"after":"b","sensitive":false,"computed":false}"#;
+        let change: Change = serde_json::from_str(json).unwrap();
+        assert_eq!(change.span, None);
+    }
+    
+    #[test]
+    fn test_summary_from_resources() {
+        let resources = vec![
+            sample_create_resource(),
+            sample_update_resource(),
+            sample_destroy_resource(),
+            sample_create_resource(),
+        ];
+        
+        let summary = Summary::from_resources(&resources);
+        assert_eq!(summary, Summary { add: 2, change: 1, destroy: 1, read: 0 });
+        assert!(summary.matches_counts(&Summary { add: 2, change: 1, destroy: 1, read: 3 }));
+        assert!(!summary.matches_counts(&Summary { add: 2, change: 0, destroy: 1, read: 0 }));
+    }
+}
@@ -0,0 +1,101 @@
+use tfdiff::parser::*;
+use tfdiff::models::*;
+use crate::common::fixtures::*;
+use crate::common::assertions::*;
+
+#[cfg(test)]
+mod apply_parser_tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_apply_progress() {
+        let lines = vec![
+            "aws_s3_bucket.logs: Creating...",
+            "aws_s3_bucket.logs: Still creating... [10s elapsed]",
+            "aws_s3_bucket.logs: Creation complete after 12s [id=logs]",
+            "aws_instance.web: Modifying... [id=i-0abc123]",
+        ];
+        let progress = parse_apply_progress(&lines);
+        
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].address, "aws_s3_bucket.logs");
+        assert_eq!(progress[0].action, ActionType::Create);
+        assert!(progress[0].completed);
+        assert_eq!(progress[0].line, 1);
+        assert_eq!(progress[1].action, ActionType::Update);
+        assert!(!progress[1].completed);
+    }
+    
+    #[test]
+    fn test_parse_apply_progress_replacement_is_update() {
+        let lines = vec![
+            "aws_instance.web: Destroying... [id=i-0abc123]",
+            "aws_instance.web: Destruction complete after 30s",
+            "aws_instance.web: Creating...",
+            "aws_instance.web: Creation complete after 40s [id=i-0def456]",
+        ];
+        let progress = parse_apply_progress(&lines);
+        
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].action, ActionType::Update);
+        assert!(progress[0].completed);
+    }
+    
+    #[test]
+    fn test_parse_error_blocks() {
+        let lines = vec![
+            "╷",
+            "│ Error: creating IAM Role (app): EntityAlreadyExists",
+            "│ ",
+            "│   with aws_iam_role.app,",
+            "│   on main.tf line 12, in resource \"aws_iam_role\" \"app\":",
+            "╵",
+            "Error: Invalid provider configuration",
+        ];
+        let errors = parse_error_blocks(&lines);
+        
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "creating IAM Role (app): EntityAlreadyExists");
+        assert_eq!(errors[0].level, WarningLevel::Error);
+        assert_eq!(errors[0].address.as_deref(), Some("aws_iam_role.app"));
+        assert_eq!(errors[0].span, Some(SourceSpan::new(2, 5)));
+        assert_eq!(errors[1].address, None);
+    }
+    
+    #[test]
+    fn test_parse_apply_output_marks_applied_resources() {
+        let plan = parse_terraform_output(&load_terraform_fixture("apply_output.txt")).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Apply);
+        assert_summary_totals(&plan.summary, 3, 0, 0);
+        assert_eq!(plan.resources.len(), 3);
+        assert!(plan.resources.iter().all(|r| r.applied && r.action == ActionType::Create));
+        assert_has_resource(&plan, "aws_lambda_function.processor");
+    }
+    
+    #[test]
+    fn test_parse_failed_apply_output() {
+        let plan = parse_terraform_output(&load_fixture("reconcile/apply_diverged.txt")).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Apply);
+        let role = plan.resources.iter().find(|r| r.id == "aws_iam_role.app").unwrap();
+        assert!(!role.applied);
+        
+        let error = plan.warnings.iter().find(|w| w.level == WarningLevel::Error).unwrap();
+        assert_eq!(error.address.as_deref(), Some("aws_iam_role.app"));
+        assert!(error.message.starts_with("creating IAM Role (app)"));
+    }
+    
+    #[test]
+    fn test_apply_output_with_plan_is_apply_mode() {
+        let input = format!("{}\naws_s3_bucket.logs: Creating...\naws_s3_bucket.logs: Creation complete after 2s [id=app-logs]\n",
+            load_fixture("reconcile/reviewed_plan.txt"));
+        let plan = parse_terraform_output(&input).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Apply);
+        assert_eq!(plan.resources.len(), 4);
+        let bucket = plan.resources.iter().find(|r| r.id == "aws_s3_bucket.logs").unwrap();
+        assert!(bucket.applied);
+        assert!(!plan.resources.iter().find(|r| r.id == "aws_instance.web").unwrap().applied);
+    }
+}
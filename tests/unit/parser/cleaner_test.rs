@@ -88,4 +88,36 @@ mod cleaner_tests {
         
         assert_eq!(result, expected);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_clean_input_lines_tracks_origins() {
+        let input = "\x1b[1mfirst\x1b[0m\nbase14-aws-use1-eks-scout    # aws_s3_bucket.logs (logs/\nbucket) will be created\nlast";
+        let lines = clean_input_lines(input);
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].text, "first");
+        assert_eq!((lines[0].start_line, lines[0].end_line), (1, 1));
+        
+        assert_eq!(lines[1].text, "# aws_s3_bucket.logs (logs/bucket) will be created");
+        assert_eq!((lines[1].start_line, lines[1].end_line), (2, 3));
+        assert_eq!(lines[1].prefix.as_deref(), Some("base14-aws-use1-eks-scout"));
+        
+        assert_eq!(lines[2].text, "last");
+        assert_eq!((lines[2].start_line, lines[2].end_line), (4, 4));
+        assert_eq!(lines[2].prefix, None);
+    }
+    
+    #[test]
+    fn test_clean_input_lines_with_prefixes() {
+        let patterns = vec![regex::Regex::new(r"\[[a-z0-9-]+\]\s+").unwrap()];
+        let input = "[stack-a]   # aws_s3_bucket.logs will be created\n[stack-a]     + bucket = \"logs\"\nno [prefix] here";
+        let lines = clean_input_lines_with_prefixes(input, &patterns);
+        
+        assert_eq!(lines[0].text, "# aws_s3_bucket.logs will be created");
+        assert_eq!(lines[0].prefix.as_deref(), Some("[stack-a]"));
+        assert_eq!(lines[1].text, "+ bucket = \"logs\"");
+        // Patterns only match at the start of a line's content
+        assert_eq!(lines[2].text, "no [prefix] here");
+        assert_eq!(lines[2].prefix, None);
+    }
+}
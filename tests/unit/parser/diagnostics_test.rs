@@ -0,0 +1,126 @@
+use tfdiff::parser::*;
+use tfdiff::models::*;
+use tfdiff::TfdiffError;
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use super::*;
+    
+    const WELL_FORMED: &str = r#"Terraform used the selected providers to generate the following execution plan. Resource actions are indicated with the following symbols:
+  + create
+
+Terraform will perform the following actions:
+
+  # aws_s3_bucket.logs will be created
+  + resource "aws_s3_bucket" "logs" {
+      + bucket = "logs-bucket"
+      + policy = <<-EOT
+            { "Version": "2012-10-17"
+        EOT
+      + tags   = {
+          + "Name" = "logs {primary}"
+        }
+    }
+
+Plan: 1 to add, 0 to change, 0 to destroy."#;
+    
+    fn kinds(plan: &TerraformPlan) -> Vec<DiagnosticKind> {
+        plan.warnings.iter().filter_map(|w| w.kind).collect()
+    }
+    
+    #[test]
+    fn test_well_formed_plan_has_no_diagnostics() {
+        let plan = parse_terraform_output_with_mode(WELL_FORMED, ParseMode::Strict).unwrap();
+        assert_eq!(plan.resources.len(), 1);
+        assert!(plan.warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_unrecognized_line_is_reported_with_line_number() {
+        let input = WELL_FORMED.replace("Terraform will perform", "garbage here\nTerraform will perform");
+        let plan = parse_terraform_output(&input).unwrap();
+        
+        assert_eq!(kinds(&plan), vec![DiagnosticKind::UnrecognizedLine]);
+        let warning = &plan.warnings[0];
+        assert_eq!(warning.level, WarningLevel::Info);
+        assert_eq!(warning.span.as_ref().unwrap().start_line, 4);
+        assert!(warning.message.contains("garbage here"));
+    }
+    
+    #[test]
+    fn test_free_text_sections_are_recognized() {
+        let input = format!("{}\n\nChanges to Outputs:\n  + bucket_arn = (known after apply)\n\nWarning: Deprecated argument\n\n  on main.tf line 4:\n   4:   acl = \"private\"\n", WELL_FORMED);
+        let plan = parse_terraform_output_with_mode(&input, ParseMode::Strict).unwrap();
+        assert!(plan.warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_summary_mismatch() {
+        let input = WELL_FORMED.replace("Plan: 1 to add", "Plan: 3 to add");
+        let plan = parse_terraform_output(&input).unwrap();
+        
+        assert_eq!(kinds(&plan), vec![DiagnosticKind::SummaryMismatch]);
+        assert!(plan.warnings[0].message.contains("3 to add"));
+        assert!(plan.warnings[0].message.contains("1 created"));
+    }
+    
+    #[test]
+    fn test_unbalanced_braces_before_next_resource() {
+        let input = r#"  # aws_s3_bucket.a will be created
+  + resource "aws_s3_bucket" "a" {
+      + tags = {
+    }
+
+  # aws_s3_bucket.b will be created
+  + resource "aws_s3_bucket" "b" {
+    }
+
+Plan: 2 to add, 0 to change, 0 to destroy."#;
+        let plan = parse_terraform_output(input).unwrap();
+        
+        assert_eq!(kinds(&plan), vec![DiagnosticKind::UnbalancedBraces]);
+        let span = plan.warnings[0].span.as_ref().unwrap();
+        assert_eq!((span.start_line, span.end_line), (2, 5));
+    }
+    
+    #[test]
+    fn test_incomplete_plan_fixture_strict() {
+        let content = load_malformed_fixture("incomplete_plan.txt");
+        let error = parse_terraform_output_with_mode(&content, ParseMode::Strict).unwrap_err();
+        
+        match error {
+            TfdiffError::InvalidInput(diagnostics) => {
+                let kinds: Vec<_> = diagnostics.iter().map(|d| d.kind).collect();
+                assert_eq!(kinds, vec![DiagnosticKind::UnbalancedBraces, DiagnosticKind::TruncatedInput]);
+                assert_eq!(diagnostics[0].span.start_line, 8);
+                assert_eq!(diagnostics[0].to_string(), "lines 8-18: resource block is missing 2 closing braces");
+            }
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_incomplete_plan_fixture_lenient() {
+        let content = load_malformed_fixture("incomplete_plan.txt");
+        let plan = parse_terraform_output(&content).unwrap();
+        
+        assert_eq!(plan.resources.len(), 1);
+        assert_eq!(kinds(&plan), vec![DiagnosticKind::UnbalancedBraces, DiagnosticKind::TruncatedInput]);
+    }
+    
+    #[test]
+    fn test_corrupted_json_fixture_strict() {
+        let content = load_malformed_fixture("corrupted_json.txt");
+        let result = parse_terraform_output_with_mode(&content, ParseMode::Strict);
+        
+        assert!(matches!(result, Err(TfdiffError::InvalidInput(_))));
+    }
+    
+    #[test]
+    fn test_apply_log_is_recognized() {
+        let content = load_terraform_fixture("apply_output.txt");
+        let plan = parse_terraform_output_with_mode(&content, ParseMode::Strict).unwrap();
+        assert!(matches!(plan.mode, PlanMode::Apply));
+    }
+}
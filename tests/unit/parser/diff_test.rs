@@ -1,5 +1,6 @@
 use tfdiff::parser::diff::*;
 use tfdiff::models::*;
+use tfdiff::semantic::{classify_change, ChangeClass};
 
 #[cfg(test)]
 mod diff_parser_tests {
@@ -46,6 +47,32 @@ mod diff_parser_tests {
         assert!(matches!(resource.action, ActionType::Update));
     }
     
+    #[test]
+    fn test_parse_resource_changes_update_nested_paths() {
+        let lines = vec![
+            "# aws_instance.web will be updated in-place",
+            "~ resource \"aws_instance\" \"web\" {",
+            "    ~ tags_all      = {",
+            "        ~ \"Environment\" = \"staging\" -> \"production\"",
+            "        + \"Owner\"       = \"platform\"",
+            "      }",
+            "    ~ root_block_device {",
+            "        ~ volume_size = 20 -> 40",
+            "      }",
+            "    ~ instance_type = \"t2.micro\" -> \"t3.small\"",
+            "  }",
+        ];
+        
+        let resources = parse_resource_changes(&lines);
+        let paths: Vec<String> = resources[0].changes.iter().map(|change| change.path.join(".")).collect();
+        assert_eq!(paths, vec![
+            "tags_all.Environment",
+            "tags_all.Owner",
+            "root_block_device.volume_size",
+            "instance_type",
+        ]);
+    }
+    
     #[test]
     fn test_parse_resource_changes_destroy() {
         let lines = vec![
@@ -138,4 +165,58 @@ mod diff_parser_tests {
             }
         }
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_resource_changes_replacement() {
+        let lines = vec![
+            "# aws_instance.web must be replaced",
+            "-/+ resource \"aws_instance\" \"web\" {",
+            "~ ami           = \"ami-1\" -> \"ami-2\" # forces replacement",
+            "~ instance_type = \"t2.micro\" -> \"t3.small\"",
+            "}",
+        ];
+        let resources = parse_resource_changes(&lines);
+        
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].action, ActionType::Update);
+        assert!(resources[0].replace);
+        
+        let changes = &resources[0].changes;
+        assert_eq!(changes[0].after, Some(serde_json::json!("ami-2")));
+        assert!(changes[0].forces_replacement);
+        assert!(!changes[1].forces_replacement);
+    }
+    
+    #[test]
+    fn test_parse_resource_changes_list_elements() {
+        let lines = vec![
+            "# aws_instance.web will be updated in-place",
+            "~ resource \"aws_instance\" \"web\" {",
+            "    ~ vpc_security_group_ids = [",
+            "        - \"sg-111\",",
+            "          \"sg-222\",",
+            "        + \"sg-111\",",
+            "          # (1 unchanged element hidden)",
+            "      ]",
+            "    + ports                  = [",
+            "        + 443,",
+            "      ]",
+            "    ~ ingress                = [",
+            "        - {",
+            "            - from_port = 443",
+            "          },",
+            "      ]",
+            "  }",
+        ];
+        
+        let changes = &parse_resource_changes(&lines)[0].changes;
+        let paths: Vec<String> = changes.iter().map(|change| change.path.join(".")).collect();
+        assert_eq!(paths, vec!["vpc_security_group_ids", "ports", "ingress.from_port"]);
+        assert_eq!(changes[0].before, Some(serde_json::json!(["sg-111", "sg-222"])));
+        assert_eq!(changes[0].after, Some(serde_json::json!(["sg-222", "sg-111"])));
+        assert_eq!(changes[0].span, Some(SourceSpan::new(3, 8)));
+        assert!(matches!(classify_change(&changes[0]), ChangeClass::Cosmetic(_)));
+        assert_eq!(changes[1].before, None);
+        assert_eq!(changes[1].after, Some(serde_json::json!([443])));
+    }
+}
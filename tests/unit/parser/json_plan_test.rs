@@ -0,0 +1,106 @@
+use tfdiff::parser::*;
+use tfdiff::models::*;
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod json_plan_parser_tests {
+    use super::*;
+    
+    #[test]
+    fn test_is_json_plan() {
+        assert!(is_json_plan(&load_terraform_fixture("plan.json")));
+        assert!(!is_json_plan(&load_terraform_fixture("simple_plan.txt")));
+        assert!(!is_json_plan("{ not json"));
+    }
+    
+    #[test]
+    fn test_parse_json_plan_fixture() {
+        let plan = parse_terraform_output(&load_terraform_fixture("plan.json")).unwrap();
+        
+        assert_eq!(plan.resources.len(), 2);
+        assert_eq!(plan.summary.add, 1);
+        assert_eq!(plan.summary.change, 1);
+        assert_eq!(plan.summary, plan.parsed_summary);
+        assert_eq!(plan.metadata.terraform_version.as_deref(), Some("1.5.7"));
+        
+        let bucket = &plan.resources[0];
+        assert_eq!(bucket.id, "aws_s3_bucket.app_bucket");
+        assert_eq!(bucket.provider, "aws");
+        assert!(matches!(bucket.action, ActionType::Create));
+        
+        let instance = &plan.resources[1];
+        assert!(matches!(instance.action, ActionType::Update));
+        assert!(!instance.changes.is_empty());
+    }
+    
+    #[test]
+    fn test_parse_json_plan_provider_alias() {
+        let input = r#"{
+            "format_version": "1.2",
+            "resource_changes": [
+                {
+                    "address": "module.dr.aws_s3_bucket.replica",
+                    "module_address": "module.dr",
+                    "mode": "managed",
+                    "type": "aws_s3_bucket",
+                    "name": "replica",
+                    "provider_name": "registry.terraform.io/hashicorp/aws",
+                    "change": { "actions": ["create"], "before": null, "after": { "bucket": "replica" }, "after_unknown": { "arn": true } }
+                },
+                {
+                    "address": "aws_s3_bucket.unchanged",
+                    "mode": "managed",
+                    "type": "aws_s3_bucket",
+                    "name": "unchanged",
+                    "provider_name": "registry.terraform.io/hashicorp/aws",
+                    "change": { "actions": ["no-op"], "before": {}, "after": {} }
+                }
+            ],
+            "configuration": {
+                "root_module": {
+                    "module_calls": {
+                        "dr": {
+                            "module": {
+                                "resources": [
+                                    { "address": "aws_s3_bucket.replica", "provider_config_key": "dr:aws.west" }
+                                ]
+                            }
+                        }
+                    }
+                }
+            }
+        }"#;
+        
+        let plan = parse_json_plan(input).unwrap();
+        assert_eq!(plan.resources.len(), 1);
+        
+        let replica = &plan.resources[0];
+        assert_eq!(replica.provider, "aws.west");
+        assert_eq!(replica.module(), Some("module.dr".to_string()));
+        assert_eq!(replica.attributes.get("arn"), Some(&serde_json::json!("(known after apply)")));
+    }
+    
+    #[test]
+    fn test_parse_json_plan_replacement() {
+        let input = r#"{
+            "format_version": "1.2",
+            "resource_changes": [
+                {
+                    "address": "aws_db_instance.main",
+                    "mode": "managed",
+                    "type": "aws_db_instance",
+                    "name": "main",
+                    "provider_name": "registry.terraform.io/hashicorp/aws",
+                    "change": { "actions": ["delete", "create"], "before": { "engine_version": "13.7" }, "after": { "engine_version": "15.4" }, "replace_paths": [["engine_version"]] }
+                }
+            ]
+        }"#;
+        let plan = parse_terraform_output(input).unwrap();
+        
+        let database = &plan.resources[0];
+        assert_eq!(database.action, ActionType::Update);
+        assert!(database.replace);
+        assert!(database.changes[0].forces_replacement);
+        assert_eq!((plan.summary.add, plan.summary.change, plan.summary.destroy), (1, 0, 1));
+    }
+}
@@ -1,4 +1,9 @@
 mod cleaner_test;
 mod terraform_test;
 mod diff_test;
-mod interleaved_test;
\ No newline at end of file
+mod interleaved_test;
+mod diagnostics_test;
+mod provider_test;
+mod json_plan_test;
+mod ndjson_test;
+mod apply_test;
@@ -0,0 +1,74 @@
+use tfdiff::parser::*;
+use tfdiff::models::*;
+use crate::common::fixtures::*;
+use crate::common::assertions::*;
+
+#[cfg(test)]
+mod ndjson_parser_tests {
+    use super::*;
+    
+    #[test]
+    fn test_is_ndjson_log() {
+        assert!(is_ndjson_log(&load_terraform_fixture("plan_log.ndjson")));
+        assert!(!is_ndjson_log(&load_terraform_fixture("plan.json")));
+        assert!(!is_ndjson_log(&load_terraform_fixture("simple_plan.txt")));
+        assert!(!is_ndjson_log(""));
+    }
+    
+    #[test]
+    fn test_parse_plan_log() {
+        let plan = parse_terraform_output(&load_terraform_fixture("plan_log.ndjson")).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Plan);
+        assert_summary_totals(&plan.summary, 1, 1, 1);
+        assert!(plan.summary.matches_counts(&plan.parsed_summary));
+        assert_eq!(plan.resources.len(), 3);
+        assert_eq!(plan.data_sources.len(), 1);
+        assert_eq!(plan.metadata.terraform_version.as_deref(), Some("1.6.2"));
+        
+        let instance = plan.resources.iter().find(|r| r.id == "module.app.aws_instance.web[0]").unwrap();
+        assert!(matches!(instance.action, ActionType::Update));
+        assert_eq!(instance.module(), Some("module.app".to_string()));
+        
+        let bucket = plan.resources.iter().find(|r| r.id == "google_storage_bucket.old").unwrap();
+        assert!(matches!(bucket.action, ActionType::Destroy));
+        assert_eq!(bucket.provider, "google");
+        
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].message.contains("Argument is deprecated: Use the aws_s3_bucket_acl resource instead"));
+    }
+    
+    #[test]
+    fn test_parse_apply_log_marks_applied_resources() {
+        let plan = parse_terraform_output(&load_terraform_fixture("apply_log.ndjson")).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Apply);
+        assert_summary_totals(&plan.summary, 1, 0, 0);
+        
+        let bucket = plan.resources.iter().find(|r| r.id == "aws_s3_bucket.logs").unwrap();
+        let role = plan.resources.iter().find(|r| r.id == "aws_iam_role.app").unwrap();
+        assert!(bucket.applied);
+        assert!(!role.applied);
+        assert!(matches!(plan.warnings[0].level, WarningLevel::Error));
+    }
+    
+    #[test]
+    fn test_parse_apply_errored_keeps_address() {
+        let input = [
+            r#"{"@level":"info","@message":"aws_iam_role.app: Plan to create","type":"planned_change","change":{"resource":{"addr":"aws_iam_role.app","resource_type":"aws_iam_role","resource_name":"app"},"action":"create"}}"#,
+            r#"{"@level":"error","@message":"aws_iam_role.app: Creation errored after 1s","type":"apply_errored","hook":{"resource":{"addr":"aws_iam_role.app"},"action":"create"}}"#,
+        ].join("\n");
+        let plan = parse_ndjson_log(&input).unwrap();
+        
+        assert_eq!(plan.mode, PlanMode::Apply);
+        assert_eq!(plan.warnings.len(), 1);
+        assert_eq!(plan.warnings[0].level, WarningLevel::Error);
+        assert_eq!(plan.warnings[0].address.as_deref(), Some("aws_iam_role.app"));
+    }
+    
+    #[test]
+    fn test_parse_log_without_changes_fails() {
+        let input = r#"{"@level":"info","@message":"Terraform 1.6.2","type":"version","terraform":"1.6.2"}"#;
+        assert!(parse_ndjson_log(input).is_err());
+    }
+}
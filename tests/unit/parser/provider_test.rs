@@ -0,0 +1,105 @@
+use tfdiff::parser::*;
+use tfdiff::models::*;
+
+#[cfg(test)]
+mod provider_inference_tests {
+    use super::*;
+    
+    #[test]
+    fn test_provider_from_type() {
+        assert_eq!(provider_from_type("aws_s3_bucket"), "aws");
+        assert_eq!(provider_from_type("google_compute_instance"), "google");
+        assert_eq!(provider_from_type("random"), "unknown");
+    }
+    
+    #[test]
+    fn test_provider_from_source_with_alias() {
+        assert_eq!(provider_from_source("registry.terraform.io/hashicorp/aws", None), "aws");
+        assert_eq!(provider_from_source("registry.terraform.io/hashicorp/aws", Some("west")), "aws.west");
+    }
+    
+    #[test]
+    fn test_provider_from_reference() {
+        let line = r#"+ provider = provider["registry.terraform.io/hashicorp/aws"].west"#;
+        assert_eq!(provider_from_reference(line), Some("aws.west".to_string()));
+        assert_eq!(provider_from_reference("+ bucket = \"test\""), None);
+    }
+    
+    #[test]
+    fn test_parsed_resources_use_provider_reference() {
+        let lines = vec![
+            "# aws_s3_bucket.replica will be created",
+            "+ resource \"aws_s3_bucket\" \"replica\" {",
+            "+   bucket   = \"replica\"",
+            "+   provider = provider[\"registry.terraform.io/hashicorp/aws\"].west",
+            "  }",
+            "# google_storage_bucket.logs will be created",
+            "+ resource \"google_storage_bucket\" \"logs\" {",
+            "+   name = \"logs\"",
+            "  }",
+        ];
+        
+        let resources = parse_resource_changes(&lines);
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].provider, "aws.west");
+        assert_eq!(resources[1].provider, "google");
+    }
+    
+    #[test]
+    fn test_provider_reference_only_inside_the_resource_block() {
+        let lines = vec![
+            "# aws_s3_bucket.east will be updated in-place",
+            "~ resource \"aws_s3_bucket\" \"east\" {",
+            "    provider = provider[\"registry.terraform.io/hashicorp/aws\"].east",
+            "  ~ acl      = \"private\" -> \"log-delivery-write\"",
+            "  }",
+            "provider = provider[\"registry.terraform.io/hashicorp/aws\"].stray",
+            "# aws_s3_bucket.west will be updated in-place",
+            "~ resource \"aws_s3_bucket\" \"west\" {",
+            "    provider = provider[\"registry.terraform.io/hashicorp/aws\"].west",
+            "  ~ acl      = \"private\" -> \"log-delivery-write\"",
+            "  }",
+            "# aws_s3_bucket.default will be updated in-place",
+            "~ resource \"aws_s3_bucket\" \"default\" {",
+            "  ~ acl = \"private\" -> \"log-delivery-write\"",
+            "  }",
+            "  Plan: 0 to add, 3 to change, 0 to destroy. provider[\"registry.terraform.io/hashicorp/aws\"].later",
+        ];
+        
+        let providers: Vec<String> = parse_resource_changes(&lines).into_iter().map(|resource| resource.provider).collect();
+        assert_eq!(providers, vec!["aws.east", "aws.west", "aws"]);
+    }
+    
+    #[test]
+    fn test_resource_address_parse() {
+        let address = ResourceAddress::parse("module.vpc.module.subnets.aws_subnet.public[0]").unwrap();
+        assert_eq!(address.module.as_deref(), Some("module.vpc.module.subnets"));
+        assert_eq!(address.type_name, "aws_subnet");
+        assert_eq!(address.name, "public[0]");
+        assert!(!address.data);
+        
+        let data = ResourceAddress::parse("data.aws_ami.ubuntu").unwrap();
+        assert!(data.data);
+        assert_eq!(data.module, None);
+        
+        let keyed = ResourceAddress::parse(r#"aws_instance.web["a.b"]"#).unwrap();
+        assert_eq!(keyed.name, r#"web["a.b"]"#);
+        
+        assert!(ResourceAddress::parse("not_an_address").is_none());
+    }
+    
+    #[test]
+    fn test_resource_module() {
+        let lines = vec![
+            "# module.vpc.aws_vpc.main will be created",
+            "+ resource \"aws_vpc\" \"main\" {",
+            "+   cidr_block = \"10.0.0.0/16\"",
+            "  }",
+        ];
+        
+        let resources = parse_resource_changes(&lines);
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].id, "module.vpc.aws_vpc.main");
+        assert_eq!(resources[0].module(), Some("module.vpc".to_string()));
+    }
+}
@@ -132,4 +132,27 @@ Apply complete! Resources: 1 added, 0 changed, 0 destroyed.
         assert_eq!(result.summary.change, 0);
         assert_eq!(result.summary.destroy, 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_terraform_output_records_source_spans() {
+        let input = "\x1b[32mTerraform will perform the following actions:\x1b[0m\n\
+\n\
+base14-aws-use1-eks-scout    # aws_instance.web will be updated in-place\n\
+  ~ resource \"aws_instance\" \"web\" {\n\
+      ~ instance_type = \"t2.micro\" -> \"t3.small\"\n\
+    }\n\
+\n\
+Plan: 0 to add, 1 to change, 0 to destroy.";
+        
+        let result = parse_terraform_output(input).unwrap();
+        let resource = &result.resources[0];
+        let span = resource.span.as_ref().unwrap();
+        assert_eq!(span.start_line, 3);
+        assert_eq!(span.end_line, 6);
+        assert_eq!(span.prefix.as_deref(), Some("base14-aws-use1-eks-scout"));
+        
+        let change_span = resource.changes[0].span.as_ref().unwrap();
+        assert_eq!(change_span.start_line, 5);
+        assert_eq!(change_span.end_line, 5);
+    }
+}
@@ -0,0 +1 @@
+mod policy_test;
@@ -0,0 +1,113 @@
+use tfdiff::policy::*;
+use tfdiff::models::*;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod policy_tests {
+    use super::*;
+    
+    fn evaluate(labels: &[&str]) -> Vec<PolicyResult> {
+        let policies = PolicySet::load(&fixture_path("policy/policies.toml")).unwrap();
+        let labels: Vec<String> = labels.iter().map(|label| label.to_string()).collect();
+        policies.evaluate(&guarded_plan(), &labels).unwrap()
+    }
+    
+    fn result<'a>(results: &'a [PolicyResult], name: &str) -> &'a PolicyResult {
+        results.iter().find(|result| result.policy == name).unwrap()
+    }
+    
+    #[test]
+    fn test_replacement_is_parsed() {
+        let plan = guarded_plan();
+        let database = plan.resources.iter().find(|r| r.id == "aws_db_instance.main").unwrap();
+        
+        assert_eq!(database.action, ActionType::Update);
+        assert!(database.replace);
+        assert_eq!(database.changes[0].after, Some(serde_json::json!("15.4")));
+        // A replacement counts as one to add and one to destroy, as in Terraform's summary
+        assert!(plan.summary.matches_counts(&plan.parsed_summary));
+        assert!(plan.warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_deny_destroy_and_replace() {
+        let results = evaluate(&[]);
+        
+        let databases = result(&results, "protect-databases");
+        assert_eq!(databases.outcome, PolicyOutcome::Fail);
+        assert_eq!(databases.violations, vec!["aws_db_instance.main will be replaced"]);
+        assert_eq!(databases.addresses, vec!["aws_db_instance.main"]);
+        assert_eq!(databases.description.as_deref(), Some("Databases are never destroyed or replaced"));
+        
+        let production = result(&results, "protect-production");
+        assert_eq!(production.violations, vec!["module.prod_app.aws_instance.web will be destroyed"]);
+    }
+    
+    #[test]
+    fn test_max_changes() {
+        let results = evaluate(&[]);
+        
+        let small = result(&results, "small-plans");
+        assert_eq!(small.outcome, PolicyOutcome::Warn);
+        assert_eq!(small.violations, vec!["4 resources change, more than the limit of 3"]);
+        assert!(small.addresses.is_empty());
+    }
+    
+    #[test]
+    fn test_unless_label() {
+        let unlabelled = evaluate(&[]);
+        assert_eq!(result(&unlabelled, "iam-review").outcome, PolicyOutcome::Fail);
+        assert_eq!(result(&unlabelled, "iam-review").waived_by, None);
+        
+        let labelled = evaluate(&["iam-approved"]);
+        let iam = result(&labelled, "iam-review");
+        assert_eq!(iam.outcome, PolicyOutcome::Pass);
+        assert_eq!(iam.waived_by.as_deref(), Some("iam-approved"));
+        assert_eq!(iam.violations, vec!["aws_iam_role_policy.deploy will be updated in-place"]);
+    }
+    
+    #[test]
+    fn test_policy_outcome() {
+        assert_eq!(policy_outcome(&[]), PolicyOutcome::Pass);
+        assert_eq!(policy_outcome(&evaluate(&[])), PolicyOutcome::Fail);
+        
+        let warn_only = PolicySet::from_toml("[[policy]]\nname = \"tiny\"\nlevel = \"warn\"\nmax_changes = 1\n").unwrap();
+        let results = warn_only.evaluate(&guarded_plan(), &[]).unwrap();
+        assert_eq!(policy_outcome(&results), PolicyOutcome::Warn);
+    }
+    
+    #[test]
+    fn test_where_expression() {
+        let policies = PolicySet::from_toml(r#"
+[[policy]]
+name = "no-database-engine-upgrades"
+where = 'attr("engine_version") changed'
+"#).unwrap();
+        let results = policies.evaluate(&guarded_plan(), &[]).unwrap();
+        
+        assert_eq!(results[0].violations, vec!["aws_db_instance.main will be replaced"]);
+    }
+    
+    #[test]
+    fn test_passing_policy() {
+        let policies = PolicySet::from_toml("[[policy]]\nname = \"no-gcp\"\nproviders = [\"google\"]\n").unwrap();
+        let results = policies.evaluate(&guarded_plan(), &[]).unwrap();
+        
+        assert_eq!(results[0].outcome, PolicyOutcome::Pass);
+        assert!(results[0].violations.is_empty());
+    }
+    
+    #[test]
+    fn test_invalid_policies() {
+        let unnamed = PolicySet::from_toml("[[policy]]\nactions = [\"destroy\"]\n").unwrap_err();
+        assert_eq!(unnamed.to_string(), "Invalid configuration: every policy needs a name");
+        
+        let action = PolicySet::from_toml("[[policy]]\nname = \"p\"\nactions = [\"delete\"]\n").unwrap_err();
+        assert_eq!(action.to_string(),
+            "Invalid configuration: policy 'p': unknown action 'delete' (expected create, update, destroy, read or no-op)");
+        
+        let level = PolicySet::from_toml("[[policy]]\nname = \"p\"\nlevel = \"block\"\n").unwrap_err();
+        assert!(level.to_string().starts_with("Invalid configuration: line 3:"));
+    }
+}
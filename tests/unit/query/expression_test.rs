@@ -0,0 +1,161 @@
+use tfdiff::query::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod expression_tests {
+    use super::*;
+    
+    fn matches(source: &str, resource: &Resource) -> bool {
+        Expression::parse(source).unwrap().matches(resource)
+    }
+    
+    fn parse_error(source: &str) -> String {
+        Expression::parse(source).unwrap_err().to_string()
+    }
+    
+    fn json_plan_resource(address: &str) -> Resource {
+        let plan = parse_terraform_output(&load_terraform_fixture("plan.json")).unwrap();
+        plan.resources.into_iter().find(|r| r.id == address).unwrap()
+    }
+    
+    #[test]
+    fn test_fields() {
+        let resource = sample_update_resource();
+        
+        assert!(matches(r#"action == "update""#, &resource));
+        assert!(matches(r#"action != "create""#, &resource));
+        assert!(matches(r#"address == "aws_instance.web""#, &resource));
+        assert!(matches(r#"type == "aws_instance" and name == "web""#, &resource));
+        assert!(matches(r#"provider == "aws""#, &resource));
+        assert!(matches("module == null", &resource));
+        assert!(matches("not applied", &resource));
+        assert!(matches("changes == 1", &resource));
+    }
+    
+    #[test]
+    fn test_regex_match() {
+        let plan = parse_terraform_output(&load_terraform_fixture("module_plan.txt")).unwrap();
+        let expression = Expression::parse(r#"module ~ "subnets$" and type !~ "^aws_vpc""#).unwrap();
+        
+        let selected: Vec<&str> = plan.resources.iter()
+            .filter(|r| expression.matches(r))
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(selected, vec!["module.vpc.module.subnets.aws_subnet.public[0]", "module.vpc.module.subnets.aws_subnet.public[1]"]);
+        
+        // The root module has no module to match
+        assert!(!matches(r#"module ~ ".*""#, &sample_update_resource()));
+    }
+    
+    #[test]
+    fn test_attribute_values() {
+        let resource = sample_update_resource();
+        
+        assert!(matches(r#"attr("instance_type") == "t3.small""#, &resource));
+        assert!(matches(r#"before("instance_type") == "t2.micro""#, &resource));
+        assert!(matches(r#"after("instance_type") ~ "^t3""#, &resource));
+        assert!(matches(r#"attr("instance_type") changed"#, &resource));
+        assert!(matches(r#"attr("missing") == null"#, &resource));
+        assert!(!matches(r#"attr("missing") changed"#, &resource));
+    }
+    
+    #[test]
+    fn test_nested_attribute_paths() {
+        let instance = json_plan_resource("aws_instance.web_server");
+        
+        assert!(matches(r#"attr("tags.Environment") changed"#, &instance));
+        assert!(matches(r#"before("tags.Environment") == "staging" and after("tags.Environment") == "production""#, &instance));
+        assert!(!matches(r#"attr("tags.Name") changed"#, &instance));
+        assert!(matches(r#"attr("tags") changed"#, &instance));
+    }
+    
+    #[test]
+    fn test_text_plan_attribute_values_are_normalised() {
+        // Creates from text plans keep display strings such as `+ "test-bucket"`
+        let resource = sample_create_resource();
+        assert!(matches(r#"attr("bucket") == "test-bucket""#, &resource));
+        assert!(matches(r#"attr("force_destroy") == false"#, &resource));
+    }
+    
+    #[test]
+    fn test_numeric_comparisons() {
+        let mut resource = sample_update_resource();
+        resource.changes[0].path = vec!["port".to_string()];
+        resource.changes[0].before = Some(serde_json::json!(80));
+        resource.changes[0].after = Some(serde_json::json!("443"));
+        
+        assert!(matches(r#"after("port") > 400"#, &resource));
+        assert!(matches(r#"before("port") <= 80"#, &resource));
+        assert!(matches(r#"after("port") == 443"#, &resource));
+        assert!(!matches(r#"attr("missing") > 1"#, &resource));
+    }
+    
+    #[test]
+    fn test_boolean_operators_and_precedence() {
+        let resource = sample_update_resource();
+        
+        // `and` binds tighter than `or`
+        assert!(matches(r#"action == "create" and type == "x" or name == "web""#, &resource));
+        assert!(!matches(r#"action == "create" and (type == "x" or name == "web")"#, &resource));
+        assert!(matches(r#"not (action == "create" or action == "destroy")"#, &resource));
+        assert!(matches(r#"not not action == "update""#, &resource));
+    }
+    
+    #[test]
+    fn test_string_escapes() {
+        let mut resource = sample_update_resource();
+        resource.name = r#"say "hi""#.to_string();
+        assert!(matches(r#"name == "say \"hi\"""#, &resource));
+    }
+    
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_error(r#"acton == "update""#),
+            r#"Invalid query: unknown field 'acton' (expected action, address, type, name, provider, module, applied, changes or attr/before/after("path")) at column 1 of `acton == "update"`"#);
+        assert!(parse_error("action ==").contains("expected a value after '==' at column 8"));
+        assert!(parse_error(r#"action == "update" and"#).contains("expected a field, attr(\"path\") or a value at column 23"));
+        assert!(parse_error(r#"(action == "update""#).contains("unclosed '(' at column 1"));
+        assert!(parse_error(r#"action == "update")"#).contains("unexpected ')' at column 19"));
+        assert!(parse_error(r#"name == "open"#).contains("unterminated string at column 9"));
+        assert!(parse_error("attr(tags) changed").contains("expected attr(\"path\") at column 1"));
+        assert!(parse_error("type changed").contains("'changed' only follows attr(\"path\")"));
+        assert!(parse_error(r#"module ~ "[""#).contains("invalid regular expression \"[\": unclosed character class"));
+        assert!(parse_error("module ~ prod").contains("expected a quoted pattern after '~' but found 'prod'"));
+        assert!(parse_error(r#"action = "update""#).contains("unexpected '=' (use '==' to compare)"));
+        assert!(parse_error("action == $").contains("unexpected character '$' at column 11"));
+    }
+    
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}action == \"update\"{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(Expression::parse(&nested(64)).is_ok());
+        assert!(Expression::parse(&format!("{}action == \"update\"", "not ".repeat(64))).is_ok());
+        
+        assert_eq!(parse_error(&nested(65)), format!(
+            "Invalid query: expression nests deeper than 64 levels at column 65 of `{}`", nested(65)
+        ));
+        assert!(parse_error(&nested(30_000)).contains("expression nests deeper than 64 levels at column 65"));
+        assert!(parse_error(&"not ".repeat(30_000)).contains("expression nests deeper than 64 levels at column 257"));
+        
+        // Long chains of `or` are not nesting
+        let chain = vec!["name == \"web\""; 30_000].join(" or ");
+        assert!(matches(&chain, &sample_update_resource()));
+    }
+    
+    #[test]
+    fn test_expression_in_resource_query() {
+        let mut plan = sample_terraform_plan();
+        let query = ResourceQuery {
+            expression: Some(r#"attr("instance_type") changed"#.parse().unwrap()),
+            ..Default::default()
+        };
+        query.filter_plan(&mut plan);
+        
+        assert_eq!(plan.resources.len(), 1);
+        assert_eq!(plan.summary.change, 1);
+        assert_eq!(plan.summary.add, 0);
+    }
+}
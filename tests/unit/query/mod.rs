@@ -0,0 +1,2 @@
+mod query_test;
+mod expression_test;
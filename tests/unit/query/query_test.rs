@@ -0,0 +1,156 @@
+use tfdiff::query::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use crate::common::assertions::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod query_tests {
+    use super::*;
+    
+    fn selected(query: &ResourceQuery, plan: &TerraformPlan) -> Vec<String> {
+        plan.resources.iter()
+            .filter(|resource| query.matches(resource))
+            .map(|resource| resource.id.clone())
+            .collect()
+    }
+    
+    fn module_plan() -> TerraformPlan {
+        parse_terraform_output(&load_terraform_fixture("module_plan.txt")).unwrap()
+    }
+    
+    #[test]
+    fn test_empty_query_matches_everything() {
+        let query = ResourceQuery::default();
+        let plan = sample_terraform_plan();
+        
+        assert!(query.is_empty());
+        assert_eq!(selected(&query, &plan).len(), plan.resources.len());
+    }
+    
+    #[test]
+    fn test_query_by_action() {
+        let query = ResourceQuery { actions: vec![ActionType::Update], ..Default::default() };
+        assert_eq!(selected(&query, &sample_terraform_plan()), vec!["aws_instance.web"]);
+    }
+    
+    #[test]
+    fn test_query_by_resource_type_glob() {
+        let query = ResourceQuery { resource_types: vec!["aws_s3_*".to_string()], ..Default::default() };
+        assert_eq!(selected(&query, &sample_terraform_plan()), vec!["aws_s3_bucket.test"]);
+    }
+    
+    #[test]
+    fn test_query_by_address_glob() {
+        let query = ResourceQuery { addresses: vec!["module.vpc.*".to_string()], ..Default::default() };
+        assert_eq!(selected(&query, &module_plan()).len(), 3);
+    }
+    
+    #[test]
+    fn test_query_by_module() {
+        let plan = module_plan();
+        
+        let vpc = ResourceQuery { modules: vec!["vpc".to_string()], ..Default::default() };
+        assert_eq!(selected(&vpc, &plan).len(), 3);
+        
+        let subnets = ResourceQuery { modules: vec!["module.vpc.module.subnets".to_string()], ..Default::default() };
+        assert_eq!(selected(&subnets, &plan).len(), 2);
+        
+        let root = ResourceQuery { modules: vec!["root".to_string()], ..Default::default() };
+        assert_eq!(selected(&root, &sample_terraform_plan()).len(), 2);
+        assert!(selected(&root, &plan).is_empty());
+    }
+    
+    #[test]
+    fn test_query_by_provider_includes_aliases() {
+        let mut plan = sample_terraform_plan();
+        plan.resources[0].provider = "aws.west".to_string();
+        
+        let aws = ResourceQuery { providers: vec!["aws".to_string()], ..Default::default() };
+        assert_eq!(selected(&aws, &plan).len(), 2);
+        
+        let west = ResourceQuery { providers: vec!["aws.west".to_string()], ..Default::default() };
+        assert_eq!(selected(&west, &plan), vec!["aws_s3_bucket.test"]);
+    }
+    
+    #[test]
+    fn test_query_by_changed_attribute() {
+        let mut resource = sample_update_resource();
+        resource.changes.push(Change {
+            path: vec!["tags".to_string(), "Environment".to_string()],
+            before: Some(serde_json::json!("staging")),
+            after: Some(serde_json::json!("prod")),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        });
+        
+        for pattern in ["tags.Environment", "tags", "tags.*"] {
+            let query = ResourceQuery { changed_attributes: vec![pattern.to_string()], ..Default::default() };
+            assert!(query.matches(&resource), "{} should match", pattern);
+        }
+        let query = ResourceQuery { changed_attributes: vec!["tags.Owner".to_string()], ..Default::default() };
+        assert!(!query.matches(&resource));
+        
+        // Creates list their attributes rather than changes
+        let query = ResourceQuery { changed_attributes: vec!["force_destroy".to_string()], ..Default::default() };
+        assert!(query.matches(&sample_create_resource()));
+    }
+    
+    #[test]
+    fn test_exclude_wins_over_other_criteria() {
+        let query = ResourceQuery {
+            actions: vec![ActionType::Create, ActionType::Update],
+            exclude: vec!["aws_s3_*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(selected(&query, &sample_terraform_plan()), vec!["aws_instance.web"]);
+        
+        let by_address = ResourceQuery { exclude: vec!["module.vpc.module.subnets.*".to_string()], ..Default::default() };
+        assert_eq!(selected(&by_address, &module_plan()).len(), 2);
+    }
+    
+    #[test]
+    fn test_match_all_and_any() {
+        let mut query = ResourceQuery {
+            actions: vec![ActionType::Create],
+            resource_types: vec!["aws_instance".to_string()],
+            ..Default::default()
+        };
+        assert!(selected(&query, &sample_terraform_plan()).is_empty());
+        
+        query.match_mode = MatchMode::Any;
+        assert_eq!(selected(&query, &sample_terraform_plan()).len(), 2);
+    }
+    
+    #[test]
+    fn test_filter_plan_recomputes_summary() {
+        let mut plan = parse_terraform_output(&load_terraform_fixture("complex_aws_infrastructure.txt")).unwrap();
+        let query = ResourceQuery { actions: vec![ActionType::Destroy], ..Default::default() };
+        query.filter_plan(&mut plan);
+        
+        assert_eq!(plan.resources.len(), 2);
+        assert_summary_totals(&plan.summary, 0, 0, 2);
+        assert_eq!(plan.summary, plan.parsed_summary);
+    }
+    
+    #[test]
+    fn test_empty_query_keeps_reported_summary() {
+        let mut plan = sample_terraform_plan();
+        ResourceQuery::default().filter_plan(&mut plan);
+        assert_summary_totals(&plan.summary, 2, 1, 0);
+    }
+    
+    #[test]
+    fn test_parse_action_names() {
+        assert_eq!("create".parse::<ActionType>().unwrap(), ActionType::Create);
+        assert_eq!("Destroy".parse::<ActionType>().unwrap(), ActionType::Destroy);
+        assert_eq!("no-op".parse::<ActionType>().unwrap(), ActionType::NoOp);
+        assert_eq!("noop".parse::<ActionType>().unwrap(), ActionType::NoOp);
+        
+        let error = "remove".parse::<ActionType>().unwrap_err();
+        assert!(error.to_string().contains("unknown action 'remove'"));
+    }
+}
@@ -0,0 +1 @@
+mod reconcile_test;
@@ -0,0 +1,66 @@
+use tfdiff::reconcile::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+
+#[cfg(test)]
+mod reconcile_tests {
+    use super::*;
+    
+    fn reconcile_fixtures(plan: &str, apply: &str) -> tfdiff::Result<Reconciliation> {
+        let plan = parse_terraform_output(&load_fixture(plan)).unwrap();
+        let apply = parse_terraform_output(&load_fixture(apply)).unwrap();
+        reconcile(&plan, &apply)
+    }
+    
+    #[test]
+    fn test_reconcile_matching_apply() {
+        let reconciliation = reconcile_fixtures("reconcile/reviewed_plan.txt", "reconcile/apply_success.txt").unwrap();
+        
+        assert!(reconciliation.is_consistent());
+        assert_eq!(reconciliation.matched.len(), 4);
+        assert_eq!(reconciliation.planned_summary, reconciliation.applied_summary);
+    }
+    
+    #[test]
+    fn test_reconcile_diverged_apply() {
+        let reconciliation = reconcile_fixtures("reconcile/reviewed_plan.txt", "reconcile/apply_diverged.txt").unwrap();
+        
+        assert!(!reconciliation.is_consistent());
+        // The replaced instance counts as the planned update
+        assert_eq!(reconciliation.matched, vec!["aws_instance.web", "aws_s3_bucket.logs"]);
+        
+        assert_eq!(reconciliation.failed.len(), 1);
+        assert_eq!(reconciliation.failed[0].address, "aws_iam_role.app");
+        assert!(reconciliation.failed[0].errors[0].contains("EntityAlreadyExists"));
+        
+        assert_eq!(reconciliation.action_mismatches, vec![ActionMismatch {
+            address: "aws_security_group.legacy".to_string(),
+            planned: ActionType::Destroy,
+            applied: ActionType::Update,
+        }]);
+        
+        let unplanned: Vec<&str> = reconciliation.unplanned.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(unplanned, vec!["aws_sqs_queue.jobs"]);
+        assert!(reconciliation.not_applied.is_empty());
+        assert!(reconciliation.errors.is_empty());
+    }
+    
+    #[test]
+    fn test_reconcile_json_logs() {
+        let reconciliation = reconcile_fixtures("terraform/plan_log.ndjson", "terraform/apply_log.ndjson").unwrap();
+        
+        assert_eq!(reconciliation.matched, vec!["aws_s3_bucket.logs"]);
+        let not_applied: Vec<&str> = reconciliation.not_applied.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(not_applied, vec!["module.app.aws_instance.web[0]", "google_storage_bucket.old"]);
+        // The role failed to create and the error does not name it
+        assert_eq!(reconciliation.unplanned[0].id, "aws_iam_role.app");
+        assert_eq!(reconciliation.errors, vec!["creating IAM Role (app): EntityAlreadyExists"]);
+    }
+    
+    #[test]
+    fn test_reconcile_rejects_plan_as_apply_log() {
+        let result = reconcile_fixtures("reconcile/reviewed_plan.txt", "reconcile/reviewed_plan.txt");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1 @@
+mod redact_test;
@@ -0,0 +1 @@
+mod risk_test;
@@ -0,0 +1,106 @@
+use tfdiff::risk::*;
+use tfdiff::catalog::ResourceCatalog;
+use tfdiff::config::Config;
+use tfdiff::models::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod risk_tests {
+    use super::*;
+    
+    #[test]
+    fn test_risk_category() {
+        assert_eq!(risk_category("aws_db_instance"), Some(RiskCategory::Database));
+        assert_eq!(risk_category("aws_iam_role_policy"), Some(RiskCategory::Iam));
+        assert_eq!(risk_category("google_project_iam_member"), Some(RiskCategory::Iam));
+        assert_eq!(risk_category("aws_route53_record"), Some(RiskCategory::Dns));
+        assert_eq!(risk_category("aws_route_table"), Some(RiskCategory::Networking));
+        assert_eq!(risk_category("azurerm_mssql_database"), Some(RiskCategory::Database));
+        assert_eq!(risk_category("aws_kms_key"), Some(RiskCategory::Kms));
+        assert_eq!(risk_category("aws_s3_bucket"), None);
+    }
+    
+    #[test]
+    fn test_risk_level_for_score() {
+        assert_eq!(risk_level_for_score(0), RiskLevel::None);
+        assert_eq!(risk_level_for_score(10), RiskLevel::Low);
+        assert_eq!(risk_level_for_score(30), RiskLevel::Medium);
+        assert_eq!(risk_level_for_score(60), RiskLevel::High);
+    }
+    
+    #[test]
+    fn test_assess_resource_by_action() {
+        let create = assess_resource(&sample_create_resource(), 1);
+        assert_eq!((create.score, create.level), (10, RiskLevel::Low));
+        assert_eq!(create.factors, vec!["create"]);
+        
+        let update = assess_resource(&sample_update_resource(), 1);
+        assert_eq!((update.score, update.level), (30, RiskLevel::Medium));
+        
+        let destroy = assess_resource(&sample_destroy_resource(), 1);
+        assert_eq!((destroy.score, destroy.level), (60, RiskLevel::High));
+    }
+    
+    #[test]
+    fn test_assess_plan() {
+        let risk = assess_plan(&guarded_plan());
+        
+        let database = risk.resource("aws_db_instance.main").unwrap();
+        assert_eq!(database.score, 94);
+        assert_eq!(database.factors, vec![
+            "replace", "database", "1 attribute forces replacement", "2 other changes in the root module",
+        ]);
+        
+        let iam = risk.resource("aws_iam_role_policy.deploy").unwrap();
+        assert_eq!((iam.score, iam.level), (54, RiskLevel::Medium));
+        
+        // Alone in its module, so no blast radius
+        let production = risk.resource("module.prod_app.aws_instance.web").unwrap();
+        assert_eq!(production.factors, vec!["destroy"]);
+        
+        // The riskiest change plus 2 for the other high-risk change
+        assert_eq!((risk.score, risk.level), (96, RiskLevel::High));
+    }
+    
+    #[test]
+    fn test_catalog_notes_score_uncategorized_types() {
+        let mut plan = TerraformPlan::default();
+        for (address, type_name) in [("acme_ledger.main", "acme_ledger"), ("aws_db_instance.old", "aws_db_instance")] {
+            let mut resource = sample_destroy_resource();
+            resource.id = address.to_string();
+            resource.type_name = type_name.to_string();
+            plan.resources.push(resource);
+        }
+        
+        // Unknown to the built-in catalog, so only the action counts
+        ResourceCatalog::default().annotate(&mut plan);
+        assert_eq!(assess_plan(&plan).resource("acme_ledger.main").unwrap().factors, vec!["destroy", "1 other change in the root module"]);
+        
+        let config = Config::from_toml("[catalog.acme]\nstateful = [\"acme_*\"]\n").unwrap();
+        let mut catalog = ResourceCatalog::default();
+        catalog.extend(&config.catalog);
+        catalog.annotate(&mut plan);
+        let risk = assess_plan(&plan);
+        
+        let ledger = risk.resource("acme_ledger.main").unwrap();
+        assert_eq!((ledger.score, ledger.level), (87, RiskLevel::High));
+        assert_eq!(ledger.factors[1], "stateful");
+        // A type with a risk category is scored by it alone
+        assert_eq!(risk.resource("aws_db_instance.old").unwrap().factors[1], "database");
+        assert_eq!(risk.resource("aws_db_instance.old").unwrap().score, 87);
+    }
+    
+    #[test]
+    fn test_assess_empty_plan() {
+        let risk = assess_plan(&TerraformPlan::default());
+        assert_eq!((risk.score, risk.level), (0, RiskLevel::None));
+        assert!(risk.resources.is_empty());
+    }
+    
+    #[test]
+    fn test_blast_radius_is_capped() {
+        let risk = assess_resource(&sample_create_resource(), 50);
+        assert_eq!(risk.score, 20);
+        assert_eq!(risk.factors[1], "49 other changes in the root module");
+    }
+}
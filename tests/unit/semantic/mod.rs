@@ -0,0 +1 @@
+mod semantic_test;
@@ -0,0 +1,127 @@
+use tfdiff::semantic::*;
+use tfdiff::models::*;
+use tfdiff::ignore::IgnoreRules;
+use tfdiff::formatter::{format_html_output, format_terminal_output};
+use tfdiff::parser::parse_terraform_output;
+use crate::common::fixtures::*;
+use serde_json::{json, Value};
+
+#[cfg(test)]
+mod semantic_tests {
+    use super::*;
+    
+    fn classify(before: Value, after: Value) -> ChangeClass {
+        classify_change(&Change {
+            path: vec!["value".to_string()],
+            before: Some(before),
+            after: Some(after),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        })
+    }
+    
+    fn cosmetic_plan() -> TerraformPlan {
+        parse_terraform_output(&load_fixture("semantic/cosmetic_plan.json")).unwrap()
+    }
+    
+    #[test]
+    fn test_equivalent_json_documents() {
+        let before = json!(r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Resource":"*"}]}"#);
+        let after = json!("{\n  \"Statement\": [{\"Resource\": \"*\", \"Effect\": \"Allow\"}],\n  \"Version\": \"2012-10-17\"\n}");
+        assert_eq!(classify(before.clone(), after), ChangeClass::Cosmetic(CosmeticReason::EquivalentJson));
+        
+        let changed = json!(r#"{"Version":"2012-10-17","Statement":[{"Effect":"Deny","Resource":"*"}]}"#);
+        assert_eq!(classify(before, changed), ChangeClass::Semantic);
+    }
+    
+    #[test]
+    fn test_equivalent_values() {
+        assert_eq!(classify(json!("1"), json!(1)), ChangeClass::Cosmetic(CosmeticReason::EquivalentValue));
+        assert_eq!(classify(json!(true), json!("true")), ChangeClass::Cosmetic(CosmeticReason::EquivalentValue));
+        assert_eq!(classify(json!({"port": "80"}), json!({"port": 80})), ChangeClass::Cosmetic(CosmeticReason::EquivalentValue));
+        assert_eq!(classify(json!("1"), json!(2)), ChangeClass::Semantic);
+        assert_eq!(classify(json!("web"), json!("web ")), ChangeClass::Semantic);
+    }
+    
+    #[test]
+    fn test_reordered_elements() {
+        assert_eq!(classify(json!(["a", "b"]), json!(["b", "a"])), ChangeClass::Cosmetic(CosmeticReason::ReorderedElements));
+        assert_eq!(classify(json!(["a", "b"]), json!(["a", "c"])), ChangeClass::Semantic);
+        assert_eq!(classify(json!(["a", "a"]), json!(["a"])), ChangeClass::Semantic);
+    }
+    
+    #[test]
+    fn test_nested_ordered_lists_are_semantic() {
+        // Blocks, and lists inside them, may be evaluated in order
+        assert_eq!(classify(json!([{"ports": [443, 80]}]), json!([{"ports": [80, 443]}])), ChangeClass::Semantic);
+        assert_eq!(classify(json!([{"name": "a"}, {"name": "b"}]), json!([{"name": "b"}, {"name": "a"}])), ChangeClass::Semantic);
+        
+        let command = Change {
+            path: vec!["spec".to_string(), "container".to_string(), "0".to_string(), "command".to_string()],
+            before: Some(json!(["sh", "-c"])),
+            after: Some(json!(["-c", "sh"])),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        };
+        assert_eq!(classify_change(&command), ChangeClass::Semantic);
+        
+        // IAM statements are evaluated in order
+        let before = json!(r#"{"Statement":[{"Effect":"Deny"},{"Effect":"Allow"}]}"#);
+        let after = json!(r#"{"Statement":[{"Effect":"Allow"},{"Effect":"Deny"}]}"#);
+        assert_eq!(classify(before, after), ChangeClass::Semantic);
+        let before = json!(r#"["s3:GetObject","s3:PutObject"]"#);
+        let after = json!(r#"["s3:PutObject","s3:GetObject"]"#);
+        assert_eq!(classify(before, after), ChangeClass::Semantic);
+    }
+    
+    #[test]
+    fn test_additions_unknowns_and_secrets_are_semantic() {
+        let mut change = Change {
+            path: vec!["value".to_string()],
+            before: None,
+            after: Some(json!("1")),
+            sensitive: false,
+            computed: false,
+            forces_replacement: false,
+            span: None,
+        };
+        assert_eq!(classify_change(&change), ChangeClass::Semantic);
+        
+        change.before = Some(json!(1));
+        change.sensitive = true;
+        assert_eq!(classify_change(&change), ChangeClass::Semantic);
+        
+        change.sensitive = false;
+        assert!(classify_change(&change).is_cosmetic());
+    }
+    
+    #[test]
+    fn test_formatters_dim_cosmetic_changes() {
+        let plan = cosmetic_plan();
+        
+        let terminal = format_terminal_output(&plan);
+        assert!(terminal.contains("policy (equivalent JSON)"));
+        assert!(terminal.contains("cidr_blocks (reordered elements)"));
+        assert!(terminal.contains("timeout (equivalent value)"));
+        
+        let html = format_html_output(&plan);
+        assert!(html.contains(r#"<span class="line remove cosmetic" title="cosmetic: equivalent JSON">"#));
+    }
+    
+    #[test]
+    fn test_hide_cosmetic_changes() {
+        let mut plan = cosmetic_plan();
+        IgnoreRules { cosmetic_changes: true, ..Default::default() }.apply(&mut plan);
+        
+        // Only the policy description really changes
+        assert_eq!(plan.resources.len(), 1);
+        assert_eq!(plan.resources[0].changes.len(), 1);
+        assert_eq!(plan.resources[0].changes[0].path, vec!["description"]);
+        assert_eq!(plan.suppressed.len(), 4);
+        assert_eq!(plan.suppressed[0].reason, SuppressionReason::Cosmetic { reason: CosmeticReason::EquivalentJson });
+    }
+}
@@ -0,0 +1 @@
+mod template_test;
\ No newline at end of file
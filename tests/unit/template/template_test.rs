@@ -0,0 +1,118 @@
+use tfdiff::config::Config;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use tfdiff::template::*;
+use tfdiff::TfdiffError;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+
+#[cfg(test)]
+mod template_tests {
+    use super::*;
+    
+    fn render(source: &str, plan: &TerraformPlan) -> String {
+        PlanTemplate::new(source, false).unwrap().render(plan).unwrap()
+    }
+    
+    #[test]
+    fn test_render_plan_fields() {
+        let output = render("{{summary.add}}/{{summary.change}}/{{summary.destroy}} {{#each resources}}[{{id}}]{{/each}}", &sample_terraform_plan());
+        
+        assert_eq!(output, "2/1/0 [aws_s3_bucket.test][aws_instance.web]");
+    }
+    
+    #[test]
+    fn test_render_risk() {
+        let output = render("{{risk.level}} {{#each risk.resources}}{{address}}={{level}} {{/each}}", &sample_terraform_plan());
+        
+        assert!(output.starts_with("medium "));
+        assert!(output.contains("aws_instance.web=medium"));
+    }
+    
+    #[test]
+    fn test_action_helpers() {
+        let mut plan = sample_terraform_plan();
+        plan.resources.push(sample_destroy_resource());
+        let output = render("{{#each resources}}{{action_icon this}} {{action this}}\n{{/each}}{{action_icon \"no-op\"}}", &plan);
+        
+        assert_eq!(output, "➕ created\n🔄 updated in-place\n❌ destroyed\n⭕");
+    }
+    
+    #[test]
+    fn test_diff_helper() {
+        let plan = parse_terraform_output(&load_fixture("terraform/complex_update.txt")).unwrap();
+        let output = render("{{#each resources}}{{#if (eq id \"aws_instance.web_server\")}}{{diff this}}{{/if}}{{/each}}", &plan);
+        
+        assert!(output.contains("~ user_data = \"c765373c563b260626d113c4a56a46e8a8248872\" -> \"a123b456c789d012e345f678g901h234i567j890\" # forces replacement"));
+    }
+    
+    #[test]
+    fn test_value_helpers() {
+        let plan = sample_terraform_plan();
+        let output = render(
+            "{{#with (lookup resources 0)}}{{value attributes.bucket}} {{value attributes.bucket max=4}} {{value attributes.force_destroy}} {{json attributes.bucket}}{{/with}}",
+            &plan,
+        );
+        
+        assert_eq!(output, "test-bucket test… false \"test-bucket\"");
+    }
+    
+    #[test]
+    fn test_money_and_join_helpers() {
+        let plan = sample_terraform_plan();
+        
+        assert_eq!(render("{{money 1234.5}} {{money 3 currency=\"EUR\"}}", &plan), "$1,234.50 €3.00");
+        assert_eq!(render("{{#with (lookup resources 1)}}{{#with (lookup changes 0)}}{{join path \".\"}}{{/with}}{{/with}}", &plan), "instance_type");
+    }
+    
+    #[test]
+    fn test_escaping() {
+        let mut plan = sample_terraform_plan();
+        plan.resources[0].id = "aws_s3_bucket.test[\"<b>\"]".to_string();
+        let source = "{{#with (lookup resources 0)}}{{id}}{{/with}}";
+        
+        assert_eq!(PlanTemplate::new(source, false).unwrap().render(&plan).unwrap(), "aws_s3_bucket.test[\"<b>\"]");
+        assert_eq!(PlanTemplate::new(source, true).unwrap().render(&plan).unwrap(), "aws_s3_bucket.test[&quot;&lt;b&gt;&quot;]");
+    }
+    
+    #[test]
+    fn test_load_escapes_html_files() {
+        let plan = sample_terraform_plan();
+        let output = PlanTemplate::load(&fixture_path("template/report.html")).unwrap().render(&plan).unwrap();
+        
+        assert!(output.contains("<li>aws_s3_bucket.test: test-bucket</li>"));
+    }
+    
+    #[test]
+    fn test_load_change_ticket() {
+        let mut plan = parse_terraform_output(&load_fixture("cost/cost_plan.txt")).unwrap();
+        tfdiff::PriceTable::builtin().price(&mut plan);
+        let output = PlanTemplate::load(&fixture_path("template/change_ticket.hbs")).unwrap().render(&plan).unwrap();
+        
+        assert!(output.starts_with("CHANGE REQUEST\nSummary: 2 to add, 2 to change, 1 to destroy\nRisk: high ("));
+        assert!(output.contains("Monthly cost: $47.56 -> $76.32"));
+        assert!(output.contains("❌ aws_nat_gateway.main will be destroyed\n- id = \"nat-1\"\n"));
+    }
+    
+    #[test]
+    fn test_invalid_template() {
+        let Err(TfdiffError::TemplateError(message)) = PlanTemplate::new("{{#each resources}", false) else {
+            panic!("expected a template error");
+        };
+        assert!(message.contains("invalid handlebars syntax"));
+    }
+    
+    #[test]
+    fn test_unknown_helper() {
+        let template = PlanTemplate::new("{{shout summary}}", false).unwrap();
+        
+        assert!(matches!(template.render(&sample_terraform_plan()), Err(TfdiffError::TemplateError(_))));
+    }
+    
+    #[test]
+    fn test_config_template() {
+        let config = Config::from_toml("template = \"templates/ticket.hbs\"").unwrap();
+        
+        assert_eq!(config.template.unwrap(), std::path::PathBuf::from("templates/ticket.hbs"));
+    }
+}
@@ -0,0 +1,85 @@
+use tfdiff::ui::*;
+use tfdiff::models::*;
+use tfdiff::parser::parse_terraform_output;
+use tfdiff::risk::assess_plan;
+use crate::common::fixtures::*;
+use crate::common::test_data::*;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod plan_store_tests {
+    use super::*;
+    
+    #[test]
+    fn test_in_memory_store_lists_newest_first() {
+        let mut store = PlanStore::in_memory();
+        assert!(store.is_empty());
+        
+        store.insert(Some("staging".to_string()), sample_terraform_plan()).unwrap();
+        store.insert(None, TerraformPlan::default()).unwrap();
+        
+        let listings = store.list();
+        assert_eq!(store.len(), 2);
+        assert_eq!(listings[0].id, 2);
+        assert_eq!(listings[0].name, "Plan 2");
+        assert_eq!(listings[0].risk, RiskLevel::None);
+        assert_eq!(listings[0].risk_score, 0);
+        assert_eq!(listings[1].name, "staging");
+        assert_eq!(listings[1].summary, sample_terraform_plan().summary);
+    }
+    
+    #[test]
+    fn test_on_disk_store_reloads_plans() {
+        let dir = TempDir::new().unwrap();
+        
+        {
+            let mut store = PlanStore::on_disk(dir.path()).unwrap();
+            store.insert(Some("release".to_string()), sample_terraform_plan()).unwrap();
+        }
+        
+        let mut store = PlanStore::on_disk(dir.path()).unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get(1).unwrap().name, "release");
+        assert_eq!(store.get(1).unwrap().plan.resources.len(), sample_terraform_plan().resources.len());
+        
+        // Ids continue after the reloaded plans
+        assert_eq!(store.insert(None, TerraformPlan::default()).unwrap().id, 2);
+    }
+    
+    #[test]
+    fn test_listing_risk_matches_the_report() {
+        let mut store = PlanStore::in_memory();
+        let plan = parse_terraform_output(&load_terraform_fixture("module_plan.txt")).unwrap();
+        store.insert(None, plan.clone()).unwrap();
+        
+        let risk = assess_plan(&plan);
+        let listing = &store.list()[0];
+        assert_eq!(listing.risk, risk.level);
+        assert_eq!(listing.risk_score, risk.score);
+        
+        let html = render_dashboard(&store.list(), false);
+        assert!(html.contains(&format!("{} ({})</span>", risk.level, risk.score)));
+    }
+    
+    #[test]
+    fn test_risk_level_display() {
+        assert_eq!(RiskLevel::None.to_string(), "none");
+        assert_eq!(RiskLevel::High.to_string(), "high");
+    }
+    
+    #[test]
+    fn test_render_dashboard() {
+        let mut store = PlanStore::in_memory();
+        store.insert(Some("<release>".to_string()), sample_terraform_plan()).unwrap();
+        
+        let html = render_dashboard(&store.list(), false);
+        assert!(html.contains(r#"<a href="/plans/1">&lt;release&gt;</a>"#));
+        assert!(html.contains("risk-"));
+        assert!(html.contains("upload-form"));
+        assert!(!html.contains("View the plan this server was started with"));
+        
+        let empty = render_dashboard(&[], true);
+        assert!(empty.contains("No plans uploaded yet"));
+        assert!(empty.contains("View the plan this server was started with"));
+    }
+}